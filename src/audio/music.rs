@@ -44,7 +44,11 @@ use ffi::audio::music as ffi;
 /// Musics are sounds that are streamed rather than completely loaded in memory.
 pub struct Music {
     #[doc(hidden)]
-    music: *mut ffi::sfMusic
+    music: *mut ffi::sfMusic,
+    // The data streamed by a music created from a Vec
+    #[doc(hidden)]
+    #[allow(dead_code)]
+    data: Option<Vec<u8>>
 }
 
 impl Music {
@@ -71,7 +75,8 @@ impl Music {
             None
         } else {
             Some(Music{
-                    music: music_tmp
+                    music: music_tmp,
+                    data: None
                 })
         }
     }
@@ -94,7 +99,32 @@ impl Music {
             None
         } else {
             Some(Music{
-                    music: music_tmp
+                    music: music_tmp,
+                    data: None
+                })
+        }
+    }
+
+    /// Create a new music from a file loaded in memory, owned by the music
+    ///
+    /// A music is streamed from its data while it plays, so the data is
+    /// kept with the music and freed when the music is destroyed.
+    ///
+    /// # Arguments
+    /// * data - The in-memory music file
+    ///
+    /// Return Some(Music) or None
+    pub fn new_from_vec(data: Vec<u8>) -> Option<Music> {
+        if data.is_empty() {
+            return None;
+        }
+        let music_tmp = unsafe { ffi::sfMusic_createFromMemory(&data[0], data.len() as size_t) };
+        if music_tmp.is_null() {
+            None
+        } else {
+            Some(Music{
+                    music: music_tmp,
+                    data: Some(data)
                 })
         }
     }
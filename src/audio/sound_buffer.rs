@@ -26,6 +26,7 @@
 //!
 //! A sound buffer holds the data of a sound, which is an array of audio samples.
 
+use libc::{c_void, size_t};
 use std::ptr;
 
 use traits::Wrappable;
@@ -72,6 +73,32 @@ impl SoundBuffer {
         }
     }
 
+    /// Create a new sound buffer and load it from a file in memory
+    ///
+    /// The supported audio formats are the same as for new.
+    ///
+    /// # Arguments
+    /// * mem - Pointer to the file data in memory
+    ///
+    /// Return an option to a SoundBuffer object or None.
+    pub fn new_from_memory(mem: &[u8]) -> Option<SoundBuffer> {
+        if mem.is_empty() {
+            return None;
+        }
+        let sound_buffer = unsafe {
+            ffi::sfSoundBuffer_createFromMemory(mem.as_ptr() as *const c_void,
+                                                mem.len() as size_t)
+        };
+        if sound_buffer.is_null() {
+            None
+        } else {
+            Some(SoundBuffer{
+                    sound_buffer: sound_buffer,
+                    dropable: true
+                })
+        }
+    }
+
     /// Create a new sound buffer by copying an existing one
     ///
     /// Return an option to a cloned SoundBuffer object or None.
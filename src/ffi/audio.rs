@@ -142,6 +142,7 @@ pub mod sound_buffer {
 
     extern "C" {
         pub fn sfSoundBuffer_createFromFile(filename: *const c_char) -> *mut sfSoundBuffer;
+        pub fn sfSoundBuffer_createFromMemory(data: *const c_void, sizeInBytes: size_t) -> *mut sfSoundBuffer;
         pub fn sfSoundBuffer_copy(soundBuffer: *mut sfSoundBuffer) -> *mut sfSoundBuffer;
         pub fn sfSoundBuffer_destroy(soundBuffer: *mut sfSoundBuffer) -> ();
         pub fn sfSoundBuffer_saveToFile(soundBuffer: *mut sfSoundBuffer, filename: *const c_char) -> SfBool;
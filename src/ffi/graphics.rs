@@ -278,20 +278,6 @@ pub mod image {
     }
 }
 
-pub mod rect {
-    use libc::{c_int};
-    use graphics::{FloatRect, IntRect};
-
-    use ffi::sfml_types::{SfBool};
-
-    extern "C" {
-        pub fn sfIntRect_contains(rect: *const IntRect, x: c_int, y: c_int) -> SfBool;
-        pub fn sfIntRect_intersects(rect1: *const IntRect, rect2: *const IntRect, intersectons: *const IntRect) -> SfBool;
-        pub fn sfFloatRect_intersects(rect1: *const FloatRect, rect2: *const FloatRect, intersectons: *const FloatRect) -> SfBool;
-        pub fn sfFloatRect_contains(rect: *const FloatRect, x: f32, y: f32) -> SfBool;
-    }
-}
-
 pub mod rectangle_shape {
     use libc::{c_void, c_float, c_uint};
 
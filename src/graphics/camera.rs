@@ -0,0 +1,497 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! 2D camera built on top of a View
+//!
+//! A camera follows a target with an optional dead zone and smoothing,
+//! stays inside the world bounds, zooms toward a point, shakes and eases
+//! its rotation. It is advanced every frame with update and applied to a
+//! render target with apply.
+
+use std::num::Float;
+
+use graphics::{View, FloatRect, RenderTarget};
+use system::vector2::{Vector2f, Vector2i};
+use system::Time;
+
+/// 2D camera built on top of a View
+pub struct Camera {
+    #[doc(hidden)]
+    view: View,
+    #[doc(hidden)]
+    position: Vector2f,
+    #[doc(hidden)]
+    size: Vector2f,
+    #[doc(hidden)]
+    zoom: f32,
+    #[doc(hidden)]
+    target: Option<Vector2f>,
+    #[doc(hidden)]
+    dead_zone: Vector2f,
+    #[doc(hidden)]
+    smoothing: f32,
+    #[doc(hidden)]
+    bounds: Option<FloatRect>,
+    #[doc(hidden)]
+    rotation: f32,
+    #[doc(hidden)]
+    target_rotation: f32,
+    #[doc(hidden)]
+    rotation_speed: f32,
+    #[doc(hidden)]
+    shake_intensity: f32,
+    #[doc(hidden)]
+    shake_duration: f32,
+    #[doc(hidden)]
+    shake_remaining: f32,
+    #[doc(hidden)]
+    shake_offset: Vector2f,
+    #[doc(hidden)]
+    seed: u32
+}
+
+impl Camera {
+    /// Create a new camera
+    ///
+    /// # Arguments
+    /// * center - The center of the camera
+    /// * size - The size of the area shown by the camera
+    ///
+    /// Return Some(Camera) or None
+    pub fn new(center: &Vector2f, size: &Vector2f) -> Option<Camera> {
+        match View::new_init(center, size) {
+            Some(view) => Some(Camera {
+                view: view,
+                position: *center,
+                size: *size,
+                zoom: 1.,
+                target: None,
+                dead_zone: Vector2f::new(0., 0.),
+                smoothing: 0.,
+                bounds: None,
+                rotation: 0.,
+                target_rotation: 0.,
+                rotation_speed: 0.,
+                shake_intensity: 0.,
+                shake_duration: 0.,
+                shake_remaining: 0.,
+                shake_offset: Vector2f::new(0., 0.),
+                seed: 0x9E3779B9
+            }),
+            None => None
+        }
+    }
+
+    /// Create a new camera showing a rectangle
+    ///
+    /// # Arguments
+    /// * rectangle - The rectangle shown by the camera
+    ///
+    /// Return Some(Camera) or None
+    pub fn new_from_rect(rectangle: &FloatRect) -> Option<Camera> {
+        Camera::new(&rectangle.get_center(), &rectangle.get_size())
+    }
+
+    /// Get the view of the camera, as of the last update
+    ///
+    /// Return a reference to the View
+    pub fn get_view(&self) -> &View {
+        &self.view
+    }
+
+    /// Apply the camera to a render target
+    ///
+    /// # Arguments
+    /// * render_target - The target on which the view is set
+    pub fn apply<RT: RenderTarget>(&self, render_target: &mut RT) -> () {
+        render_target.set_view(&self.view)
+    }
+
+    /// Move the camera immediately, without smoothing
+    ///
+    /// The position is still clamped to the bounds.
+    ///
+    /// # Arguments
+    /// * position - The new center of the camera
+    pub fn set_position(&mut self, position: &Vector2f) -> () {
+        self.position = *position;
+        self.clamp_to_bounds();
+        self.sync_view();
+    }
+
+    /// Get the center of the camera, without shake
+    ///
+    /// Return the position of the camera
+    pub fn get_position(&self) -> Vector2f {
+        self.position
+    }
+
+    /// Set the size of the area shown at zoom 1
+    ///
+    /// # Arguments
+    /// * size - The new size
+    pub fn set_size(&mut self, size: &Vector2f) -> () {
+        self.size = *size;
+        self.clamp_to_bounds();
+        self.sync_view();
+    }
+
+    /// Get the size of the area shown at zoom 1
+    ///
+    /// Return the size of the camera
+    pub fn get_size(&self) -> Vector2f {
+        self.size
+    }
+
+    /// Get the size of the area currently shown, zoom included
+    ///
+    /// Return the visible size
+    pub fn get_visible_size(&self) -> Vector2f {
+        self.size * self.zoom
+    }
+
+    /// Follow a target
+    ///
+    /// Call it every frame with the position of the target, the
+    /// camera moves toward it on update.
+    ///
+    /// # Arguments
+    /// * target - The position to follow
+    pub fn follow(&mut self, target: &Vector2f) -> () {
+        self.target = Some(*target);
+    }
+
+    /// Stop following the target
+    pub fn stop_following(&mut self) -> () {
+        self.target = None;
+    }
+
+    /// Get the followed target
+    ///
+    /// Return Some(target) or None if the camera follows nothing
+    pub fn get_target(&self) -> Option<Vector2f> {
+        self.target
+    }
+
+    /// Set the size of the dead zone
+    ///
+    /// The dead zone is a rectangle centered on the camera inside of
+    /// which the target can move without moving the camera.
+    /// The default dead zone is empty.
+    ///
+    /// # Arguments
+    /// * size - The size of the dead zone
+    pub fn set_dead_zone(&mut self, size: &Vector2f) -> () {
+        self.dead_zone = Vector2f::new(size.x.max(0.), size.y.max(0.));
+    }
+
+    /// Get the size of the dead zone
+    ///
+    /// Return the size of the dead zone
+    pub fn get_dead_zone(&self) -> Vector2f {
+        self.dead_zone
+    }
+
+    /// Set the smoothing applied when following the target
+    ///
+    /// The camera covers the distance to its destination exponentially,
+    /// the higher the value the faster. 0 (the default) disables
+    /// smoothing and the camera snaps to the target.
+    ///
+    /// # Arguments
+    /// * smoothing - The smoothing rate, per second
+    pub fn set_smoothing(&mut self, smoothing: f32) -> () {
+        self.smoothing = smoothing.max(0.);
+    }
+
+    /// Get the smoothing applied when following the target
+    ///
+    /// Return the smoothing rate, per second
+    pub fn get_smoothing(&self) -> f32 {
+        self.smoothing
+    }
+
+    /// Keep the visible area inside the world bounds
+    ///
+    /// If the bounds are smaller than the visible area, the camera is
+    /// centered on them. The rotation of the camera is ignored.
+    ///
+    /// # Arguments
+    /// * bounds - The world bounds
+    pub fn set_bounds(&mut self, bounds: &FloatRect) -> () {
+        self.bounds = Some(*bounds);
+        self.clamp_to_bounds();
+        self.sync_view();
+    }
+
+    /// Remove the world bounds
+    pub fn clear_bounds(&mut self) -> () {
+        self.bounds = None;
+    }
+
+    /// Get the world bounds
+    ///
+    /// Return Some(bounds) or None if the camera is not bounded
+    pub fn get_bounds(&self) -> Option<FloatRect> {
+        self.bounds
+    }
+
+    /// Set the zoom of the camera
+    ///
+    /// # Arguments
+    /// * zoom - The new zoom, strictly positive, multiplying the size shown:
+    ///          1 shows the size of the camera, more than 1 shows more (objects
+    ///          appear smaller) and less than 1 shows less (objects appear bigger)
+    pub fn set_zoom(&mut self, zoom: f32) -> () {
+        if zoom > 0. {
+            self.zoom = zoom;
+            self.clamp_to_bounds();
+            self.sync_view();
+        }
+    }
+
+    /// Get the zoom of the camera
+    ///
+    /// Return the zoom factor
+    pub fn get_zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Zoom toward a point of the screen
+    ///
+    /// The world point under the pixel stays under it after the zoom,
+    /// which makes it suitable for zooming with the mouse wheel.
+    ///
+    /// # Arguments
+    /// * factor - The zoom factor to apply, relatively to the current zoom
+    /// * pixel - The pixel to zoom toward
+    /// * render_target - The target on which the camera is applied
+    pub fn zoom_at<RT: RenderTarget>(&mut self,
+                                     factor: f32,
+                                     pixel: &Vector2i,
+                                     render_target: &RT) -> () {
+        if factor <= 0. {
+            return;
+        }
+        let before = render_target.map_pixel_to_coords(pixel, &self.view);
+        self.zoom *= factor;
+        self.sync_view();
+        let after = render_target.map_pixel_to_coords(pixel, &self.view);
+        self.position = self.position + (before - after);
+        self.clamp_to_bounds();
+        self.sync_view();
+    }
+
+    /// Set the rotation of the camera immediately
+    ///
+    /// # Arguments
+    /// * angle - The new angle, in degrees
+    pub fn set_rotation(&mut self, angle: f32) -> () {
+        self.rotation = angle;
+        self.target_rotation = angle;
+        self.sync_view();
+    }
+
+    /// Rotate the camera toward an angle
+    ///
+    /// The camera turns in the shortest direction, at the speed set
+    /// with set_rotation_speed. If the speed is 0 the rotation is immediate.
+    ///
+    /// # Arguments
+    /// * angle - The angle to reach, in degrees
+    pub fn rotate_to(&mut self, angle: f32) -> () {
+        self.target_rotation = angle;
+    }
+
+    /// Get the current rotation of the camera
+    ///
+    /// Return the angle, in degrees
+    pub fn get_rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// Set the speed of the rotation easing
+    ///
+    /// # Arguments
+    /// * speed - The easing rate, per second (0 makes rotations immediate)
+    pub fn set_rotation_speed(&mut self, speed: f32) -> () {
+        self.rotation_speed = speed.max(0.);
+    }
+
+    /// Get the speed of the rotation easing
+    ///
+    /// Return the easing rate, per second
+    pub fn get_rotation_speed(&self) -> f32 {
+        self.rotation_speed
+    }
+
+    /// Shake the camera
+    ///
+    /// The amplitude of the shake decays linearly to 0 over the duration.
+    /// A stronger shake replaces the current one.
+    ///
+    /// # Arguments
+    /// * intensity - The maximum offset, in world units
+    /// * duration - The duration of the shake
+    pub fn shake(&mut self, intensity: f32, duration: Time) -> () {
+        let duration = duration.as_seconds();
+        if duration <= 0. || intensity <= 0. {
+            return;
+        }
+        if intensity >= self.current_shake_intensity() {
+            self.shake_intensity = intensity;
+            self.shake_duration = duration;
+            self.shake_remaining = duration;
+        }
+    }
+
+    /// Stop shaking the camera
+    pub fn stop_shaking(&mut self) -> () {
+        self.shake_remaining = 0.;
+        self.shake_offset = Vector2f::new(0., 0.);
+        self.sync_view();
+    }
+
+    /// Tell whether the camera is shaking
+    ///
+    /// Return true if the camera is shaking, false otherwise
+    pub fn is_shaking(&self) -> bool {
+        self.shake_remaining > 0.
+    }
+
+    /// Advance the camera
+    ///
+    /// # Arguments
+    /// * time - The time elapsed since the last update
+    pub fn update(&mut self, time: Time) -> () {
+        let dt = time.as_seconds().max(0.);
+
+        match self.target {
+            Some(target) => {
+                let destination = self.dead_zone_destination(&target);
+                let factor = approach_factor(self.smoothing, dt);
+                self.position = self.position + (destination - self.position) * factor;
+            },
+            None => {}
+        }
+        self.clamp_to_bounds();
+
+        let mut delta = (self.target_rotation - self.rotation) % 360.;
+        if delta > 180. {
+            delta -= 360.;
+        } else if delta < -180. {
+            delta += 360.;
+        }
+        if delta.abs() < 0.01 {
+            self.rotation = self.target_rotation;
+        } else {
+            self.rotation += delta * approach_factor(self.rotation_speed, dt);
+        }
+
+        if self.shake_remaining > 0. {
+            self.shake_remaining = (self.shake_remaining - dt).max(0.);
+            let intensity = self.current_shake_intensity();
+            let x = self.next_random();
+            let y = self.next_random();
+            self.shake_offset = Vector2f::new(x * intensity, y * intensity);
+        } else {
+            self.shake_offset = Vector2f::new(0., 0.);
+        }
+
+        self.sync_view();
+    }
+
+    // Position the camera must reach to keep the target in the dead zone
+    #[doc(hidden)]
+    fn dead_zone_destination(&self, target: &Vector2f) -> Vector2f {
+        let half = self.dead_zone * 0.5;
+        let mut destination = self.position;
+        if target.x < self.position.x - half.x {
+            destination.x = target.x + half.x;
+        } else if target.x > self.position.x + half.x {
+            destination.x = target.x - half.x;
+        }
+        if target.y < self.position.y - half.y {
+            destination.y = target.y + half.y;
+        } else if target.y > self.position.y + half.y {
+            destination.y = target.y - half.y;
+        }
+        destination
+    }
+
+    #[doc(hidden)]
+    fn clamp_to_bounds(&mut self) -> () {
+        let bounds = match self.bounds {
+            Some(bounds) => bounds,
+            None => return
+        };
+        let half = self.get_visible_size() * 0.5;
+        self.position.x = clamp_axis(self.position.x, bounds.left, bounds.width, half.x);
+        self.position.y = clamp_axis(self.position.y, bounds.top, bounds.height, half.y);
+    }
+
+    #[doc(hidden)]
+    fn current_shake_intensity(&self) -> f32 {
+        if self.shake_remaining <= 0. {
+            0.
+        } else {
+            self.shake_intensity * self.shake_remaining / self.shake_duration
+        }
+    }
+
+    // Xorshift, mapped to -1 .. 1
+    #[doc(hidden)]
+    fn next_random(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        (self.seed as f32 / 4294967295.) * 2. - 1.
+    }
+
+    #[doc(hidden)]
+    fn sync_view(&mut self) -> () {
+        self.view.set_center(&(self.position + self.shake_offset));
+        self.view.set_size(&self.get_visible_size());
+        self.view.set_rotation(self.rotation);
+    }
+}
+
+// Fraction of the remaining distance covered during dt
+#[doc(hidden)]
+fn approach_factor(rate: f32, dt: f32) -> f32 {
+    if rate <= 0. {
+        1.
+    } else {
+        1. - (-rate * dt).exp()
+    }
+}
+
+#[doc(hidden)]
+fn clamp_axis(center: f32, start: f32, length: f32, half: f32) -> f32 {
+    if length <= half * 2. {
+        start + length / 2.
+    } else {
+        center.max(start + half).min(start + length - half)
+    }
+}
@@ -27,7 +27,7 @@
 use libc::{c_float, c_uint};
 use std::ptr;
 
-use traits::{Drawable, Wrappable};
+use traits::{Drawable, Wrappable, Bounded};
 use graphics::{IntRect, FloatRect, Color, Texture,
                RenderTarget, Transform, RenderStates};
 use system::vector2::Vector2f;
@@ -621,6 +621,18 @@ impl<'s> Wrappable<*mut ffi::sfCircleShape> for CircleShape<'s> {
     }
 }
 
+impl_transformable!(CircleShape<'s>, 's);
+
+impl<'s> Bounded for CircleShape<'s> {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
 impl<'s> Drawable for CircleShape<'s> {
     fn draw<RT:RenderTarget>(&self, render_target: &mut RT) -> () {
         render_target.draw_circle_shape(self)
@@ -29,7 +29,7 @@ use std::cell::RefCell;
 use libc::{c_float, c_uint};
 use std::ptr;
 
-use traits::{Drawable, Wrappable};
+use traits::{Drawable, Wrappable, Bounded};
 use graphics::{IntRect, FloatRect, Color, Texture,
                RenderTarget, Transform, rc};
 use system::vector2::Vector2f;
@@ -624,6 +624,18 @@ impl Wrappable<*mut ffi::sfCircleShape> for CircleShape {
     }
 }
 
+impl_transformable!(CircleShape);
+
+impl Bounded for CircleShape {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
 impl Drawable for CircleShape {
     fn draw<RT:RenderTarget>(&self, render_target: &mut RT) -> () {
         render_target.draw_circle_shape_rc(self)
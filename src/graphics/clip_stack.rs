@@ -0,0 +1,181 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Stack of clipping rectangles shared by the render targets
+//!
+//! SFML has no scissor test, so a clip is applied by giving the target a
+//! view whose viewport is the clipping rectangle and which shows the part
+//! of the world that the view of the user shows in that rectangle. Pixels
+//! and world coordinates map the same way through both views.
+
+use std::num::Float;
+use std::f32::consts::PI;
+
+use graphics::{View, IntRect, FloatRect};
+use system::vector2::{Vector2f, Vector2u};
+
+/// Stack of clipping rectangles, in target pixels
+pub struct ClipStack {
+    #[doc(hidden)]
+    clips: Vec<Option<IntRect>>,
+    #[doc(hidden)]
+    view: Option<View>,
+    #[doc(hidden)]
+    target_size: Vector2u
+}
+
+impl ClipStack {
+    /// Create an empty stack
+    pub fn new() -> ClipStack {
+        ClipStack {
+            clips: Vec::new(),
+            view: None,
+            target_size: Vector2u::new(0, 0)
+        }
+    }
+
+    /// Tell whether no clip is active
+    pub fn is_empty(&self) -> bool {
+        self.clips.is_empty()
+    }
+
+    /// Push a clip, intersected with the current one
+    ///
+    /// # Arguments
+    /// * rect - The clipping rectangle, in target pixels
+    /// * view - The view of the user, kept by the first push
+    pub fn push(&mut self, rect: &IntRect, view: &View) -> () {
+        let clip = match self.clips.last() {
+            Some(&Some(ref current)) => current.intersection(rect),
+            Some(&None) => None,
+            None => {
+                self.view = Some(view.clone());
+                // Normalize the rectangle, None if it is empty
+                rect.intersection(rect)
+            }
+        };
+        self.clips.push(clip);
+    }
+
+    /// Pop the current clip
+    ///
+    /// Return the view of the user if the last clip was popped, to restore it
+    pub fn pop(&mut self) -> Option<View> {
+        self.clips.pop();
+        if self.clips.is_empty() {
+            self.view.take()
+        } else {
+            None
+        }
+    }
+
+    /// Get the current clip
+    ///
+    /// Return Some(rect), or None if no clip is active or if the nested
+    /// clips don't intersect
+    pub fn get_clip(&self) -> Option<IntRect> {
+        match self.clips.last() {
+            Some(&Some(clip)) => Some(clip),
+            _ => None
+        }
+    }
+
+    /// Get the view of the user while a clip is active
+    pub fn get_view(&self) -> Option<&View> {
+        self.view.as_ref()
+    }
+
+    /// Replace the view of the user while a clip is active
+    pub fn set_view(&mut self, view: &View) -> () {
+        if !self.clips.is_empty() {
+            self.view = Some(view.clone());
+        }
+    }
+
+    /// Tell whether the clip must be applied again for a target size
+    ///
+    /// # Arguments
+    /// * target_size - The current size of the target, in pixels
+    pub fn is_outdated(&self, target_size: &Vector2u) -> bool {
+        !self.clips.is_empty() && self.target_size != *target_size
+    }
+
+    /// Compute the view which clips the view of the user
+    ///
+    /// The target size is remembered for is_outdated.
+    ///
+    /// # Arguments
+    /// * viewport - The viewport of the view of the user, in target pixels
+    /// * target_size - The size of the target, in pixels
+    ///
+    /// Return Some(view), or None if no clip is active
+    pub fn get_clipped_view(&mut self,
+                            viewport: &IntRect,
+                            target_size: &Vector2u) -> Option<View> {
+        self.target_size = *target_size;
+        let view = match self.view {
+            Some(ref view) => view,
+            None => return None
+        };
+        let mut clipped = view.clone();
+        let clip = match self.get_clip() {
+            Some(clip) => clip.intersection(viewport),
+            None => None
+        };
+        let clip = match clip {
+            Some(clip) if target_size.x > 0 && target_size.y > 0 => clip,
+            _ => {
+                // Nothing may be drawn, an empty viewport draws nothing
+                clipped.set_viewport(&FloatRect::new(0., 0., 0., 0.));
+                return Some(clipped);
+            }
+        };
+        let width = target_size.x as f32;
+        let height = target_size.y as f32;
+        clipped.set_viewport(&FloatRect::new(clip.left as f32 / width,
+                                             clip.top as f32 / height,
+                                             clip.width as f32 / width,
+                                             clip.height as f32 / height));
+
+        // World units per pixel along each axis of the view
+        let size = view.get_size();
+        let scale_x = size.x / viewport.width as f32;
+        let scale_y = size.y / viewport.height as f32;
+        clipped.set_size(&Vector2f::new(clip.width as f32 * scale_x,
+                                        clip.height as f32 * scale_y));
+
+        // Move the center by the offset between the centers of the clip and
+        // of the viewport, rotated like the view
+        let offset_x = (clip.left as f32 + clip.width as f32 / 2. -
+                        viewport.left as f32 - viewport.width as f32 / 2.) * scale_x;
+        let offset_y = (clip.top as f32 + clip.height as f32 / 2. -
+                        viewport.top as f32 - viewport.height as f32 / 2.) * scale_y;
+        let angle = view.get_rotation() * PI / 180.;
+        let (sin, cos) = (angle.sin(), angle.cos());
+        let center = view.get_center();
+        clipped.set_center(&Vector2f::new(center.x + offset_x * cos - offset_y * sin,
+                                          center.y + offset_x * sin + offset_y * cos));
+        Some(clipped)
+    }
+}
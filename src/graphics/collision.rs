@@ -0,0 +1,546 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Collision detection with the separating axis theorem
+//!
+//! Shapes, sprites and rectangles are turned into a Collider in world
+//! coordinates, which can then be tested against each other, against a
+//! point, or against a ray. Polygon colliders must be convex.
+
+use std::num::Float;
+use std::f32;
+
+use traits::ShapeImpl;
+use graphics::{ConvexShape, RectangleShape, CircleShape, Shape, Sprite, Transform,
+               FloatRect};
+use graphics::geometry;
+use system::vector2::Vector2f;
+
+pub use self::Collider::{PolygonCollider, CircleCollider};
+
+/// Convex area used for collision detection, in world coordinates
+#[deriving(Clone, PartialEq, Show)]
+pub enum Collider {
+    /// Convex polygon
+    PolygonCollider {
+        /// The points of the polygon, in any winding
+        points: Vec<Vector2f>
+    },
+    /// Circle
+    CircleCollider {
+        /// The center of the circle
+        center: Vector2f,
+        /// The radius of the circle
+        radius: f32
+    }
+}
+
+/// Result of an overlap test between two colliders
+#[deriving(Clone, PartialEq, Show, Copy)]
+pub struct Contact {
+    /// Unit vector of the axis of least penetration, pointing from the
+    /// first collider to the second one
+    pub normal: Vector2f,
+    /// Penetration depth along the normal
+    pub depth: f32,
+    /// Minimum translation vector: move the first collider by this vector
+    /// (or the second by its opposite) to separate them
+    pub mtv: Vector2f
+}
+
+/// Result of a ray or segment cast against a collider
+#[deriving(Clone, PartialEq, Show, Copy)]
+pub struct RayHit {
+    /// Distance from the origin of the ray to the hit point
+    pub distance: f32,
+    /// The point where the ray enters the collider
+    pub point: Vector2f,
+    /// Unit normal of the collider at the hit point
+    pub normal: Vector2f
+}
+
+impl Collider {
+    /// Create a collider from a convex polygon
+    ///
+    /// # Arguments
+    /// * points - The points of the polygon, in world coordinates
+    ///
+    /// Return a new Collider
+    pub fn new_polygon(points: &[Vector2f]) -> Collider {
+        PolygonCollider { points: points.to_vec() }
+    }
+
+    /// Create a circle collider
+    ///
+    /// # Arguments
+    /// * center - The center of the circle, in world coordinates
+    /// * radius - The radius of the circle
+    ///
+    /// Return a new Collider
+    pub fn new_circle(center: &Vector2f, radius: f32) -> Collider {
+        CircleCollider { center: *center, radius: radius }
+    }
+
+    /// Create a collider from an axis aligned rectangle
+    ///
+    /// Use it with the global bounds of any drawable.
+    ///
+    /// # Arguments
+    /// * rect - The rectangle, in world coordinates
+    ///
+    /// Return a new Collider
+    pub fn from_rect(rect: &FloatRect) -> Collider {
+        Collider::new_polygon(&rect_points(rect))
+    }
+
+    /// Create a collider from an oriented rectangle
+    ///
+    /// # Arguments
+    /// * center - The center of the rectangle
+    /// * size - The size of the rectangle
+    /// * rotation - The rotation of the rectangle around its center, in degrees
+    ///
+    /// Return a new Collider
+    pub fn from_oriented_rect(center: &Vector2f,
+                              size: &Vector2f,
+                              rotation: f32) -> Collider {
+        let angle = geometry::to_radians(rotation);
+        let x_axis = Vector2f::new(angle.cos(), angle.sin()) * (size.x / 2.);
+        let y_axis = geometry::perpendicular(Vector2f::new(angle.cos(), angle.sin()))
+            * (size.y / 2.);
+        PolygonCollider {
+            points: vec![*center - x_axis - y_axis,
+                         *center + x_axis - y_axis,
+                         *center + x_axis + y_axis,
+                         *center - x_axis + y_axis]
+        }
+    }
+
+    /// Create a collider from a ConvexShape and its transformation
+    ///
+    /// # Arguments
+    /// * shape - The shape
+    ///
+    /// Return a new Collider
+    pub fn from_convex_shape(shape: &ConvexShape) -> Collider {
+        let points: Vec<Vector2f> = range(0, shape.get_point_count())
+            .map(|index| shape.get_point(index))
+            .collect();
+        transformed(points, shape.get_transform())
+    }
+
+    /// Create a collider from a RectangleShape and its transformation
+    ///
+    /// # Arguments
+    /// * shape - The shape
+    ///
+    /// Return a new Collider
+    pub fn from_rectangle_shape(shape: &RectangleShape) -> Collider {
+        let size = shape.get_size();
+        transformed(rect_points(&FloatRect::new(0., 0., size.x, size.y)).to_vec(),
+                    shape.get_transform())
+    }
+
+    /// Create a collider from a CircleShape and its transformation
+    ///
+    /// The result is a circle when the shape is scaled uniformly, and
+    /// the polygon of the shape otherwise.
+    ///
+    /// # Arguments
+    /// * shape - The shape
+    ///
+    /// Return a new Collider
+    pub fn from_circle_shape(shape: &CircleShape) -> Collider {
+        let radius = shape.get_radius();
+        let scale = shape.get_scale();
+        let mut transform = shape.get_transform();
+        if (scale.x.abs() - scale.y.abs()).abs() <= f32::EPSILON * scale.x.abs() {
+            CircleCollider {
+                center: transform.transform_point(&Vector2f::new(radius, radius)),
+                radius: radius * scale.x.abs()
+            }
+        } else {
+            // Same points as the ones computed by SFML
+            let count = shape.get_point_count();
+            let points: Vec<Vector2f> = range(0, count).map(|index| {
+                let angle = index as f32 * 2. * f32::consts::PI / count as f32
+                    - f32::consts::PI / 2.;
+                Vector2f::new(radius + angle.cos() * radius, radius + angle.sin() * radius)
+            }).collect();
+            transformed(points, transform)
+        }
+    }
+
+    /// Create a collider from a Shape and its transformation
+    ///
+    /// # Arguments
+    /// * shape - The shape
+    ///
+    /// Return a new Collider
+    pub fn from_shape(shape: &Shape) -> Collider {
+        let points: Vec<Vector2f> = range(0, shape.get_point_count())
+            .map(|index| shape.get_point(index))
+            .collect();
+        transformed(points, shape.get_transform())
+    }
+
+    /// Create a collider from a ShapeImpl and a transformation
+    ///
+    /// # Arguments
+    /// * shape - The implementation of the shape
+    /// * transform - The transformation from the shape to the world
+    ///
+    /// Return a new Collider
+    pub fn from_shape_impl(shape: &ShapeImpl, transform: &Transform) -> Collider {
+        let points: Vec<Vector2f> = range(0, shape.get_point_count())
+            .map(|index| shape.get_point(index))
+            .collect();
+        transformed(points, *transform)
+    }
+
+    /// Create a collider from a Sprite and its transformation
+    ///
+    /// Unlike the global bounds of the sprite, the collider follows its
+    /// rotation.
+    ///
+    /// # Arguments
+    /// * sprite - The sprite
+    ///
+    /// Return a new Collider
+    pub fn from_sprite(sprite: &Sprite) -> Collider {
+        transformed(rect_points(&sprite.get_local_bounds()).to_vec(),
+                    sprite.get_transform())
+    }
+
+    /// Get the center of the collider
+    ///
+    /// For a polygon, it is the average of its points.
+    ///
+    /// Return the center of the collider
+    pub fn get_center(&self) -> Vector2f {
+        match *self {
+            PolygonCollider { ref points } => {
+                if points.is_empty() {
+                    return Vector2f::new(0., 0.);
+                }
+                let mut center = Vector2f::new(0., 0.);
+                for point in points.iter() {
+                    center = center + *point;
+                }
+                center / points.len() as f32
+            },
+            CircleCollider { center, .. } => center
+        }
+    }
+
+    /// Get the axis aligned bounding rectangle of the collider
+    ///
+    /// Return the bounding rectangle
+    pub fn get_bounds(&self) -> FloatRect {
+        match *self {
+            PolygonCollider { ref points } => {
+                if points.is_empty() {
+                    return FloatRect::new(0., 0., 0., 0.);
+                }
+                let (mut min, mut max) = (points[0], points[0]);
+                for point in points.iter() {
+                    min = Vector2f::new(min.x.min(point.x), min.y.min(point.y));
+                    max = Vector2f::new(max.x.max(point.x), max.y.max(point.y));
+                }
+                FloatRect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+            },
+            CircleCollider { center, radius } => {
+                FloatRect::new(center.x - radius, center.y - radius, radius * 2., radius * 2.)
+            }
+        }
+    }
+
+    /// Check if a point is inside the collider
+    ///
+    /// Points on the border are inside.
+    ///
+    /// # Arguments
+    /// * point - The point to test, in world coordinates
+    ///
+    /// Return true if the point is inside
+    pub fn contains_point(&self, point: &Vector2f) -> bool {
+        match *self {
+            PolygonCollider { ref points } => {
+                if points.len() < 3 {
+                    return false;
+                }
+                let (mut positive, mut negative) = (false, false);
+                for i in range(0, points.len()) {
+                    let next = points[(i + 1) % points.len()];
+                    let side = geometry::cross(next - points[i], *point - points[i]);
+                    positive = positive || side > 0.;
+                    negative = negative || side < 0.;
+                }
+                !(positive && negative)
+            },
+            CircleCollider { center, radius } => {
+                geometry::distance(*point, center) <= radius
+            }
+        }
+    }
+
+    /// Test the overlap of two colliders
+    ///
+    /// Colliders that only touch do not overlap.
+    ///
+    /// # Arguments
+    /// * other - The collider to test against
+    ///
+    /// Return Some(Contact) if the colliders overlap, None otherwise
+    pub fn collide(&self, other: &Collider) -> Option<Contact> {
+        match (self, other) {
+            (&CircleCollider { center: a, radius: ra },
+             &CircleCollider { center: b, radius: rb }) => {
+                let distance = geometry::distance(a, b);
+                let depth = ra + rb - distance;
+                if depth <= 0. {
+                    return None;
+                }
+                let normal = if distance > 0. {
+                    (b - a) / distance
+                } else {
+                    Vector2f::new(1., 0.)
+                };
+                return Some(contact(normal, depth));
+            },
+            _ => {}
+        }
+
+        let mut axes = Vec::new();
+        self.push_axes(other, &mut axes);
+        other.push_axes(self, &mut axes);
+
+        let mut best: Option<(Vector2f, f32)> = None;
+        for axis in axes.iter() {
+            let (min_a, max_a) = self.project(*axis);
+            let (min_b, max_b) = other.project(*axis);
+            let overlap = max_a.min(max_b) - min_a.max(min_b);
+            if overlap <= 0. {
+                return None;
+            }
+            best = match best {
+                Some((_, depth)) if depth <= overlap => best,
+                _ => Some((*axis, overlap))
+            };
+        }
+
+        match best {
+            Some((axis, depth)) => {
+                let direction = other.get_center() - self.get_center();
+                let normal = if geometry::dot(direction, axis) < 0. { axis * -1. } else { axis };
+                Some(contact(normal, depth))
+            },
+            None => None
+        }
+    }
+
+    /// Cast a ray against the collider
+    ///
+    /// A ray starting inside the collider hits it at distance 0.
+    ///
+    /// # Arguments
+    /// * origin - The origin of the ray
+    /// * direction - The direction of the ray, it doesn't need to be normalized
+    /// * max_distance - The length of the ray
+    ///
+    /// Return Some(RayHit) at the first hit point, None if the ray misses
+    pub fn ray_cast(&self,
+                    origin: &Vector2f,
+                    direction: &Vector2f,
+                    max_distance: f32) -> Option<RayHit> {
+        let direction = geometry::normalize(*direction);
+        if direction == Vector2f::new(0., 0.) {
+            return None;
+        }
+        if self.contains_point(origin) {
+            return Some(RayHit {
+                distance: 0.,
+                point: *origin,
+                normal: direction * -1.
+            });
+        }
+
+        match *self {
+            PolygonCollider { ref points } => {
+                if points.len() < 3 {
+                    return None;
+                }
+                // Clip the ray against the half plane of every edge
+                let orientation = if geometry::signed_area(points.as_slice()) < 0. { -1. } else { 1. };
+                let (mut enter, mut exit) = (0., max_distance);
+                let mut enter_normal = Vector2f::new(0., 0.);
+                for i in range(0, points.len()) {
+                    let edge = points[(i + 1) % points.len()] - points[i];
+                    if edge == Vector2f::new(0., 0.) {
+                        continue;
+                    }
+                    let outward = geometry::normalize(geometry::perpendicular(edge))
+                        * -orientation;
+                    let distance = geometry::dot(points[i] - *origin, outward);
+                    let speed = geometry::dot(direction, outward);
+                    if speed == 0. {
+                        if distance < 0. {
+                            return None;
+                        }
+                    } else {
+                        let t = distance / speed;
+                        if speed < 0. {
+                            if t > enter {
+                                enter = t;
+                                enter_normal = outward;
+                            }
+                        } else if t < exit {
+                            exit = t;
+                        }
+                        if enter > exit {
+                            return None;
+                        }
+                    }
+                }
+                Some(RayHit {
+                    distance: enter,
+                    point: *origin + direction * enter,
+                    normal: enter_normal
+                })
+            },
+            CircleCollider { center, radius } => {
+                let offset = *origin - center;
+                let b = geometry::dot(offset, direction);
+                let c = geometry::dot(offset, offset) - radius * radius;
+                let discriminant = b * b - c;
+                if discriminant < 0. {
+                    return None;
+                }
+                let t = -b - discriminant.sqrt();
+                if t < 0. || t > max_distance {
+                    return None;
+                }
+                let point = *origin + direction * t;
+                Some(RayHit {
+                    distance: t,
+                    point: point,
+                    normal: geometry::normalize(point - center)
+                })
+            }
+        }
+    }
+
+    /// Cast a segment against the collider
+    ///
+    /// # Arguments
+    /// * start - The start of the segment
+    /// * end - The end of the segment
+    ///
+    /// Return Some(RayHit) at the hit point closest to start, None if the
+    /// segment misses
+    pub fn segment_cast(&self, start: &Vector2f, end: &Vector2f) -> Option<RayHit> {
+        self.ray_cast(start, &(*end - *start), geometry::distance(*start, *end))
+    }
+
+    // Separating axes to test against the other collider
+    #[doc(hidden)]
+    fn push_axes(&self, other: &Collider, axes: &mut Vec<Vector2f>) -> () {
+        match *self {
+            PolygonCollider { ref points } => {
+                for i in range(0, points.len()) {
+                    let edge = points[(i + 1) % points.len()] - points[i];
+                    if edge != Vector2f::new(0., 0.) {
+                        axes.push(geometry::normalize(geometry::perpendicular(edge)));
+                    }
+                }
+            },
+            CircleCollider { center, .. } => {
+                // The axis from the circle to the closest point of the polygon
+                let points = match *other {
+                    PolygonCollider { ref points } => points,
+                    CircleCollider { .. } => return
+                };
+                let mut closest = None;
+                let mut closest_distance = f32::INFINITY;
+                for point in points.iter() {
+                    let distance = geometry::distance(*point, center);
+                    if distance < closest_distance {
+                        closest = Some(*point);
+                        closest_distance = distance;
+                    }
+                }
+                match closest {
+                    Some(point) if closest_distance > 0. => {
+                        axes.push((point - center) / closest_distance)
+                    },
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    fn project(&self, axis: Vector2f) -> (f32, f32) {
+        match *self {
+            PolygonCollider { ref points } => {
+                let (mut min, mut max) = (f32::INFINITY, f32::NEG_INFINITY);
+                for point in points.iter() {
+                    let projection = geometry::dot(*point, axis);
+                    min = min.min(projection);
+                    max = max.max(projection);
+                }
+                (min, max)
+            },
+            CircleCollider { center, radius } => {
+                let projection = geometry::dot(center, axis);
+                (projection - radius, projection + radius)
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+fn contact(normal: Vector2f, depth: f32) -> Contact {
+    Contact {
+        normal: normal,
+        depth: depth,
+        mtv: normal * -depth
+    }
+}
+
+#[doc(hidden)]
+fn rect_points(rect: &FloatRect) -> [Vector2f, ..4] {
+    [Vector2f::new(rect.left, rect.top),
+     Vector2f::new(rect.left + rect.width, rect.top),
+     Vector2f::new(rect.left + rect.width, rect.top + rect.height),
+     Vector2f::new(rect.left, rect.top + rect.height)]
+}
+
+#[doc(hidden)]
+fn transformed(points: Vec<Vector2f>, transform: Transform) -> Collider {
+    let mut transform = transform;
+    PolygonCollider {
+        points: points.iter().map(|point| transform.transform_point(point)).collect()
+    }
+}
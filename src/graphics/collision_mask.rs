@@ -0,0 +1,217 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+#![allow(missing_copy_implementations)]
+
+//! Pixel perfect collision masks
+//!
+//! A CollisionMask stores which pixels of an image are solid, one bit per
+//! pixel. Sprites use it to test their overlap pixel by pixel.
+
+use std::num::{Int, Float};
+
+use graphics::{Image, Texture, FloatRect, IntRect, Transform};
+use system::vector2::{Vector2f, Vector2u};
+
+/// Bitmask of the solid pixels of an image
+#[deriving(Clone, PartialEq, Show)]
+pub struct CollisionMask {
+    #[doc(hidden)]
+    width: uint,
+    #[doc(hidden)]
+    height: uint,
+    #[doc(hidden)]
+    stride: uint,
+    #[doc(hidden)]
+    bits: Vec<u32>
+}
+
+impl CollisionMask {
+    /// Create a collision mask where no pixel is solid
+    ///
+    /// # Arguments
+    /// * width - Width of the mask
+    /// * height - Height of the mask
+    ///
+    /// Return a new CollisionMask
+    pub fn new(width: uint, height: uint) -> CollisionMask {
+        let stride = (width + 31) / 32;
+        CollisionMask {
+            width: width,
+            height: height,
+            stride: stride,
+            bits: Vec::from_elem(stride * height, 0u32)
+        }
+    }
+
+    /// Create a collision mask from the alpha channel of an image
+    ///
+    /// # Arguments
+    /// * image - The image to build the mask from
+    /// * threshold - Pixels with an alpha above this value are solid
+    ///
+    /// Return a new CollisionMask
+    pub fn new_from_image(image: &Image, threshold: u8) -> CollisionMask {
+        let size = image.get_size();
+        let mut mask = CollisionMask::new(size.x as uint, size.y as uint);
+        for y in range(0, mask.height) {
+            for x in range(0, mask.width) {
+                if image.get_pixel(x, y).alpha > threshold {
+                    mask.set_solid(x, y, true);
+                }
+            }
+        }
+        mask
+    }
+
+    /// Create a collision mask from the alpha channel of a texture
+    ///
+    /// The texture is copied back from the graphic card, which is slow:
+    /// build the mask once when the texture is loaded.
+    ///
+    /// # Arguments
+    /// * texture - The texture to build the mask from
+    /// * threshold - Pixels with an alpha above this value are solid
+    ///
+    /// Return Some(CollisionMask) or None
+    pub fn new_from_texture(texture: &Texture, threshold: u8) -> Option<CollisionMask> {
+        match texture.copy_to_image() {
+            Some(image) => Some(CollisionMask::new_from_image(&image, threshold)),
+            None        => None
+        }
+    }
+
+    /// Get the size of the mask
+    ///
+    /// Return the size of the mask, in pixels
+    pub fn get_size(&self) -> Vector2u {
+        Vector2u::new(self.width as u32, self.height as u32)
+    }
+
+    /// Tell if a pixel of the mask is solid
+    ///
+    /// # Arguments
+    /// * x - X coordinate of the pixel
+    /// * y - Y coordinate of the pixel
+    ///
+    /// Return true if the pixel is solid, false if it is not or if it is
+    /// outside of the mask
+    pub fn is_solid(&self, x: uint, y: uint) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        self.bits[y * self.stride + x / 32] & (1 << (x % 32)) != 0
+    }
+
+    /// Change the state of a pixel of the mask
+    ///
+    /// Pixels outside of the mask are ignored.
+    ///
+    /// # Arguments
+    /// * x - X coordinate of the pixel
+    /// * y - Y coordinate of the pixel
+    /// * solid - The new state of the pixel
+    pub fn set_solid(&mut self, x: uint, y: uint, solid: bool) -> () {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let index = y * self.stride + x / 32;
+        if solid {
+            self.bits[index] |= 1 << (x % 32);
+        } else {
+            self.bits[index] &= !(1 << (x % 32));
+        }
+    }
+
+    /// Count the solid pixels of the mask
+    ///
+    /// Return the number of solid pixels
+    pub fn get_solid_count(&self) -> uint {
+        self.bits.iter().fold(0, |count, word| count + word.count_ones())
+    }
+}
+
+// What the pixel test needs to know about a sprite
+#[doc(hidden)]
+pub struct MaskedSprite<'a> {
+    pub bounds: FloatRect,
+    pub inverse_transform: Transform,
+    pub texture_rect: IntRect,
+    pub mask: &'a CollisionMask
+}
+
+impl<'a> MaskedSprite<'a> {
+    #[doc(hidden)]
+    fn is_solid_at(&self, point: &Vector2f) -> bool {
+        let mut inverse_transform = self.inverse_transform;
+        let local = inverse_transform.transform_point(point);
+        let rect = self.texture_rect;
+        if local.x < 0. || local.y < 0. ||
+           local.x >= (rect.width as f32).abs() || local.y >= (rect.height as f32).abs() {
+            return false;
+        }
+        // A negative size flips the texture rect
+        let x = (rect.left as f32 + local.x * (rect.width as f32).signum()).floor();
+        let y = (rect.top as f32 + local.y * (rect.height as f32).signum()).floor();
+        x >= 0. && y >= 0. && self.mask.is_solid(x as uint, y as uint)
+    }
+
+    // World distance between two samples which can't skip a texel: the
+    // samples map to a grid whose cell fits in a texel
+    #[doc(hidden)]
+    fn get_sample_step(&self) -> f32 {
+        let mut inverse_transform = self.inverse_transform;
+        let origin = inverse_transform.transform_point(&Vector2f::new(0., 0.));
+        let u = inverse_transform.transform_point(&Vector2f::new(1., 0.)) - origin;
+        let v = inverse_transform.transform_point(&Vector2f::new(0., 1.)) - origin;
+        let density = (u.x.abs() + v.x.abs()).max(u.y.abs() + v.y.abs());
+        if density.is_finite() && density > 1. { 1. / density } else { 1. }
+    }
+}
+
+// Test points covering the overlap of both sprites, one per world pixel or
+// closer when a sprite has more than one texel per world pixel
+#[doc(hidden)]
+pub fn pixel_collides(a: &MaskedSprite, b: &MaskedSprite) -> bool {
+    let overlap = match a.bounds.intersection(&b.bounds) {
+        Some(overlap) => overlap,
+        None => return false
+    };
+    let (right, bottom) = (overlap.left + overlap.width, overlap.top + overlap.height);
+    let step = a.get_sample_step().min(b.get_sample_step());
+
+    let mut y = overlap.top;
+    while y < bottom {
+        let mut x = overlap.left;
+        while x < right {
+            let point = Vector2f::new(x + step / 2., y + step / 2.);
+            if a.is_solid_at(&point) && b.is_solid_at(&point) {
+                return true;
+            }
+            x += step;
+        }
+        y += step;
+    }
+    false
+}
@@ -28,8 +28,12 @@
 //!
 //! Color is a simple color class composed of 4 components: Red, Green, Blue, Alpha
 
+use std::num::Float;
+
 use ffi::graphics::color as ffi;
 
+use self::Separable::{MultiplyMode, ScreenMode, OverlayMode, SubtractMode};
+
 /// Utility class for manpulating RGBA colors
 ///
 /// Color is a simple color class composed of 4 components: Red, Green, Blue, Alpha
@@ -150,6 +154,397 @@ impl Color {
         Color::new_RGBA(0, 0, 0, 0)
     }
 
+    /// Construct a color from its hue, saturation and value
+    ///
+    /// # Arguments
+    /// * hue - Hue, in degrees (wrapped to 0 .. 360)
+    /// * saturation - Saturation (0 .. 1)
+    /// * value - Value (0 .. 1)
+    ///
+    /// Return an opaque Color object
+    pub fn new_HSV(hue: f32, saturation: f32, value: f32) -> Color {
+        Color::new_HSVA(hue, saturation, value, 255)
+    }
+
+    /// Construct a color from its hue, saturation, value and alpha
+    ///
+    /// # Arguments
+    /// * hue - Hue, in degrees (wrapped to 0 .. 360)
+    /// * saturation - Saturation (0 .. 1)
+    /// * value - Value (0 .. 1)
+    /// * alpha - Alpha component (0 .. 255)
+    ///
+    /// Return Color object constructed from the components
+    pub fn new_HSVA(hue: f32, saturation: f32, value: f32, alpha: u8) -> Color {
+        let value = clamp(value);
+        let chroma = value * clamp(saturation);
+        from_hue(hue, chroma, value - chroma, alpha)
+    }
+
+    /// Construct a color from its hue, saturation and lightness
+    ///
+    /// # Arguments
+    /// * hue - Hue, in degrees (wrapped to 0 .. 360)
+    /// * saturation - Saturation (0 .. 1)
+    /// * lightness - Lightness (0 .. 1)
+    ///
+    /// Return an opaque Color object
+    pub fn new_HSL(hue: f32, saturation: f32, lightness: f32) -> Color {
+        Color::new_HSLA(hue, saturation, lightness, 255)
+    }
+
+    /// Construct a color from its hue, saturation, lightness and alpha
+    ///
+    /// # Arguments
+    /// * hue - Hue, in degrees (wrapped to 0 .. 360)
+    /// * saturation - Saturation (0 .. 1)
+    /// * lightness - Lightness (0 .. 1)
+    /// * alpha - Alpha component (0 .. 255)
+    ///
+    /// Return Color object constructed from the components
+    pub fn new_HSLA(hue: f32, saturation: f32, lightness: f32, alpha: u8) -> Color {
+        let lightness = clamp(lightness);
+        let chroma = (1. - (2. * lightness - 1.).abs()) * clamp(saturation);
+        from_hue(hue, chroma, lightness - chroma / 2., alpha)
+    }
+
+    /// Get the hue, saturation and value of a color
+    ///
+    /// Return the hue in degrees (0 .. 360), the saturation and the value (0 .. 1)
+    pub fn to_HSV(&self) -> (f32, f32, f32) {
+        let (hue, max, min) = self.hue();
+        let saturation = if max == 0. { 0. } else { (max - min) / max };
+        (hue, saturation, max)
+    }
+
+    /// Get the hue, saturation and lightness of a color
+    ///
+    /// Return the hue in degrees (0 .. 360), the saturation and the lightness (0 .. 1)
+    pub fn to_HSL(&self) -> (f32, f32, f32) {
+        let (hue, max, min) = self.hue();
+        let lightness = (max + min) / 2.;
+        let saturation = if max == min {
+            0.
+        } else {
+            (max - min) / (1. - (2. * lightness - 1.).abs())
+        };
+        (hue, saturation, lightness)
+    }
+
+    /// Parse a color written as #rrggbb or #rrggbbaa
+    ///
+    /// The # is optional and the digits are case insensitive.
+    ///
+    /// # Arguments
+    /// * hex - The string to parse
+    ///
+    /// Return Some(Color) or None if the string is not a valid color
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        let digits = if hex.starts_with("#") { hex.slice_from(1) } else { hex };
+        if digits.len() != 6 && digits.len() != 8 {
+            return None;
+        }
+        let mut components = [255u8, 255, 255, 255];
+        let bytes = digits.as_bytes();
+        for i in range(0, digits.len() / 2) {
+            match (hex_digit(bytes[i * 2]), hex_digit(bytes[i * 2 + 1])) {
+                (Some(high), Some(low)) => components[i] = high * 16 + low,
+                _ => return None
+            }
+        }
+        Some(Color::new_RGBA(components[0], components[1], components[2], components[3]))
+    }
+
+    /// Format a color as #rrggbb, or #rrggbbaa if it is not opaque
+    ///
+    /// Return the lower case hexadecimal representation of the color
+    pub fn to_hex(&self) -> String {
+        if self.alpha == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", self.red, self.green, self.blue, self.alpha)
+        }
+    }
+
+    /// Get a color from its CSS name
+    ///
+    /// The name is case insensitive.
+    ///
+    /// # Arguments
+    /// * name - The CSS name of the color, like "cornflowerblue"
+    ///
+    /// Return Some(Color) or None if the name is unknown
+    pub fn from_name(name: &str) -> Option<Color> {
+        for &(css_name, rgba) in CSS_COLORS.iter() {
+            if name.len() == css_name.len() &&
+               name.bytes().zip(css_name.bytes()).all(|(a, b)| to_lower(a) == b) {
+                return Some(from_u32(rgba));
+            }
+        }
+        None
+    }
+
+    /// Get the CSS name of a color
+    ///
+    /// Return Some(name) if the color has a CSS name, None otherwise
+    pub fn to_name(&self) -> Option<&'static str> {
+        for &(css_name, rgba) in CSS_COLORS.iter() {
+            if from_u32(rgba) == *self {
+                return Some(css_name);
+            }
+        }
+        None
+    }
+
+    /// Parse a color written in hexadecimal or as a CSS name
+    ///
+    /// # Arguments
+    /// * text - The string to parse
+    ///
+    /// Return Some(Color) or None if the string is not a valid color
+    pub fn parse(text: &str) -> Option<Color> {
+        match Color::from_hex(text) {
+            Some(color) => Some(color),
+            None => Color::from_name(text)
+        }
+    }
+
+    /// Interpolate linearly between two colors, component by component
+    ///
+    /// # Arguments
+    /// * other - The color at t = 1
+    /// * t - The interpolation factor (0 .. 1)
+    ///
+    /// Return the interpolated color
+    pub fn lerp(&self, other: &Color, t: f32) -> Color {
+        let t = clamp(t);
+        let mix = |a: u8, b: u8| -> u8 { to_u8(from_u8(a) + (from_u8(b) - from_u8(a)) * t) };
+        Color::new_RGBA(mix(self.red, other.red),
+                        mix(self.green, other.green),
+                        mix(self.blue, other.blue),
+                        mix(self.alpha, other.alpha))
+    }
+
+    /// Interpolate between two colors in the HSV color space
+    ///
+    /// The hue turns in the shortest direction.
+    ///
+    /// # Arguments
+    /// * other - The color at t = 1
+    /// * t - The interpolation factor (0 .. 1)
+    ///
+    /// Return the interpolated color
+    pub fn lerp_HSV(&self, other: &Color, t: f32) -> Color {
+        let t = clamp(t);
+        let (hue1, saturation1, value1) = self.to_HSV();
+        let (hue2, saturation2, value2) = other.to_HSV();
+        let mut delta = hue2 - hue1;
+        if delta > 180. {
+            delta -= 360.;
+        } else if delta < -180. {
+            delta += 360.;
+        }
+        let alpha = to_u8(from_u8(self.alpha) + (from_u8(other.alpha) - from_u8(self.alpha)) * t);
+        Color::new_HSVA(hue1 + delta * t,
+                        saturation1 + (saturation2 - saturation1) * t,
+                        value1 + (value2 - value1) * t,
+                        alpha)
+    }
+
+    /// Multiply the red, green and blue components by the alpha
+    ///
+    /// Return the premultiplied color
+    pub fn premultiply(&self) -> Color {
+        let alpha = from_u8(self.alpha);
+        Color::new_RGBA(to_u8(from_u8(self.red) * alpha),
+                        to_u8(from_u8(self.green) * alpha),
+                        to_u8(from_u8(self.blue) * alpha),
+                        self.alpha)
+    }
+
+    /// Divide the red, green and blue components by the alpha
+    ///
+    /// Return the color without premultiplied alpha
+    pub fn unpremultiply(&self) -> Color {
+        if self.alpha == 0 {
+            return Color::transparent();
+        }
+        let alpha = from_u8(self.alpha);
+        Color::new_RGBA(to_u8(from_u8(self.red) / alpha),
+                        to_u8(from_u8(self.green) / alpha),
+                        to_u8(from_u8(self.blue) / alpha),
+                        self.alpha)
+    }
+
+    /// Convert a color to linear components
+    ///
+    /// The red, green and blue components are converted from the sRGB
+    /// curve, the alpha is only scaled.
+    ///
+    /// Return the red, green, blue and alpha components (0 .. 1)
+    pub fn to_linear(&self) -> (f32, f32, f32, f32) {
+        (srgb_to_linear(from_u8(self.red)),
+         srgb_to_linear(from_u8(self.green)),
+         srgb_to_linear(from_u8(self.blue)),
+         from_u8(self.alpha))
+    }
+
+    /// Construct a color from linear components
+    ///
+    /// # Arguments
+    /// * red - Linear red component (0 .. 1)
+    /// * green - Linear green component (0 .. 1)
+    /// * blue - Linear blue component (0 .. 1)
+    /// * alpha - Alpha component (0 .. 1)
+    ///
+    /// Return the sRGB Color object
+    pub fn from_linear(red: f32, green: f32, blue: f32, alpha: f32) -> Color {
+        Color::new_RGBA(to_u8(linear_to_srgb(red)),
+                        to_u8(linear_to_srgb(green)),
+                        to_u8(linear_to_srgb(blue)),
+                        to_u8(alpha))
+    }
+
+    /// Get the relative luminance of a color, as defined by WCAG
+    ///
+    /// Return the luminance, from 0 for black to 1 for white
+    pub fn luminance(&self) -> f32 {
+        let (red, green, blue, _) = self.to_linear();
+        0.2126 * red + 0.7152 * green + 0.0722 * blue
+    }
+
+    /// Get the contrast ratio between two colors, as defined by WCAG
+    ///
+    /// # Arguments
+    /// * other - The color to compare with
+    ///
+    /// Return the contrast ratio, from 1 to 21
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        let (luminance1, luminance2) = (self.luminance(), other.luminance());
+        (luminance1.max(luminance2) + 0.05) / (luminance1.min(luminance2) + 0.05)
+    }
+
+    /// Porter-Duff source over: draw the source above the destination
+    ///
+    /// # Arguments
+    /// * source - The color drawn
+    /// * destination - The color drawn onto
+    ///
+    /// Return the composed color
+    pub fn source_over(source: Color, destination: Color) -> Color {
+        compose(source, destination, 1., 1. - from_u8(source.alpha))
+    }
+
+    /// Porter-Duff source in: keep the source where the destination is
+    ///
+    /// # Arguments
+    /// * source - The color drawn
+    /// * destination - The color drawn onto
+    ///
+    /// Return the composed color
+    pub fn source_in(source: Color, destination: Color) -> Color {
+        compose(source, destination, from_u8(destination.alpha), 0.)
+    }
+
+    /// Porter-Duff source out: keep the source where the destination is not
+    ///
+    /// # Arguments
+    /// * source - The color drawn
+    /// * destination - The color drawn onto
+    ///
+    /// Return the composed color
+    pub fn source_out(source: Color, destination: Color) -> Color {
+        compose(source, destination, 1. - from_u8(destination.alpha), 0.)
+    }
+
+    /// Porter-Duff source atop: draw the source only above the destination
+    ///
+    /// # Arguments
+    /// * source - The color drawn
+    /// * destination - The color drawn onto
+    ///
+    /// Return the composed color
+    pub fn source_atop(source: Color, destination: Color) -> Color {
+        compose(source, destination, from_u8(destination.alpha), 1. - from_u8(source.alpha))
+    }
+
+    /// Porter-Duff xor: keep the source and the destination where they don't overlap
+    ///
+    /// # Arguments
+    /// * source - The color drawn
+    /// * destination - The color drawn onto
+    ///
+    /// Return the composed color
+    pub fn xor(source: Color, destination: Color) -> Color {
+        compose(source, destination,
+                1. - from_u8(destination.alpha),
+                1. - from_u8(source.alpha))
+    }
+
+    /// Multiply blend mode, which darkens the destination
+    ///
+    /// # Arguments
+    /// * source - The color drawn
+    /// * destination - The color drawn onto
+    ///
+    /// Return the blended color, composed over the destination
+    pub fn multiply(source: Color, destination: Color) -> Color {
+        blend(source, destination, MultiplyMode)
+    }
+
+    /// Screen blend mode, which lightens the destination
+    ///
+    /// # Arguments
+    /// * source - The color drawn
+    /// * destination - The color drawn onto
+    ///
+    /// Return the blended color, composed over the destination
+    pub fn screen(source: Color, destination: Color) -> Color {
+        blend(source, destination, ScreenMode)
+    }
+
+    /// Overlay blend mode, which multiplies the dark parts of the
+    /// destination and screens its light parts
+    ///
+    /// # Arguments
+    /// * source - The color drawn
+    /// * destination - The color drawn onto
+    ///
+    /// Return the blended color, composed over the destination
+    pub fn overlay(source: Color, destination: Color) -> Color {
+        blend(source, destination, OverlayMode)
+    }
+
+    /// Subtract blend mode, which removes the source from the destination
+    ///
+    /// # Arguments
+    /// * source - The color drawn
+    /// * destination - The color drawn onto
+    ///
+    /// Return the blended color, composed over the destination
+    pub fn subtract(source: Color, destination: Color) -> Color {
+        blend(source, destination, SubtractMode)
+    }
+
+    // Hue in degrees, largest and smallest components (0 .. 1)
+    #[doc(hidden)]
+    fn hue(&self) -> (f32, f32, f32) {
+        let (red, green, blue) = (from_u8(self.red), from_u8(self.green), from_u8(self.blue));
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let delta = max - min;
+        let hue = if delta == 0. {
+            0.
+        } else if max == red {
+            60. * ((green - blue) / delta)
+        } else if max == green {
+            60. * ((blue - red) / delta + 2.)
+        } else {
+            60. * ((red - green) / delta + 4.)
+        };
+        (if hue < 0. { hue + 360. } else { hue }, max, min)
+    }
+
 }
 
 impl Add<Color, Color> for Color {
@@ -181,3 +576,276 @@ impl Mul<Color, Color> for Color {
         }
     }
 }
+
+// Separable blend modes, which blend each component independently
+#[doc(hidden)]
+enum Separable {
+    MultiplyMode,
+    ScreenMode,
+    OverlayMode,
+    SubtractMode
+}
+
+// Blend the source with the destination, then compose the result over
+// the destination, as described by the W3C compositing specification
+#[doc(hidden)]
+fn blend(source: Color, destination: Color, mode: Separable) -> Color {
+    let destination_alpha = from_u8(destination.alpha);
+    let mix = |source: u8, destination: u8| -> u8 {
+        let (cs, cb) = (from_u8(source), from_u8(destination));
+        let blended = match mode {
+            MultiplyMode => cs * cb,
+            ScreenMode => cs + cb - cs * cb,
+            OverlayMode => {
+                if cb <= 0.5 { 2. * cs * cb } else { 1. - 2. * (1. - cs) * (1. - cb) }
+            },
+            SubtractMode => (cb - cs).max(0.)
+        };
+        to_u8((1. - destination_alpha) * cs + destination_alpha * blended)
+    };
+    let mixed = Color::new_RGBA(mix(source.red, destination.red),
+                                mix(source.green, destination.green),
+                                mix(source.blue, destination.blue),
+                                source.alpha);
+    Color::source_over(mixed, destination)
+}
+
+// Porter-Duff composition with the source and destination factors
+#[doc(hidden)]
+fn compose(source: Color, destination: Color, source_factor: f32, destination_factor: f32) -> Color {
+    let source_alpha = from_u8(source.alpha) * source_factor;
+    let destination_alpha = from_u8(destination.alpha) * destination_factor;
+    let alpha = source_alpha + destination_alpha;
+    if alpha <= 0. {
+        return Color::transparent();
+    }
+    let mix = |source: u8, destination: u8| -> u8 {
+        to_u8((from_u8(source) * source_alpha + from_u8(destination) * destination_alpha) / alpha)
+    };
+    Color::new_RGBA(mix(source.red, destination.red),
+                    mix(source.green, destination.green),
+                    mix(source.blue, destination.blue),
+                    to_u8(alpha))
+}
+
+#[doc(hidden)]
+fn from_hue(hue: f32, chroma: f32, offset: f32, alpha: u8) -> Color {
+    let hue = ((hue % 360.) + 360.) % 360. / 60.;
+    let x = chroma * (1. - (hue % 2. - 1.).abs());
+    let (red, green, blue) = match hue as uint {
+        0 => (chroma, x, 0.),
+        1 => (x, chroma, 0.),
+        2 => (0., chroma, x),
+        3 => (0., x, chroma),
+        4 => (x, 0., chroma),
+        _ => (chroma, 0., x)
+    };
+    Color::new_RGBA(to_u8(red + offset), to_u8(green + offset), to_u8(blue + offset), alpha)
+}
+
+#[doc(hidden)]
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+#[doc(hidden)]
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1. / 2.4) - 0.055
+    }
+}
+
+#[doc(hidden)]
+fn clamp(value: f32) -> f32 {
+    value.max(0.).min(1.)
+}
+
+#[doc(hidden)]
+fn from_u8(value: u8) -> f32 {
+    value as f32 / 255.
+}
+
+#[doc(hidden)]
+fn to_u8(value: f32) -> u8 {
+    (clamp(value) * 255.).round() as u8
+}
+
+#[doc(hidden)]
+fn from_u32(rgba: u32) -> Color {
+    Color::new_RGBA((rgba >> 24) as u8, (rgba >> 16) as u8, (rgba >> 8) as u8, rgba as u8)
+}
+
+#[doc(hidden)]
+fn hex_digit(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'...b'9' => Some(digit - b'0'),
+        b'a'...b'f' => Some(digit - b'a' + 10),
+        b'A'...b'F' => Some(digit - b'A' + 10),
+        _ => None
+    }
+}
+
+#[doc(hidden)]
+fn to_lower(byte: u8) -> u8 {
+    if byte >= b'A' && byte <= b'Z' { byte + 32 } else { byte }
+}
+
+// CSS named colors, as 0xRRGGBBAA
+#[doc(hidden)]
+static CSS_COLORS: &'static [(&'static str, u32)] = &[
+    ("aliceblue", 0xf0f8ffff),
+    ("antiquewhite", 0xfaebd7ff),
+    ("aqua", 0x00ffffff),
+    ("aquamarine", 0x7fffd4ff),
+    ("azure", 0xf0ffffff),
+    ("beige", 0xf5f5dcff),
+    ("bisque", 0xffe4c4ff),
+    ("black", 0x000000ff),
+    ("blanchedalmond", 0xffebcdff),
+    ("blue", 0x0000ffff),
+    ("blueviolet", 0x8a2be2ff),
+    ("brown", 0xa52a2aff),
+    ("burlywood", 0xdeb887ff),
+    ("cadetblue", 0x5f9ea0ff),
+    ("chartreuse", 0x7fff00ff),
+    ("chocolate", 0xd2691eff),
+    ("coral", 0xff7f50ff),
+    ("cornflowerblue", 0x6495edff),
+    ("cornsilk", 0xfff8dcff),
+    ("crimson", 0xdc143cff),
+    ("cyan", 0x00ffffff),
+    ("darkblue", 0x00008bff),
+    ("darkcyan", 0x008b8bff),
+    ("darkgoldenrod", 0xb8860bff),
+    ("darkgray", 0xa9a9a9ff),
+    ("darkgreen", 0x006400ff),
+    ("darkgrey", 0xa9a9a9ff),
+    ("darkkhaki", 0xbdb76bff),
+    ("darkmagenta", 0x8b008bff),
+    ("darkolivegreen", 0x556b2fff),
+    ("darkorange", 0xff8c00ff),
+    ("darkorchid", 0x9932ccff),
+    ("darkred", 0x8b0000ff),
+    ("darksalmon", 0xe9967aff),
+    ("darkseagreen", 0x8fbc8fff),
+    ("darkslateblue", 0x483d8bff),
+    ("darkslategray", 0x2f4f4fff),
+    ("darkslategrey", 0x2f4f4fff),
+    ("darkturquoise", 0x00ced1ff),
+    ("darkviolet", 0x9400d3ff),
+    ("deeppink", 0xff1493ff),
+    ("deepskyblue", 0x00bfffff),
+    ("dimgray", 0x696969ff),
+    ("dimgrey", 0x696969ff),
+    ("dodgerblue", 0x1e90ffff),
+    ("firebrick", 0xb22222ff),
+    ("floralwhite", 0xfffaf0ff),
+    ("forestgreen", 0x228b22ff),
+    ("fuchsia", 0xff00ffff),
+    ("gainsboro", 0xdcdcdcff),
+    ("ghostwhite", 0xf8f8ffff),
+    ("gold", 0xffd700ff),
+    ("goldenrod", 0xdaa520ff),
+    ("gray", 0x808080ff),
+    ("green", 0x008000ff),
+    ("greenyellow", 0xadff2fff),
+    ("grey", 0x808080ff),
+    ("honeydew", 0xf0fff0ff),
+    ("hotpink", 0xff69b4ff),
+    ("indianred", 0xcd5c5cff),
+    ("indigo", 0x4b0082ff),
+    ("ivory", 0xfffff0ff),
+    ("khaki", 0xf0e68cff),
+    ("lavender", 0xe6e6faff),
+    ("lavenderblush", 0xfff0f5ff),
+    ("lawngreen", 0x7cfc00ff),
+    ("lemonchiffon", 0xfffacdff),
+    ("lightblue", 0xadd8e6ff),
+    ("lightcoral", 0xf08080ff),
+    ("lightcyan", 0xe0ffffff),
+    ("lightgoldenrodyellow", 0xfafad2ff),
+    ("lightgray", 0xd3d3d3ff),
+    ("lightgreen", 0x90ee90ff),
+    ("lightgrey", 0xd3d3d3ff),
+    ("lightpink", 0xffb6c1ff),
+    ("lightsalmon", 0xffa07aff),
+    ("lightseagreen", 0x20b2aaff),
+    ("lightskyblue", 0x87cefaff),
+    ("lightslategray", 0x778899ff),
+    ("lightslategrey", 0x778899ff),
+    ("lightsteelblue", 0xb0c4deff),
+    ("lightyellow", 0xffffe0ff),
+    ("lime", 0x00ff00ff),
+    ("limegreen", 0x32cd32ff),
+    ("linen", 0xfaf0e6ff),
+    ("magenta", 0xff00ffff),
+    ("maroon", 0x800000ff),
+    ("mediumaquamarine", 0x66cdaaff),
+    ("mediumblue", 0x0000cdff),
+    ("mediumorchid", 0xba55d3ff),
+    ("mediumpurple", 0x9370dbff),
+    ("mediumseagreen", 0x3cb371ff),
+    ("mediumslateblue", 0x7b68eeff),
+    ("mediumspringgreen", 0x00fa9aff),
+    ("mediumturquoise", 0x48d1ccff),
+    ("mediumvioletred", 0xc71585ff),
+    ("midnightblue", 0x191970ff),
+    ("mintcream", 0xf5fffaff),
+    ("mistyrose", 0xffe4e1ff),
+    ("moccasin", 0xffe4b5ff),
+    ("navajowhite", 0xffdeadff),
+    ("navy", 0x000080ff),
+    ("oldlace", 0xfdf5e6ff),
+    ("olive", 0x808000ff),
+    ("olivedrab", 0x6b8e23ff),
+    ("orange", 0xffa500ff),
+    ("orangered", 0xff4500ff),
+    ("orchid", 0xda70d6ff),
+    ("palegoldenrod", 0xeee8aaff),
+    ("palegreen", 0x98fb98ff),
+    ("paleturquoise", 0xafeeeeff),
+    ("palevioletred", 0xdb7093ff),
+    ("papayawhip", 0xffefd5ff),
+    ("peachpuff", 0xffdab9ff),
+    ("peru", 0xcd853fff),
+    ("pink", 0xffc0cbff),
+    ("plum", 0xdda0ddff),
+    ("powderblue", 0xb0e0e6ff),
+    ("purple", 0x800080ff),
+    ("rebeccapurple", 0x663399ff),
+    ("red", 0xff0000ff),
+    ("rosybrown", 0xbc8f8fff),
+    ("royalblue", 0x4169e1ff),
+    ("saddlebrown", 0x8b4513ff),
+    ("salmon", 0xfa8072ff),
+    ("sandybrown", 0xf4a460ff),
+    ("seagreen", 0x2e8b57ff),
+    ("seashell", 0xfff5eeff),
+    ("sienna", 0xa0522dff),
+    ("silver", 0xc0c0c0ff),
+    ("skyblue", 0x87ceebff),
+    ("slateblue", 0x6a5acdff),
+    ("slategray", 0x708090ff),
+    ("slategrey", 0x708090ff),
+    ("snow", 0xfffafaff),
+    ("springgreen", 0x00ff7fff),
+    ("steelblue", 0x4682b4ff),
+    ("tan", 0xd2b48cff),
+    ("teal", 0x008080ff),
+    ("thistle", 0xd8bfd8ff),
+    ("tomato", 0xff6347ff),
+    ("turquoise", 0x40e0d0ff),
+    ("violet", 0xee82eeff),
+    ("wheat", 0xf5deb3ff),
+    ("white", 0xffffffff),
+    ("whitesmoke", 0xf5f5f5ff),
+    ("yellow", 0xffff00ff),
+    ("yellowgreen", 0x9acd32ff),
+    ("transparent", 0x00000000)
+];
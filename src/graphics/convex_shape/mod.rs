@@ -34,7 +34,7 @@
 use libc::{c_float, c_uint};
 use std::ptr;
 
-use traits::{Wrappable, Drawable};
+use traits::{Wrappable, Drawable, Bounded};
 use graphics::{Color, Texture, RenderTarget, FloatRect, IntRect, Transform, RenderStates};
 use system::vector2::Vector2f;
 
@@ -49,6 +49,7 @@ pub mod rc;
 /// always be... convex, otherwise it may not be drawn correctly.
 /// Moreover, the points must be defined in order; using a random
 /// order would result in an incorrect shape.
+/// Use PolygonShape to draw concave polygons.
 pub struct ConvexShape<'s> {
     #[doc(hidden)]
     convex_shape: *mut ffi::sfConvexShape,
@@ -340,7 +341,7 @@ impl<'s> ConvexShape<'s> {
     ///
     /// # Arguments
     /// * rotation - New rotation
-    pub fn set_rotation(&self, angle: f32) -> () {
+    pub fn set_rotation(&mut self, angle: f32) -> () {
         unsafe {
             ffi::sfConvexShape_setRotation(self.convex_shape, angle as c_float)
         }
@@ -635,6 +636,18 @@ impl<'s> Wrappable<*mut ffi::sfConvexShape> for ConvexShape<'s> {
     }
 }
 
+impl_transformable!(ConvexShape<'s>, 's);
+
+impl<'s> Bounded for ConvexShape<'s> {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
 impl<'s> Drawable for ConvexShape<'s> {
     fn draw<RT: RenderTarget>(&self, render_target: &mut RT) -> () {
         render_target.draw_convex_shape(self)
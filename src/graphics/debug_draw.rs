@@ -0,0 +1,526 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Immediate mode drawing of debug geometry
+//!
+//! A DebugDraw accumulates the lines, rectangles, circles, arrows, crosses
+//! and texts requested during a frame into a few vertex arrays, and draws
+//! all of them at once when flushed. Helpers outline the bounds of sprites,
+//! shapes and texts, mark their origins, show the rectangle of a View and
+//! draw a graph of the recent frame times.
+
+use std::num::Float;
+use std::f32::consts::PI;
+
+use traits::{Bounded, Transformable};
+use graphics::{Color, Vertex, VertexArray, FloatRect, Font, Text, View, RenderTarget, Lines,
+               Triangles};
+use system::vector2::Vector2f;
+use system::Time;
+
+/// Frame time drawn as reference line by the frame graph, 60 frames per second
+static TARGET_FRAME_TIME: f32 = 1. / 60.;
+
+// A text waiting to be drawn
+#[doc(hidden)]
+struct Label {
+    string: String,
+    position: Vector2f,
+    color: Color
+}
+
+// Geometry accumulated in one coordinate system
+#[doc(hidden)]
+struct Layer {
+    lines: VertexArray,
+    triangles: VertexArray,
+    labels: Vec<Label>
+}
+
+impl Layer {
+    #[doc(hidden)]
+    fn new() -> Option<Layer> {
+        let lines = match VertexArray::new_init(Lines, 0) {
+            Some(lines) => lines,
+            None => return None
+        };
+        let triangles = match VertexArray::new_init(Triangles, 0) {
+            Some(triangles) => triangles,
+            None => return None
+        };
+        Some(Layer {
+            lines: lines,
+            triangles: triangles,
+            labels: Vec::new()
+        })
+    }
+
+    #[doc(hidden)]
+    fn line(&mut self, from: &Vector2f, to: &Vector2f, color: &Color) -> () {
+        self.lines.append(&Vertex::new_with_pos_color(from, color));
+        self.lines.append(&Vertex::new_with_pos_color(to, color));
+    }
+
+    #[doc(hidden)]
+    fn rect(&mut self, rect: &FloatRect, color: &Color) -> () {
+        let corners = corners(rect);
+        for i in range(0u, 4) {
+            self.line(&corners[i], &corners[(i + 1) % 4], color);
+        }
+    }
+
+    #[doc(hidden)]
+    fn fill_rect(&mut self, rect: &FloatRect, color: &Color) -> () {
+        let corners = corners(rect);
+        for &i in [0u, 1, 2, 0, 2, 3].iter() {
+            self.triangles.append(&Vertex::new_with_pos_color(&corners[i], color));
+        }
+    }
+
+    #[doc(hidden)]
+    fn text(&mut self, string: &str, position: &Vector2f, color: &Color) -> () {
+        self.labels.push(Label {
+            string: string.to_string(),
+            position: *position,
+            color: *color
+        });
+    }
+
+    #[doc(hidden)]
+    fn clear(&mut self) -> () {
+        self.lines.clear();
+        self.triangles.clear();
+        self.labels.clear();
+    }
+
+    #[doc(hidden)]
+    fn draw<RT: RenderTarget>(&self,
+                              render_target: &mut RT,
+                              font: Option<&Font>,
+                              character_size: uint) -> () {
+        if self.triangles.get_vertex_count() > 0 {
+            render_target.draw(&self.triangles);
+        }
+        if self.lines.get_vertex_count() > 0 {
+            render_target.draw(&self.lines);
+        }
+        let font = match font {
+            Some(font) => font,
+            None => return
+        };
+        for label in self.labels.iter() {
+            match Text::new_init(label.string.as_slice(), font, character_size) {
+                Some(mut text) => {
+                    text.set_position(&label.position);
+                    text.set_color(&label.color);
+                    render_target.draw(&text);
+                },
+                None => {}
+            }
+        }
+    }
+}
+
+/// Immediate mode drawing of debug geometry
+///
+/// The geometry is given in world coordinates and drawn with the current
+/// view of the target, except the frame graph which is given in pixels and
+/// drawn with the default view. The texts are only drawn if a font is set.
+pub struct DebugDraw<'s> {
+    #[doc(hidden)]
+    world: Layer,
+    #[doc(hidden)]
+    overlay: Layer,
+    #[doc(hidden)]
+    font: Option<&'s Font>,
+    #[doc(hidden)]
+    character_size: uint,
+    #[doc(hidden)]
+    enabled: bool,
+    #[doc(hidden)]
+    frame_times: Vec<f32>,
+    #[doc(hidden)]
+    frame_time_capacity: uint
+}
+
+impl<'s> DebugDraw<'s> {
+    /// Create a new DebugDraw without font
+    ///
+    /// Return Some(DebugDraw) or None
+    pub fn new() -> Option<DebugDraw<'s>> {
+        let world = match Layer::new() {
+            Some(world) => world,
+            None => return None
+        };
+        let overlay = match Layer::new() {
+            Some(overlay) => overlay,
+            None => return None
+        };
+        Some(DebugDraw {
+            world: world,
+            overlay: overlay,
+            font: None,
+            character_size: 12,
+            enabled: true,
+            frame_times: Vec::new(),
+            frame_time_capacity: 120
+        })
+    }
+
+    /// Set the font of the texts
+    ///
+    /// # Arguments
+    /// * font - The font
+    pub fn set_font(&mut self, font: &'s Font) -> () {
+        self.font = Some(font)
+    }
+
+    /// Get the font of the texts
+    ///
+    /// Return Some(font) or None if no font is set
+    pub fn get_font(&self) -> Option<&'s Font> {
+        self.font
+    }
+
+    /// Set the character size of the texts
+    ///
+    /// # Arguments
+    /// * character_size - The character size, 12 by default
+    pub fn set_character_size(&mut self, character_size: uint) -> () {
+        self.character_size = character_size
+    }
+
+    /// Get the character size of the texts
+    ///
+    /// Return the character size
+    pub fn get_character_size(&self) -> uint {
+        self.character_size
+    }
+
+    /// Enable or disable the DebugDraw
+    ///
+    /// While disabled, the drawing calls are ignored and flush draws nothing.
+    ///
+    /// # Arguments
+    /// * enabled - true to enable, false to disable
+    pub fn set_enabled(&mut self, enabled: bool) -> () {
+        self.enabled = enabled;
+        if !enabled {
+            self.clear();
+        }
+    }
+
+    /// Tell whether the DebugDraw is enabled
+    ///
+    /// Return true if enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Draw a line
+    ///
+    /// # Arguments
+    /// * from - The start of the line
+    /// * to - The end of the line
+    /// * color - The color of the line
+    pub fn line(&mut self, from: &Vector2f, to: &Vector2f, color: &Color) -> () {
+        if self.enabled {
+            self.world.line(from, to, color);
+        }
+    }
+
+    /// Draw the outline of a rectangle
+    ///
+    /// # Arguments
+    /// * rect - The rectangle
+    /// * color - The color of the outline
+    pub fn rect(&mut self, rect: &FloatRect, color: &Color) -> () {
+        if self.enabled {
+            self.world.rect(rect, color);
+        }
+    }
+
+    /// Draw a filled rectangle
+    ///
+    /// # Arguments
+    /// * rect - The rectangle
+    /// * color - The color of the rectangle
+    pub fn fill_rect(&mut self, rect: &FloatRect, color: &Color) -> () {
+        if self.enabled {
+            self.world.fill_rect(rect, color);
+        }
+    }
+
+    /// Draw the outline of a circle
+    ///
+    /// # Arguments
+    /// * center - The center of the circle
+    /// * radius - The radius of the circle
+    /// * color - The color of the outline
+    pub fn circle(&mut self, center: &Vector2f, radius: f32, color: &Color) -> () {
+        if !self.enabled {
+            return;
+        }
+        let segments = (radius.abs() / 2.).max(12.).min(64.) as uint;
+        let mut previous = Vector2f::new(center.x + radius, center.y);
+        for i in range(1, segments + 1) {
+            let angle = 2. * PI * i as f32 / segments as f32;
+            let point = Vector2f::new(center.x + radius * angle.cos(),
+                                      center.y + radius * angle.sin());
+            self.world.line(&previous, &point, color);
+            previous = point;
+        }
+    }
+
+    /// Draw an arrow
+    ///
+    /// The head of the arrow is a quarter of its length, 12 units at most.
+    ///
+    /// # Arguments
+    /// * from - The tail of the arrow
+    /// * to - The head of the arrow
+    /// * color - The color of the arrow
+    pub fn arrow(&mut self, from: &Vector2f, to: &Vector2f, color: &Color) -> () {
+        if !self.enabled {
+            return;
+        }
+        self.world.line(from, to, color);
+        let dx = to.x - from.x;
+        let dy = to.y - from.y;
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0. {
+            return;
+        }
+        let head = (length / 4.).min(12.);
+        let angle = dy.atan2(dx);
+        for &side in [-1f32, 1.].iter() {
+            let wing = angle + PI + side * PI / 6.;
+            let point = Vector2f::new(to.x + head * wing.cos(), to.y + head * wing.sin());
+            self.world.line(to, &point, color);
+        }
+    }
+
+    /// Draw a cross
+    ///
+    /// # Arguments
+    /// * center - The center of the cross
+    /// * size - The width and height of the cross
+    /// * color - The color of the cross
+    pub fn cross(&mut self, center: &Vector2f, size: f32, color: &Color) -> () {
+        if !self.enabled {
+            return;
+        }
+        let half = size / 2.;
+        self.world.line(&Vector2f::new(center.x - half, center.y),
+                        &Vector2f::new(center.x + half, center.y),
+                        color);
+        self.world.line(&Vector2f::new(center.x, center.y - half),
+                        &Vector2f::new(center.x, center.y + half),
+                        color);
+    }
+
+    /// Draw a text
+    ///
+    /// The text is only drawn if a font is set.
+    ///
+    /// # Arguments
+    /// * string - The string of the text
+    /// * position - The position of the top left corner of the text
+    /// * color - The color of the text
+    pub fn text(&mut self, string: &str, position: &Vector2f, color: &Color) -> () {
+        if self.enabled {
+            self.world.text(string, position, color);
+        }
+    }
+
+    /// Outline the global bounds of a sprite, a shape or a text
+    ///
+    /// # Arguments
+    /// * object - The object
+    /// * color - The color of the outline
+    pub fn outline_bounds<T: Bounded>(&mut self, object: &T, color: &Color) -> () {
+        self.rect(&object.get_global_bounds(), color)
+    }
+
+    /// Mark the origin of a sprite, a shape or a text with a cross
+    ///
+    /// The origin of an object is drawn at its position.
+    ///
+    /// # Arguments
+    /// * object - The object
+    /// * size - The size of the cross
+    /// * color - The color of the cross
+    pub fn mark_origin<T: Transformable>(&mut self, object: &T, size: f32, color: &Color) -> () {
+        self.cross(&object.get_position(), size, color)
+    }
+
+    /// Outline the area of the world shown by a view
+    ///
+    /// The rotation of the view is taken in account and its center is marked
+    /// with a cross.
+    ///
+    /// # Arguments
+    /// * view - The view
+    /// * color - The color of the outline
+    pub fn view(&mut self, view: &View, color: &Color) -> () {
+        if !self.enabled {
+            return;
+        }
+        let center = view.get_center();
+        let size = view.get_size();
+        let angle = view.get_rotation() * PI / 180.;
+        let (sin, cos) = (angle.sin(), angle.cos());
+        let mut corners = Vec::with_capacity(4);
+        for &(x, y) in [(-1f32, -1f32), (1., -1.), (1., 1.), (-1., 1.)].iter() {
+            let local_x = x * size.x / 2.;
+            let local_y = y * size.y / 2.;
+            corners.push(Vector2f::new(center.x + local_x * cos - local_y * sin,
+                                       center.y + local_x * sin + local_y * cos));
+        }
+        for i in range(0u, 4) {
+            self.world.line(&corners[i], &corners[(i + 1) % 4], color);
+        }
+        self.cross(&center, size.x.abs().min(size.y.abs()) / 20., color);
+    }
+
+    /// Record the duration of a frame for the frame graph
+    ///
+    /// # Arguments
+    /// * frame_time - The duration of the last frame
+    pub fn add_frame_time(&mut self, frame_time: Time) -> () {
+        if self.frame_times.len() >= self.frame_time_capacity {
+            self.frame_times.remove(0);
+        }
+        self.frame_times.push(frame_time.as_seconds());
+    }
+
+    /// Set the number of frames shown by the frame graph
+    ///
+    /// # Arguments
+    /// * capacity - The number of frames, 120 by default and at least 1
+    pub fn set_frame_time_capacity(&mut self, capacity: uint) -> () {
+        let capacity = capacity.max(1);
+        self.frame_time_capacity = capacity;
+        while self.frame_times.len() > capacity {
+            self.frame_times.remove(0);
+        }
+    }
+
+    /// Get the number of frames shown by the frame graph
+    ///
+    /// Return the number of frames
+    pub fn get_frame_time_capacity(&self) -> uint {
+        self.frame_time_capacity
+    }
+
+    /// Get the average duration of the recorded frames
+    ///
+    /// Return the average frame time in seconds, or 0 if no frame is recorded
+    pub fn get_average_frame_time(&self) -> f32 {
+        if self.frame_times.is_empty() {
+            return 0.;
+        }
+        let total = self.frame_times.iter().fold(0., |total, &time| total + time);
+        total / self.frame_times.len() as f32
+    }
+
+    /// Draw the graph of the recorded frame times
+    ///
+    /// Each frame is a vertical bar, green under the 60 frames per second
+    /// reference line, yellow under 30 frames per second and red above. The
+    /// average frame rate and frame time are written above the graph if a
+    /// font is set.
+    ///
+    /// # Arguments
+    /// * area - The area of the graph, in pixels
+    /// * max_frame_time - The frame time at the top of the graph, in seconds
+    pub fn frame_graph(&mut self, area: &FloatRect, max_frame_time: f32) -> () {
+        if !self.enabled || max_frame_time <= 0. {
+            return;
+        }
+        self.overlay.fill_rect(area, &Color::new_RGBA(0, 0, 0, 160));
+        let bottom = area.top + area.height;
+        let bar_width = area.width / self.frame_time_capacity as f32;
+        for (i, &time) in self.frame_times.iter().enumerate() {
+            let color = if time <= TARGET_FRAME_TIME {
+                Color::green()
+            } else if time <= TARGET_FRAME_TIME * 2. {
+                Color::yellow()
+            } else {
+                Color::red()
+            };
+            let height = (time / max_frame_time).min(1.) * area.height;
+            let x = area.left + (i as f32 + 0.5) * bar_width;
+            self.overlay.line(&Vector2f::new(x, bottom),
+                              &Vector2f::new(x, bottom - height),
+                              &color);
+        }
+        if TARGET_FRAME_TIME < max_frame_time {
+            let y = bottom - TARGET_FRAME_TIME / max_frame_time * area.height;
+            self.overlay.line(&Vector2f::new(area.left, y),
+                              &Vector2f::new(area.left + area.width, y),
+                              &Color::white());
+        }
+        let average = self.get_average_frame_time();
+        if average > 0. {
+            let label = format!("{:.1} fps  {:.2} ms", 1. / average, average * 1000.);
+            let position = Vector2f::new(area.left,
+                                         area.top - self.character_size as f32 - 4.);
+            self.overlay.text(label.as_slice(), &position, &Color::white());
+        }
+    }
+
+    /// Remove the geometry accumulated since the last flush
+    pub fn clear(&mut self) -> () {
+        self.world.clear();
+        self.overlay.clear();
+    }
+
+    /// Draw the accumulated geometry and clear it
+    ///
+    /// Call it once per frame, after everything else is drawn.
+    ///
+    /// # Arguments
+    /// * render_target - The target to draw on
+    pub fn flush<RT: RenderTarget>(&mut self, render_target: &mut RT) -> () {
+        if self.enabled {
+            self.world.draw(render_target, self.font, self.character_size);
+            let previous = render_target.get_view();
+            let default = render_target.get_default_view();
+            render_target.set_view(&default);
+            self.overlay.draw(render_target, self.font, self.character_size);
+            render_target.set_view(&previous);
+        }
+        self.clear();
+    }
+}
+
+// The corners of a rectangle, clockwise from the top left one
+#[doc(hidden)]
+fn corners(rect: &FloatRect) -> [Vector2f, ..4] {
+    [Vector2f::new(rect.left, rect.top),
+     Vector2f::new(rect.left + rect.width, rect.top),
+     Vector2f::new(rect.left + rect.width, rect.top + rect.height),
+     Vector2f::new(rect.left, rect.top + rect.height)]
+}
@@ -0,0 +1,321 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Recorded draw commands, sorted and culled before drawing
+//!
+//! A DrawList records what to draw during the update, with a layer and a
+//! depth, and draws it later in a deterministic order, skipping what the
+//! view doesn't show. The recorded commands can be inspected, which makes
+//! the draw order testable without a window.
+//!
+//! The drawable type is shared by the whole list: to mix sprites, texts
+//! and shapes, implement Drawable for an enum of references to them.
+
+use std::num::Float;
+use std::cmp::Ordering;
+
+use traits::Drawable;
+use graphics::{View, FloatRect, Transform, Texture, Shader, BlendMode, BlendAlpha,
+               RenderTarget, RenderStates};
+
+/// A recorded draw command
+pub struct DrawCommand<'a, D: 'a> {
+    /// The object to draw
+    pub drawable: &'a D,
+    /// Blending mode
+    pub blend_mode: BlendMode,
+    /// Transform
+    pub transform: Transform,
+    /// Texture
+    pub texture: Option<&'a Texture>,
+    /// Shader
+    pub shader: Option<&'a Shader<'a>>,
+    /// Layer, lower layers are drawn first
+    pub layer: i32,
+    /// Depth inside the layer, lower depths are drawn first
+    pub depth: f32,
+    /// Global bounds used for culling, None to never cull the command
+    pub bounds: Option<FloatRect>,
+    /// Index of the command in the recording order
+    pub index: uint
+}
+
+/// Recorded draw commands, sorted and culled before drawing
+pub struct DrawList<'a, D: 'a> {
+    #[doc(hidden)]
+    commands: Vec<DrawCommand<'a, D>>,
+    #[doc(hidden)]
+    sorted: bool
+}
+
+impl<'a, D: Drawable + 'a> DrawList<'a, D> {
+    /// Create a new empty draw list
+    ///
+    /// Return a new DrawList
+    pub fn new() -> DrawList<'a, D> {
+        DrawList {
+            commands: Vec::new(),
+            sorted: true
+        }
+    }
+
+    /// Record a draw command with the default render states
+    ///
+    /// # Arguments
+    /// * drawable - The object to draw
+    /// * layer - The layer, lower layers are drawn first
+    /// * depth - The depth inside the layer, lower depths are drawn first
+    /// * bounds - The global bounds of the object, None to never cull it
+    pub fn add(&mut self,
+               drawable: &'a D,
+               layer: i32,
+               depth: f32,
+               bounds: Option<FloatRect>) -> () {
+        self.push(drawable, BlendAlpha, Transform::new_identity(), None, None,
+                  layer, depth, bounds)
+    }
+
+    /// Record a draw command with render states
+    ///
+    /// # Arguments
+    /// * drawable - The object to draw
+    /// * render_states - The render states used to draw the object
+    /// * layer - The layer, lower layers are drawn first
+    /// * depth - The depth inside the layer, lower depths are drawn first
+    /// * bounds - The global bounds of the object, None to never cull it
+    pub fn add_rs(&mut self,
+                  drawable: &'a D,
+                  render_states: &RenderStates<'a>,
+                  layer: i32,
+                  depth: f32,
+                  bounds: Option<FloatRect>) -> () {
+        self.push(drawable,
+                  render_states.blendMode,
+                  render_states.transform,
+                  render_states.texture,
+                  render_states.shader,
+                  layer,
+                  depth,
+                  bounds)
+    }
+
+    /// Remove all the recorded commands
+    pub fn clear(&mut self) -> () {
+        self.commands.clear();
+        self.sorted = true;
+    }
+
+    /// Get the number of recorded commands
+    ///
+    /// Return the number of commands
+    pub fn len(&self) -> uint {
+        self.commands.len()
+    }
+
+    /// Tell whether the list is empty
+    ///
+    /// Return true if no command was recorded, false otherwise
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Sort the commands by layer, then by depth
+    ///
+    /// Commands with the same layer and depth keep their recording order.
+    pub fn sort(&mut self) -> () {
+        if !self.sorted {
+            self.commands.sort_by(|a, b| {
+                match a.layer.cmp(&b.layer) {
+                    Ordering::Equal => match a.depth.partial_cmp(&b.depth) {
+                        Some(Ordering::Equal) | None => a.index.cmp(&b.index),
+                        Some(ordering) => ordering
+                    },
+                    ordering => ordering
+                }
+            });
+            self.sorted = true;
+        }
+    }
+
+    /// Get the recorded commands
+    ///
+    /// They are in recording order until the list is sorted.
+    ///
+    /// Return the commands
+    pub fn get_commands(&self) -> &[DrawCommand<'a, D>] {
+        self.commands.as_slice()
+    }
+
+    /// Get the commands visible in an area, in the order they are drawn
+    ///
+    /// # Arguments
+    /// * area - The visible area, in world coordinates
+    ///
+    /// Return the visible commands
+    pub fn get_visible_commands(&mut self, area: &FloatRect) -> Vec<&DrawCommand<'a, D>> {
+        self.sort();
+        self.commands.iter().filter(|command| is_visible(command.bounds, area)).collect()
+    }
+
+    /// Draw the visible commands to a render target, then clear the list
+    ///
+    /// The commands are culled against the current view of the target.
+    ///
+    /// # Arguments
+    /// * render_target - The target to draw to
+    ///
+    /// Return the number of commands drawn
+    pub fn flush<RT: RenderTarget>(&mut self, render_target: &mut RT) -> uint {
+        let area = get_view_bounds(&render_target.get_view());
+        self.sort();
+        let mut drawn = 0;
+        for command in self.commands.iter() {
+            if !is_visible(command.bounds, &area) {
+                continue;
+            }
+            let mut states = RenderStates::new(command.blend_mode,
+                                               command.transform,
+                                               command.texture,
+                                               command.shader);
+            command.drawable.draw_rs(render_target, &mut states);
+            drawn += 1;
+        }
+        self.clear();
+        drawn
+    }
+
+    #[doc(hidden)]
+    fn push(&mut self,
+            drawable: &'a D,
+            blend_mode: BlendMode,
+            transform: Transform,
+            texture: Option<&'a Texture>,
+            shader: Option<&'a Shader<'a>>,
+            layer: i32,
+            depth: f32,
+            bounds: Option<FloatRect>) -> () {
+        let index = self.commands.len();
+        self.commands.push(DrawCommand {
+            drawable: drawable,
+            blend_mode: blend_mode,
+            transform: transform,
+            texture: texture,
+            shader: shader,
+            layer: layer,
+            depth: depth,
+            bounds: bounds,
+            index: index
+        });
+        self.sorted = false;
+    }
+}
+
+// Bounding rectangle of the area shown by a view
+#[doc(hidden)]
+fn get_view_bounds(view: &View) -> FloatRect {
+    let center = view.get_center();
+    let size = view.get_size();
+    let angle = view.get_rotation().to_radians();
+    let (sin, cos) = (angle.sin().abs(), angle.cos().abs());
+    let width = size.x.abs() * cos + size.y.abs() * sin;
+    let height = size.x.abs() * sin + size.y.abs() * cos;
+    FloatRect::new(center.x - width / 2., center.y - height / 2., width, height)
+}
+
+#[doc(hidden)]
+fn is_visible(bounds: Option<FloatRect>, area: &FloatRect) -> bool {
+    match bounds {
+        Some(bounds) => bounds.left <= area.left + area.width &&
+                        area.left <= bounds.left + bounds.width &&
+                        bounds.top <= area.top + area.height &&
+                        area.top <= bounds.top + bounds.height,
+        None => true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DrawList, is_visible};
+    use traits::Drawable;
+    use graphics::{FloatRect, RenderTarget};
+
+    struct Dummy;
+
+    impl Drawable for Dummy {
+        fn draw<RT: RenderTarget>(&self, _: &mut RT) -> () {}
+    }
+
+    fn indices(list: &DrawList<Dummy>) -> Vec<uint> {
+        list.get_commands().iter().map(|command| command.index).collect()
+    }
+
+    #[test]
+    fn sort_by_layer_then_depth() {
+        let dummy = Dummy;
+        let mut list = DrawList::new();
+        list.add(&dummy, 1, 0., None);
+        list.add(&dummy, 0, 2., None);
+        list.add(&dummy, 0, 1., None);
+        list.add(&dummy, -1, 5., None);
+        assert_eq!(indices(&list), vec![0, 1, 2, 3]);
+        list.sort();
+        assert_eq!(indices(&list), vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn sort_keeps_recording_order() {
+        let dummy = Dummy;
+        let mut list = DrawList::new();
+        list.add(&dummy, 0, 1., None);
+        list.add(&dummy, 0, 0., None);
+        list.add(&dummy, 0, 1., None);
+        list.add(&dummy, 0, 0., None);
+        list.sort();
+        assert_eq!(indices(&list), vec![1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn cull_outside_area() {
+        let area = FloatRect::new(0., 0., 100., 100.);
+        assert!(is_visible(Some(FloatRect::new(50., 50., 10., 10.)), &area));
+        assert!(is_visible(Some(FloatRect::new(-10., -10., 20., 20.)), &area));
+        assert!(!is_visible(Some(FloatRect::new(150., 0., 10., 10.)), &area));
+        assert!(!is_visible(Some(FloatRect::new(0., -20., 10., 10.)), &area));
+        assert!(is_visible(None, &area));
+    }
+
+    #[test]
+    fn visible_commands_in_draw_order() {
+        let dummy = Dummy;
+        let mut list = DrawList::new();
+        list.add(&dummy, 1, 0., Some(FloatRect::new(10., 10., 10., 10.)));
+        list.add(&dummy, 0, 0., Some(FloatRect::new(500., 500., 10., 10.)));
+        list.add(&dummy, 0, 0., None);
+        let area = FloatRect::new(0., 0., 100., 100.);
+        let visible: Vec<uint> = list.get_visible_commands(&area).iter()
+                                     .map(|command| command.index).collect();
+        assert_eq!(visible, vec![2, 0]);
+        assert_eq!(list.len(), 3);
+    }
+}
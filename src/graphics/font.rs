@@ -27,6 +27,7 @@
 
 use libc::{c_uint, size_t};
 use std::ptr;
+use std::sync::Arc;
 
 use traits::Wrappable;
 use graphics::{Texture, Glyph};
@@ -39,7 +40,10 @@ pub struct Font {
     #[doc(hidden)]
     font: *mut ffi::sfFont,
     #[doc(hidden)]
-    dropable: bool
+    dropable: bool,
+    // The data read by a font created from a Vec, shared with its copies
+    #[doc(hidden)]
+    data: Option<Arc<Vec<u8>>>
 }
 
 impl Font {
@@ -61,7 +65,8 @@ impl Font {
         } else {
             Some(Font {
                     font: fnt,
-                    dropable: true
+                    dropable: true,
+                    data: None
                 })
         }
     }
@@ -81,7 +86,36 @@ impl Font {
         } else {
             Some(Font {
                     font: fnt,
-                    dropable: true
+                    dropable: true,
+                    data: None
+                })
+        }
+    }
+
+    /// Create a new font from a file loaded in memory, owned by the font
+    ///
+    /// SFML reads the data of a font as long as it is used, so the data is
+    /// kept with the font and freed when the font and its copies are
+    /// destroyed.
+    ///
+    /// # Arguments
+    /// * data - The in-memory font file
+    ///
+    /// Return Some(Font) or None
+    pub fn new_from_vec(data: Vec<u8>) -> Option<Font> {
+        if data.is_empty() {
+            return None;
+        }
+        let fnt = unsafe {
+            ffi::sfFont_createFromMemory(&data[0], data.len() as size_t)
+        };
+        if fnt.is_null() {
+            None
+        } else {
+            Some(Font {
+                    font: fnt,
+                    dropable: true,
+                    data: Some(Arc::new(data))
                 })
         }
     }
@@ -99,7 +133,8 @@ impl Font {
         } else {
             Some(Font {
                     font: fnt,
-                    dropable: true
+                    dropable: true,
+                    data: self.data.clone()
                 })
         }
     }
@@ -189,7 +224,8 @@ impl Clone for Font {
         } else {
             Font {
                 font: fnt,
-                dropable: true
+                dropable: true,
+                data: self.data.clone()
             }
         }
     }
@@ -199,7 +235,8 @@ impl Wrappable<*mut ffi::sfFont> for Font {
     fn wrap(font: *mut ffi::sfFont) -> Font {
         Font {
             font: font,
-            dropable: false
+            dropable: false,
+            data: None
         }
     }
     fn unwrap(&self) -> *mut ffi::sfFont {
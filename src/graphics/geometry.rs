@@ -0,0 +1,155 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Internal vector math shared by the pure Rust geometry builders
+
+use std::num::Float;
+use std::f32::consts::PI;
+
+use graphics::{Color, Vertex, VertexArray, PrimitiveType};
+use system::vector2::Vector2f;
+
+/// Dot product of two vectors
+pub fn dot(a: Vector2f, b: Vector2f) -> f32 {
+    a.x * b.x + a.y * b.y
+}
+
+/// Z component of the cross product of two vectors
+pub fn cross(a: Vector2f, b: Vector2f) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Length of a vector
+pub fn length(v: Vector2f) -> f32 {
+    (v.x * v.x + v.y * v.y).sqrt()
+}
+
+/// Distance between two points
+pub fn distance(a: Vector2f, b: Vector2f) -> f32 {
+    length(b - a)
+}
+
+/// Unit vector with the same direction, or the null vector
+pub fn normalize(v: Vector2f) -> Vector2f {
+    let len = length(v);
+    if len > 0. {
+        v / len
+    } else {
+        Vector2f::new(0., 0.)
+    }
+}
+
+/// Vector rotated by 90 degrees
+pub fn perpendicular(v: Vector2f) -> Vector2f {
+    Vector2f::new(-v.y, v.x)
+}
+
+/// Linear interpolation between two points
+pub fn lerp(a: Vector2f, b: Vector2f, t: f32) -> Vector2f {
+    a + (b - a) * t
+}
+
+/// Linear interpolation between two colors, channel by channel
+pub fn lerp_color(from: &Color, to: &Color, t: f32) -> Color {
+    from.lerp(to, t)
+}
+
+/// Point on a circle, the angle is in radians
+pub fn point_on_circle(center: Vector2f, radius: f32, angle: f32) -> Vector2f {
+    Vector2f::new(center.x + radius * angle.cos(),
+                  center.y + radius * angle.sin())
+}
+
+/// Convert an angle in degrees to radians
+pub fn to_radians(degrees: f32) -> f32 {
+    degrees * PI / 180.
+}
+
+/// Convert an angle in radians to degrees
+pub fn to_degrees(radians: f32) -> f32 {
+    radians * 180. / PI
+}
+
+/// Number of segments needed to approximate an arc within a tolerance
+///
+/// The sweep is in radians, the result is at least 1.
+pub fn arc_segments(radius: f32, sweep: f32, tolerance: f32) -> uint {
+    if radius <= tolerance || tolerance <= 0. {
+        return 1;
+    }
+    let step = 2. * (1. - tolerance / radius).acos();
+    let count = (sweep.abs() / step).ceil();
+    if count < 1. {
+        1
+    } else if count > 1024. {
+        1024
+    } else {
+        count as uint
+    }
+}
+
+/// Append a triangle fan around a center as individual triangles
+///
+/// The angles are in radians.
+pub fn push_fan(center: Vector2f,
+                radius: f32,
+                start_angle: f32,
+                sweep: f32,
+                tolerance: f32,
+                triangles: &mut Vec<Vector2f>) -> () {
+    let count = arc_segments(radius, sweep, tolerance);
+    let step = sweep / count as f32;
+    let mut previous = point_on_circle(center, radius, start_angle);
+    for i in range(1, count + 1) {
+        let next = point_on_circle(center, radius, start_angle + step * i as f32);
+        triangles.push(center);
+        triangles.push(previous);
+        triangles.push(next);
+        previous = next;
+    }
+}
+
+/// Signed area of a closed polygon (positive when clockwise in SFML's y-down space)
+pub fn signed_area(points: &[Vector2f]) -> f32 {
+    let count = points.len();
+    let mut area = 0.;
+    for i in range(0, count) {
+        area += cross(points[i], points[(i + 1) % count]);
+    }
+    area * 0.5
+}
+
+/// Build a VertexArray from a list of vertices
+pub fn to_vertex_array(primitive_type: PrimitiveType,
+                       vertices: &[Vertex]) -> Option<VertexArray> {
+    let mut vertex_array = match VertexArray::new() {
+        Some(vertex_array) => vertex_array,
+        None               => return None
+    };
+    vertex_array.set_primitive_type(primitive_type);
+    for vertex in vertices.iter() {
+        vertex_array.append(vertex);
+    }
+    Some(vertex_array)
+}
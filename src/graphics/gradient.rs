@@ -0,0 +1,417 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Linear and radial gradients with several color stops
+//!
+//! A Gradient can be turned into a mesh of colored triangles covering a
+//! rectangle or any ShapeImpl, or into an Image to use as a texture.
+
+use std::num::Float;
+
+use traits::ShapeImpl;
+use graphics::{Color, Vertex, VertexArray, Image, FloatRect, Triangles};
+use graphics::geometry;
+use system::vector2::Vector2f;
+
+pub use self::GradientKind::{LinearGradient, RadialGradient};
+
+// Maximum difference, per color channel, between the exact color of a
+// point and the one interpolated by the triangles of a mesh
+const COLOR_TOLERANCE: f32 = 2.;
+const MIN_SUBDIVISIONS: uint = 2;
+const MAX_SUBDIVISIONS: uint = 6;
+// Number of triangles above which the triangles of a mesh are not split
+const MAX_TRIANGLES: uint = 4096;
+
+/// Geometry of a gradient
+#[deriving(Clone, PartialEq, Show, Copy)]
+pub enum GradientKind {
+    /// The color changes along the line from start to end
+    LinearGradient {
+        /// Point where the gradient has the color of offset 0
+        start: Vector2f,
+        /// Point where the gradient has the color of offset 1
+        end: Vector2f
+    },
+    /// The color changes with the distance to the center
+    RadialGradient {
+        /// Point where the gradient has the color of offset 0
+        center: Vector2f,
+        /// Distance where the gradient has the color of offset 1
+        radius: f32
+    }
+}
+
+/// Color of a gradient at a given offset
+#[deriving(Clone, PartialEq, Show, Copy)]
+pub struct GradientStop {
+    /// Offset of the stop, between 0 and 1
+    pub offset: f32,
+    /// Color of the gradient at the offset
+    pub color: Color
+}
+
+impl GradientStop {
+    /// Create a new gradient stop
+    ///
+    /// # Arguments
+    /// * offset - Offset of the stop, between 0 and 1
+    /// * color - Color of the gradient at the offset
+    ///
+    /// Return a new GradientStop
+    pub fn new(offset: f32, color: &Color) -> GradientStop {
+        GradientStop {
+            offset: offset,
+            color: *color
+        }
+    }
+}
+
+/// Linear or radial gradient with several color stops
+///
+/// Points before the first stop or after the last one take the color of
+/// the nearest stop.
+#[deriving(Clone, PartialEq, Show)]
+pub struct Gradient {
+    #[doc(hidden)]
+    kind: GradientKind,
+    #[doc(hidden)]
+    stops: Vec<GradientStop>
+}
+
+impl Gradient {
+    /// Create a new gradient without any stop
+    ///
+    /// # Arguments
+    /// * kind - The geometry of the gradient
+    ///
+    /// Return a new Gradient
+    pub fn new(kind: GradientKind) -> Gradient {
+        Gradient {
+            kind: kind,
+            stops: Vec::new()
+        }
+    }
+
+    /// Create a new linear gradient between two colors
+    ///
+    /// # Arguments
+    /// * start - Point where the gradient has the start color
+    /// * end - Point where the gradient has the end color
+    /// * start_color - Color of the gradient at start
+    /// * end_color - Color of the gradient at end
+    ///
+    /// Return a new Gradient
+    pub fn new_linear(start: &Vector2f,
+                      end: &Vector2f,
+                      start_color: &Color,
+                      end_color: &Color) -> Gradient {
+        let mut gradient = Gradient::new(LinearGradient { start: *start, end: *end });
+        gradient.add_stop(0., start_color);
+        gradient.add_stop(1., end_color);
+        gradient
+    }
+
+    /// Create a new radial gradient between two colors
+    ///
+    /// # Arguments
+    /// * center - Center of the gradient
+    /// * radius - Distance to the center where the outer color is reached
+    /// * inner_color - Color of the gradient at the center
+    /// * outer_color - Color of the gradient at radius
+    ///
+    /// Return a new Gradient
+    pub fn new_radial(center: &Vector2f,
+                      radius: f32,
+                      inner_color: &Color,
+                      outer_color: &Color) -> Gradient {
+        let mut gradient = Gradient::new(RadialGradient { center: *center, radius: radius });
+        gradient.add_stop(0., inner_color);
+        gradient.add_stop(1., outer_color);
+        gradient
+    }
+
+    /// Set the geometry of the gradient
+    ///
+    /// # Arguments
+    /// * kind - The new geometry of the gradient
+    pub fn set_kind(&mut self, kind: GradientKind) -> () {
+        self.kind = kind
+    }
+
+    /// Get the geometry of the gradient
+    ///
+    /// Return the geometry of the gradient
+    pub fn get_kind(&self) -> GradientKind {
+        self.kind
+    }
+
+    /// Add a color stop to the gradient
+    ///
+    /// The stops are kept sorted by offset. A stop added at the offset
+    /// of an existing one comes after it, which makes a hard transition.
+    ///
+    /// # Arguments
+    /// * offset - Offset of the stop, between 0 and 1
+    /// * color - Color of the gradient at the offset
+    pub fn add_stop(&mut self, offset: f32, color: &Color) -> () {
+        let index = self.stops.iter().position(|stop| stop.offset > offset)
+                                     .unwrap_or(self.stops.len());
+        self.stops.insert(index, GradientStop::new(offset, color))
+    }
+
+    /// Remove all the color stops of the gradient
+    pub fn clear_stops(&mut self) -> () {
+        self.stops.clear()
+    }
+
+    /// Get the color stops of the gradient, sorted by offset
+    ///
+    /// Return a slice of the stops
+    pub fn get_stops(&self) -> &[GradientStop] {
+        self.stops.as_slice()
+    }
+
+    /// Get the offset of a point in the gradient
+    ///
+    /// The offset is not clamped, it is 0 at the start or the center of
+    /// the gradient and 1 at its end or radius.
+    ///
+    /// # Arguments
+    /// * point - The point to evaluate
+    ///
+    /// Return the offset of the point
+    pub fn get_offset(&self, point: &Vector2f) -> f32 {
+        match self.kind {
+            LinearGradient { start, end } => {
+                let direction = end - start;
+                let length = geometry::dot(direction, direction);
+                if length == 0. {
+                    0.
+                } else {
+                    geometry::dot(*point - start, direction) / length
+                }
+            },
+            RadialGradient { center, radius } => {
+                if radius <= 0. {
+                    0.
+                } else {
+                    geometry::distance(*point, center) / radius
+                }
+            }
+        }
+    }
+
+    /// Get the color of the gradient at a given offset
+    ///
+    /// # Arguments
+    /// * offset - The offset to sample
+    ///
+    /// Return the color at offset, or transparent if there is no stop
+    pub fn sample(&self, offset: f32) -> Color {
+        if self.stops.is_empty() {
+            return Color::transparent();
+        }
+        let first = self.stops[0];
+        if offset < first.offset {
+            return first.color;
+        }
+        for pair in self.stops.as_slice().windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            if offset < to.offset {
+                let t = (offset - from.offset) / (to.offset - from.offset);
+                return geometry::lerp_color(&from.color, &to.color, t);
+            }
+        }
+        self.stops[self.stops.len() - 1].color
+    }
+
+    /// Get the color of the gradient at a given point
+    ///
+    /// # Arguments
+    /// * point - The point to evaluate
+    ///
+    /// Return the color at point
+    pub fn color_at(&self, point: &Vector2f) -> Color {
+        self.sample(self.get_offset(point))
+    }
+
+    /// Build a mesh filling a rectangle with the gradient
+    ///
+    /// # Arguments
+    /// * rect - The rectangle to fill
+    ///
+    /// Return Some(VertexArray) of Triangles or None
+    pub fn mesh_rect(&self, rect: &FloatRect) -> Option<VertexArray> {
+        let corners = [Vector2f::new(rect.left, rect.top),
+                       Vector2f::new(rect.left + rect.width, rect.top),
+                       Vector2f::new(rect.left + rect.width, rect.top + rect.height),
+                       Vector2f::new(rect.left, rect.top + rect.height)];
+        self.mesh_points(&corners)
+    }
+
+    /// Build a mesh filling a shape with the gradient
+    ///
+    /// The mesh is in the local coordinates of the shape: draw it with
+    /// the transform of the Shape to put it at the same place.
+    ///
+    /// # Arguments
+    /// * shape - The shape to fill
+    ///
+    /// Return Some(VertexArray) of Triangles or None
+    pub fn mesh_shape(&self, shape: &ShapeImpl) -> Option<VertexArray> {
+        let points: Vec<Vector2f> = range(0, shape.get_point_count())
+            .map(|index| shape.get_point(index))
+            .collect();
+        self.mesh_points(points.as_slice())
+    }
+
+    /// Build a mesh filling a convex polygon with the gradient
+    ///
+    /// The polygon is filled as a fan around its center, like a Shape.
+    /// Its triangles are subdivided until the interpolated colors are
+    /// close enough to the exact ones, or until the mesh has 4096
+    /// triangles.
+    ///
+    /// # Arguments
+    /// * points - The points of the convex polygon
+    ///
+    /// Return Some(VertexArray) of Triangles or None
+    pub fn mesh_points(&self, points: &[Vector2f]) -> Option<VertexArray> {
+        let mut triangles = Vec::new();
+        if points.len() >= 3 {
+            let mut center = Vector2f::new(0., 0.);
+            for point in points.iter() {
+                center = center + *point;
+            }
+            center = center / points.len() as f32;
+            for i in range(0, points.len()) {
+                triangles.push((center, points[i], points[(i + 1) % points.len()]));
+            }
+        }
+        let mut vertices = Vec::with_capacity(triangles.len() * 3);
+        for &(a, b, c) in self.subdivide(triangles).iter() {
+            vertices.push(Vertex::new_with_pos_color(&a, &self.color_at(&a)));
+            vertices.push(Vertex::new_with_pos_color(&b, &self.color_at(&b)));
+            vertices.push(Vertex::new_with_pos_color(&c, &self.color_at(&c)));
+        }
+        geometry::to_vertex_array(Triangles, vertices.as_slice())
+    }
+
+    /// Generate an image filled with the gradient
+    ///
+    /// The gradient is evaluated at the center of each pixel, in pixel
+    /// coordinates.
+    ///
+    /// # Arguments
+    /// * width - Width of the image
+    /// * height - Height of the image
+    ///
+    /// Return Some(Image) or None
+    pub fn to_image(&self, width: uint, height: uint) -> Option<Image> {
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for y in range(0, height) {
+            for x in range(0, width) {
+                let color = self.color_at(&Vector2f::new(x as f32 + 0.5, y as f32 + 0.5));
+                pixels.push(color.red);
+                pixels.push(color.green);
+                pixels.push(color.blue);
+                pixels.push(color.alpha);
+            }
+        }
+        Image::create_from_pixels(width, height, pixels.as_slice())
+    }
+
+    // Split the triangles in four, one level at a time so that the whole
+    // mesh is refined evenly, until their colors are accurate or the mesh
+    // has too many triangles
+    #[doc(hidden)]
+    fn subdivide(&self,
+                 triangles: Vec<(Vector2f, Vector2f, Vector2f)>)
+                 -> Vec<(Vector2f, Vector2f, Vector2f)> {
+        let mut count = triangles.len();
+        let mut done = Vec::with_capacity(count);
+        let mut pending = triangles;
+        for depth in range(0, MAX_SUBDIVISIONS + 1) {
+            let mut next = Vec::new();
+            for (a, b, c) in pending.into_iter() {
+                if depth == MAX_SUBDIVISIONS || count + 3 > MAX_TRIANGLES ||
+                   (depth >= MIN_SUBDIVISIONS && self.is_accurate(a, b, c)) {
+                    done.push((a, b, c));
+                    continue;
+                }
+                let (ab, bc, ca) = (geometry::lerp(a, b, 0.5),
+                                    geometry::lerp(b, c, 0.5),
+                                    geometry::lerp(c, a, 0.5));
+                next.push((a, ab, ca));
+                next.push((ab, b, bc));
+                next.push((ca, bc, c));
+                next.push((ab, bc, ca));
+                count += 3;
+            }
+            pending = next;
+        }
+        done
+    }
+
+    // Tell if the colors interpolated by a triangle are close enough to the
+    // exact ones
+    #[doc(hidden)]
+    fn is_accurate(&self, a: Vector2f, b: Vector2f, c: Vector2f) -> bool {
+        let (ab, bc, ca) = (geometry::lerp(a, b, 0.5),
+                            geometry::lerp(b, c, 0.5),
+                            geometry::lerp(c, a, 0.5));
+        let (color_a, color_b, color_c) = (self.color_at(&a),
+                                           self.color_at(&b),
+                                           self.color_at(&c));
+        let center = (a + b + c) / 3.;
+        color_distance(&self.color_at(&ab), &geometry::lerp_color(&color_a, &color_b, 0.5))
+            <= COLOR_TOLERANCE &&
+        color_distance(&self.color_at(&bc), &geometry::lerp_color(&color_b, &color_c, 0.5))
+            <= COLOR_TOLERANCE &&
+        color_distance(&self.color_at(&ca), &geometry::lerp_color(&color_c, &color_a, 0.5))
+            <= COLOR_TOLERANCE &&
+        color_distance(&self.color_at(&center), &average_color(&color_a, &color_b, &color_c))
+            <= COLOR_TOLERANCE
+    }
+}
+
+#[doc(hidden)]
+fn average_color(a: &Color, b: &Color, c: &Color) -> Color {
+    let channel = |x: u8, y: u8, z: u8| -> u8 {
+        ((x as f32 + y as f32 + z as f32) / 3.).round() as u8
+    };
+    Color::new_RGBA(channel(a.red, b.red, c.red),
+                    channel(a.green, b.green, c.green),
+                    channel(a.blue, b.blue, c.blue),
+                    channel(a.alpha, b.alpha, c.alpha))
+}
+
+#[doc(hidden)]
+fn color_distance(a: &Color, b: &Color) -> f32 {
+    let channel = |x: u8, y: u8| -> f32 { (x as f32 - y as f32).abs() };
+    channel(a.red, b.red).max(channel(a.green, b.green))
+                         .max(channel(a.blue, b.blue))
+                         .max(channel(a.alpha, b.alpha))
+}
@@ -28,21 +28,36 @@
 pub use graphics::render_target::RenderTarget;
 pub use graphics::render_states::RenderStates;
 pub use graphics::render_window::{RenderWindow, Events};
-pub use graphics::rect::{FloatRect, IntRect};
+pub use graphics::rect::{Rect, FloatRect, IntRect};
 pub use graphics::texture::Texture;
 pub use graphics::blend_mode::{BlendMode, BlendAlpha, BlendAdd, BlendMultiply,
                                BlendNone};
 pub use graphics::transform::Transform;
 pub use graphics::text::Text;
-pub use graphics::shader::Shader;
+pub use graphics::shader::{Shader, Uniform, UniformValue, CurrentTexture, FloatUniform,
+                           Float2Uniform, Float3Uniform, Float4Uniform, ColorUniform,
+                           TransformUniform, TextureUniform, CurrentTextureUniform};
 pub use graphics::color::Color;
 pub use graphics::font::Font;
 pub use graphics::view::View;
+pub use graphics::camera::Camera;
+pub use graphics::scaled_view::{ScaledView, ViewportPolicy, Stretch, Letterbox, Crop,
+                                  IntegerScale, Expand};
+pub use graphics::view_layout::ViewLayout;
+pub use graphics::scene_node::SceneNode;
+pub use graphics::sprite_batch::{SpriteBatch, BatchSortMode, SortDeferred, SortByTexture,
+                                   SortBackToFront};
+pub use graphics::draw_list::{DrawList, DrawCommand};
+pub use graphics::render_stats::{InstrumentedTarget, RenderStats};
+pub use graphics::debug_draw::DebugDraw;
 pub use graphics::image::Image;
 pub use graphics::sprite::Sprite;
+pub use graphics::collision_mask::CollisionMask;
 pub use graphics::circle_shape::CircleShape;
 pub use graphics::rectangle_shape::RectangleShape;
 pub use graphics::convex_shape::{ConvexShape, ConvexShapePoints};
+pub use graphics::polygon_shape::PolygonShape;
+pub use graphics::polyline::Polyline;
 pub use graphics::primitive_type::{PrimitiveType, Points, Lines, LinesStrip,
                                    Triangles, TrianglesStrip, TrianglesFan,
                                    Quads};
@@ -51,14 +66,22 @@ pub use graphics::transformable::Transformable;
 pub use graphics::glyph::Glyph;
 pub use graphics::render_texture::RenderTexture;
 pub use graphics::shape::Shape;
+pub use graphics::gradient::{Gradient, GradientStop, GradientKind, LinearGradient,
+                             RadialGradient};
+pub use graphics::shapes::{RoundedRectangleShape, EllipseShape, RegularPolygonShape,
+                           StarShape, ArcShape, CapsuleShape};
 pub use graphics::vertex_array::{VertexArray, Vertices};
 pub use graphics::text_style::{TextStyle, Regular, Bold, Italic, Underlined};
+pub use graphics::path::{Path, Contour, StrokeStyle, FillRule, EvenOdd, NonZero,
+                         LineJoin, MiterJoin, RoundJoin, BevelJoin,
+                         LineCap, ButtCap, SquareCap, RoundCap};
 
 /// Shapes implementations using reference counting to manage shared resources
 pub mod rc {
     pub use graphics::circle_shape::rc::CircleShape;
     pub use graphics::rectangle_shape::rc::RectangleShape;
     pub use graphics::convex_shape::rc::{ConvexShape, ConvexShapePoints};
+    pub use graphics::polygon_shape::rc::PolygonShape;
     pub use graphics::shape::rc::Shape;
     pub use graphics::shader::rc::Shader;
     pub use graphics::text::rc::Text;
@@ -66,6 +89,10 @@ pub mod rc {
     pub use graphics::render_states::rc::RenderStates;
 }
 
+pub mod polygon;
+pub mod collision;
+pub mod spatial;
+
 #[doc(hidden)]
 #[cfg(any(target_os="macos", target_os="linux", target_os="windows"))]
 mod platform {
@@ -86,11 +113,23 @@ mod shader;
 mod color;
 mod font;
 mod view;
+mod clip_stack;
+mod camera;
+mod scaled_view;
+mod view_layout;
+mod scene_node;
+mod sprite_batch;
+mod draw_list;
+mod render_stats;
+mod debug_draw;
 mod image;
 mod sprite;
+mod collision_mask;
 mod circle_shape;
 mod rectangle_shape;
 mod convex_shape;
+mod polygon_shape;
+mod polyline;
 mod primitive_type;
 mod vertex;
 mod vertex_array;
@@ -98,3 +137,7 @@ mod transformable;
 mod glyph;
 mod render_texture;
 mod shape;
+mod shapes;
+mod path;
+mod geometry;
+mod gradient;
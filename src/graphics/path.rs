@@ -0,0 +1,708 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Vector path made of lines, Bezier curves and arcs
+//!
+//! A Path is flattened into polylines which can be filled or stroked
+//! into a VertexArray of triangles. Unlike ConvexShape, a filled path
+//! can be concave, self-intersecting and contain holes.
+
+use std::mem;
+use std::num::Float;
+use std::cmp::Ordering;
+use std::f32::consts::PI;
+
+use graphics::{Color, Vertex, VertexArray, Triangles};
+use graphics::geometry;
+use system::vector2::Vector2f;
+
+pub use self::FillRule::{EvenOdd, NonZero};
+pub use self::LineJoin::{MiterJoin, RoundJoin, BevelJoin};
+pub use self::LineCap::{ButtCap, SquareCap, RoundCap};
+
+/// Rule used to decide which parts of a path are inside when filling it
+#[deriving(Clone, PartialEq, Eq, PartialOrd, Ord, Show, Copy)]
+pub enum FillRule {
+    /// A point is inside if a ray from it crosses an odd number of edges.
+    EvenOdd,
+    /// A point is inside if the winding number of the edges around it is not zero.
+    NonZero
+}
+
+/// Shape drawn where two segments of a stroke meet
+#[deriving(Clone, PartialEq, Eq, PartialOrd, Ord, Show, Copy)]
+pub enum LineJoin {
+    /// Sharp corner, replaced by a bevel when longer than the miter limit.
+    MiterJoin,
+    /// Rounded corner.
+    RoundJoin,
+    /// Corner cut by a straight line.
+    BevelJoin
+}
+
+/// Shape drawn at the ends of an open stroke
+#[deriving(Clone, PartialEq, Eq, PartialOrd, Ord, Show, Copy)]
+pub enum LineCap {
+    /// The stroke stops exactly at the end point.
+    ButtCap,
+    /// The stroke is extended by half its width.
+    SquareCap,
+    /// The stroke ends with a half circle.
+    RoundCap
+}
+
+/// Parameters used to stroke a path
+#[deriving(Clone, PartialEq, Show, Copy)]
+pub struct StrokeStyle {
+    /// Width of the stroke.
+    pub width: f32,
+    /// Shape of the corners.
+    pub join: LineJoin,
+    /// Shape of the ends of open contours.
+    pub cap: LineCap,
+    /// Maximal ratio between the miter length and half the width.
+    pub miter_limit: f32
+}
+
+/// A flattened subpath of a Path
+#[deriving(Clone, PartialEq, Show)]
+pub struct Contour {
+    /// Points of the contour.
+    pub points: Vec<Vector2f>,
+    /// Is the contour closed?
+    pub closed: bool
+}
+
+#[deriving(Clone, PartialEq, Show)]
+enum PathCommand {
+    MoveTo(Vector2f),
+    LineTo(Vector2f),
+    QuadTo(Vector2f, Vector2f),
+    CubicTo(Vector2f, Vector2f, Vector2f),
+    ArcTo(Vector2f, f32, f32, f32),
+    Close
+}
+
+/// Vector path made of lines, Bezier curves and arcs
+#[deriving(Clone, PartialEq, Show)]
+pub struct Path {
+    #[doc(hidden)]
+    commands: Vec<PathCommand>,
+    #[doc(hidden)]
+    tolerance: f32
+}
+
+#[doc(hidden)]
+struct Edge {
+    top: Vector2f,
+    bottom: Vector2f,
+    winding: i32
+}
+
+#[doc(hidden)]
+struct Span {
+    middle: f32,
+    top: f32,
+    bottom: f32,
+    winding: i32
+}
+
+impl StrokeStyle {
+    /// Create a new StrokeStyle
+    ///
+    /// # Arguments
+    /// * width - Width of the stroke
+    ///
+    /// # Default
+    /// * join - MiterJoin
+    /// * cap - ButtCap
+    /// * miter_limit - 4
+    ///
+    /// Return a StrokeStyle
+    pub fn new(width: f32) -> StrokeStyle {
+        StrokeStyle {
+            width: width,
+            join: MiterJoin,
+            cap: ButtCap,
+            miter_limit: 4.
+        }
+    }
+
+    /// Create a new StrokeStyle
+    ///
+    /// # Arguments
+    /// * width - Width of the stroke
+    /// * join - Shape of the corners
+    /// * cap - Shape of the ends of open contours
+    ///
+    /// Return a StrokeStyle
+    pub fn new_init(width: f32, join: LineJoin, cap: LineCap) -> StrokeStyle {
+        StrokeStyle {
+            width: width,
+            join: join,
+            cap: cap,
+            miter_limit: 4.
+        }
+    }
+}
+
+impl Path {
+    /// Create a new empty path
+    ///
+    /// The default flattening tolerance is 0.25 pixel.
+    ///
+    /// Return a Path
+    pub fn new() -> Path {
+        Path {
+            commands: Vec::new(),
+            tolerance: 0.25
+        }
+    }
+
+    /// Set the maximal distance between the curves and their flattened polylines
+    ///
+    /// # Arguments
+    /// * tolerance - The new tolerance, in pixels
+    pub fn set_tolerance(&mut self, tolerance: f32) -> () {
+        self.tolerance = if tolerance > 0.001 { tolerance } else { 0.001 };
+    }
+
+    /// Get the flattening tolerance of the path
+    ///
+    /// Return the tolerance in pixels
+    pub fn get_tolerance(&self) -> f32 {
+        self.tolerance
+    }
+
+    /// Remove all the commands of the path
+    pub fn clear(&mut self) -> () {
+        self.commands.clear()
+    }
+
+    /// Check if the path contains no command
+    ///
+    /// Return true if the path is empty
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Start a new subpath at the given point
+    ///
+    /// # Arguments
+    /// * point - Start point of the subpath
+    pub fn move_to(&mut self, point: &Vector2f) -> () {
+        self.commands.push(PathCommand::MoveTo(*point))
+    }
+
+    /// Add a straight line from the current point
+    ///
+    /// # Arguments
+    /// * point - End point of the line
+    pub fn line_to(&mut self, point: &Vector2f) -> () {
+        self.commands.push(PathCommand::LineTo(*point))
+    }
+
+    /// Add a quadratic Bezier curve from the current point
+    ///
+    /// # Arguments
+    /// * control - Control point of the curve
+    /// * point - End point of the curve
+    pub fn quad_to(&mut self, control: &Vector2f, point: &Vector2f) -> () {
+        self.commands.push(PathCommand::QuadTo(*control, *point))
+    }
+
+    /// Add a cubic Bezier curve from the current point
+    ///
+    /// # Arguments
+    /// * control1 - First control point of the curve
+    /// * control2 - Second control point of the curve
+    /// * point - End point of the curve
+    pub fn cubic_to(&mut self,
+                    control1: &Vector2f,
+                    control2: &Vector2f,
+                    point: &Vector2f) -> () {
+        self.commands.push(PathCommand::CubicTo(*control1, *control2, *point))
+    }
+
+    /// Add a circular arc
+    ///
+    /// A straight line joins the current point to the start of the arc.
+    /// Angles are in degrees, a positive sweep turns clockwise on screen.
+    ///
+    /// # Arguments
+    /// * center - Center of the arc
+    /// * radius - Radius of the arc
+    /// * start_angle - Angle of the start point of the arc
+    /// * sweep_angle - Angle covered by the arc
+    pub fn arc_to(&mut self,
+                  center: &Vector2f,
+                  radius: f32,
+                  start_angle: f32,
+                  sweep_angle: f32) -> () {
+        self.commands.push(PathCommand::ArcTo(*center,
+                                              radius,
+                                              start_angle,
+                                              sweep_angle))
+    }
+
+    /// Close the current subpath
+    ///
+    /// A straight line joins the current point to the start of the subpath.
+    pub fn close(&mut self) -> () {
+        self.commands.push(PathCommand::Close)
+    }
+
+    /// Flatten the path into polylines
+    ///
+    /// Curves and arcs are subdivided adaptively until they lie within
+    /// the tolerance of the path.
+    ///
+    /// Return the list of contours of the path
+    pub fn flatten(&self) -> Vec<Contour> {
+        let mut contours = Vec::new();
+        let mut current = Contour { points: Vec::new(), closed: false };
+        let mut pen = Vector2f::new(0., 0.);
+
+        for command in self.commands.iter() {
+            match *command {
+                PathCommand::MoveTo(point) => {
+                    let previous = mem::replace(&mut current,
+                                                Contour { points: Vec::new(),
+                                                          closed: false });
+                    push_contour(&mut contours, previous);
+                    current.points.push(point);
+                    pen = point;
+                },
+                PathCommand::LineTo(point) => {
+                    if current.points.is_empty() { current.points.push(pen) }
+                    current.points.push(point);
+                    pen = point;
+                },
+                PathCommand::QuadTo(control, point) => {
+                    if current.points.is_empty() { current.points.push(pen) }
+                    flatten_quad(pen, control, point, self.tolerance, 0,
+                                 &mut current.points);
+                    pen = point;
+                },
+                PathCommand::CubicTo(control1, control2, point) => {
+                    if current.points.is_empty() { current.points.push(pen) }
+                    flatten_cubic(pen, control1, control2, point,
+                                  self.tolerance, 0, &mut current.points);
+                    pen = point;
+                },
+                PathCommand::ArcTo(center, radius, start_angle, sweep_angle) => {
+                    if current.points.is_empty() { current.points.push(pen) }
+                    let start = geometry::to_radians(start_angle);
+                    let sweep = geometry::to_radians(sweep_angle);
+                    let count = geometry::arc_segments(radius, sweep, self.tolerance);
+                    for i in range(0, count + 1) {
+                        let angle = start + sweep * i as f32 / count as f32;
+                        current.points.push(geometry::point_on_circle(center,
+                                                                      radius,
+                                                                      angle));
+                    }
+                    pen = current.points[current.points.len() - 1];
+                },
+                PathCommand::Close => {
+                    if !current.points.is_empty() {
+                        pen = current.points[0];
+                        current.closed = true;
+                        let previous = mem::replace(&mut current,
+                                                    Contour { points: Vec::new(),
+                                                              closed: false });
+                        push_contour(&mut contours, previous);
+                    }
+                }
+            }
+        }
+        push_contour(&mut contours, current);
+        contours
+    }
+
+    /// Tessellate the inside of the path into triangles
+    ///
+    /// Every contour is implicitly closed. Overlapping contours are
+    /// combined according to the fill rule, so concave shapes, holes
+    /// and self-intersections are supported.
+    ///
+    /// # Arguments
+    /// * rule - The fill rule
+    /// * color - The color of the vertices
+    ///
+    /// Return the list of vertices, three per triangle
+    pub fn fill_vertices(&self, rule: FillRule, color: &Color) -> Vec<Vertex> {
+        tessellate_fill(&self.flatten(), rule).iter()
+            .map(|point| Vertex::new_with_pos_color(point, color))
+            .collect()
+    }
+
+    /// Tessellate the inside of the path into a VertexArray of Triangles
+    ///
+    /// # Arguments
+    /// * rule - The fill rule
+    /// * color - The color of the vertices
+    ///
+    /// Return Some(VertexArray) or None
+    pub fn fill(&self, rule: FillRule, color: &Color) -> Option<VertexArray> {
+        geometry::to_vertex_array(Triangles,
+                                  self.fill_vertices(rule, color).as_slice())
+    }
+
+    /// Tessellate the outline of the path into triangles
+    ///
+    /// # Arguments
+    /// * style - The width, joins and caps of the stroke
+    /// * color - The color of the vertices
+    ///
+    /// Return the list of vertices, three per triangle
+    pub fn stroke_vertices(&self,
+                           style: &StrokeStyle,
+                           color: &Color) -> Vec<Vertex> {
+        let mut triangles = Vec::new();
+        for contour in self.flatten().iter() {
+            stroke_contour(contour, style, self.tolerance, &mut triangles);
+        }
+        triangles.iter()
+            .map(|point| Vertex::new_with_pos_color(point, color))
+            .collect()
+    }
+
+    /// Tessellate the outline of the path into a VertexArray of Triangles
+    ///
+    /// # Arguments
+    /// * style - The width, joins and caps of the stroke
+    /// * color - The color of the vertices
+    ///
+    /// Return Some(VertexArray) or None
+    pub fn stroke(&self,
+                  style: &StrokeStyle,
+                  color: &Color) -> Option<VertexArray> {
+        geometry::to_vertex_array(Triangles,
+                                  self.stroke_vertices(style, color).as_slice())
+    }
+}
+
+#[doc(hidden)]
+fn push_contour(contours: &mut Vec<Contour>, contour: Contour) -> () {
+    let mut points: Vec<Vector2f> = Vec::with_capacity(contour.points.len());
+    for point in contour.points.into_iter() {
+        if points.is_empty() || points[points.len() - 1] != point {
+            points.push(point);
+        }
+    }
+    if contour.closed && points.len() > 1 && points[0] == points[points.len() - 1] {
+        points.pop();
+    }
+    if points.len() > 1 {
+        contours.push(Contour { points: points, closed: contour.closed });
+    }
+}
+
+#[doc(hidden)]
+fn flatten_quad(from: Vector2f,
+                control: Vector2f,
+                to: Vector2f,
+                tolerance: f32,
+                depth: uint,
+                points: &mut Vec<Vector2f>) -> () {
+    let deviation = geometry::length(from - control * 2. + to) * 0.25;
+    if deviation <= tolerance || depth >= 16 {
+        points.push(to);
+    } else {
+        let left = geometry::lerp(from, control, 0.5);
+        let right = geometry::lerp(control, to, 0.5);
+        let middle = geometry::lerp(left, right, 0.5);
+        flatten_quad(from, left, middle, tolerance, depth + 1, points);
+        flatten_quad(middle, right, to, tolerance, depth + 1, points);
+    }
+}
+
+#[doc(hidden)]
+fn flatten_cubic(from: Vector2f,
+                 control1: Vector2f,
+                 control2: Vector2f,
+                 to: Vector2f,
+                 tolerance: f32,
+                 depth: uint,
+                 points: &mut Vec<Vector2f>) -> () {
+    let d1 = geometry::length(from - control1 * 2. + control2);
+    let d2 = geometry::length(control1 - control2 * 2. + to);
+    if d1.max(d2) * 0.75 <= tolerance || depth >= 16 {
+        points.push(to);
+    } else {
+        let ab = geometry::lerp(from, control1, 0.5);
+        let bc = geometry::lerp(control1, control2, 0.5);
+        let cd = geometry::lerp(control2, to, 0.5);
+        let abc = geometry::lerp(ab, bc, 0.5);
+        let bcd = geometry::lerp(bc, cd, 0.5);
+        let middle = geometry::lerp(abc, bcd, 0.5);
+        flatten_cubic(from, ab, abc, middle, tolerance, depth + 1, points);
+        flatten_cubic(middle, bcd, cd, to, tolerance, depth + 1, points);
+    }
+}
+
+#[doc(hidden)]
+fn edge_x(edge: &Edge, y: f32) -> f32 {
+    edge.top.x + (edge.bottom.x - edge.top.x) * (y - edge.top.y)
+        / (edge.bottom.y - edge.top.y)
+}
+
+#[doc(hidden)]
+fn is_inside(winding: i32, rule: FillRule) -> bool {
+    match rule {
+        EvenOdd => winding % 2 != 0,
+        NonZero => winding != 0
+    }
+}
+
+// Split the plane in horizontal bands at every vertex and every edge
+// crossing, edges never cross inside a band so the spans between them
+// are trapezoids.
+#[doc(hidden)]
+fn tessellate_fill(contours: &Vec<Contour>, rule: FillRule) -> Vec<Vector2f> {
+    let mut edges = Vec::new();
+    let mut ys = Vec::new();
+    for contour in contours.iter() {
+        let count = contour.points.len();
+        for i in range(0, count) {
+            let a = contour.points[i];
+            let b = contour.points[(i + 1) % count];
+            ys.push(a.y);
+            if a.y < b.y {
+                edges.push(Edge { top: a, bottom: b, winding: 1 });
+            } else if a.y > b.y {
+                edges.push(Edge { top: b, bottom: a, winding: -1 });
+            }
+        }
+    }
+
+    for i in range(0, edges.len()) {
+        for j in range(i + 1, edges.len()) {
+            match segment_intersection(edges[i].top, edges[i].bottom,
+                                       edges[j].top, edges[j].bottom) {
+                Some(point) => ys.push(point.y),
+                None        => {}
+            }
+        }
+    }
+
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    ys.dedup();
+
+    let mut triangles = Vec::new();
+    let mut spans: Vec<Span> = Vec::new();
+    for band in ys.as_slice().windows(2) {
+        let (y0, y1) = (band[0], band[1]);
+        if y1 - y0 < 1e-6 {
+            continue;
+        }
+        let middle = (y0 + y1) * 0.5;
+        spans.clear();
+        for edge in edges.iter() {
+            if edge.top.y <= middle && edge.bottom.y >= middle {
+                spans.push(Span {
+                    middle: edge_x(edge, middle),
+                    top: edge_x(edge, y0),
+                    bottom: edge_x(edge, y1),
+                    winding: edge.winding
+                });
+            }
+        }
+        spans.sort_by(|a, b| a.middle.partial_cmp(&b.middle).unwrap_or(Ordering::Equal));
+
+        let mut winding = 0;
+        let mut left_top = 0.;
+        let mut left_bottom = 0.;
+        for span in spans.iter() {
+            let was_inside = is_inside(winding, rule);
+            winding += span.winding;
+            let inside = is_inside(winding, rule);
+            if !was_inside && inside {
+                left_top = span.top;
+                left_bottom = span.bottom;
+            } else if was_inside && !inside {
+                let top_left = Vector2f::new(left_top, y0);
+                let top_right = Vector2f::new(span.top, y0);
+                let bottom_right = Vector2f::new(span.bottom, y1);
+                let bottom_left = Vector2f::new(left_bottom, y1);
+                triangles.push(top_left);
+                triangles.push(top_right);
+                triangles.push(bottom_right);
+                triangles.push(top_left);
+                triangles.push(bottom_right);
+                triangles.push(bottom_left);
+            }
+        }
+    }
+    triangles
+}
+
+#[doc(hidden)]
+fn segment_intersection(a0: Vector2f,
+                        a1: Vector2f,
+                        b0: Vector2f,
+                        b1: Vector2f) -> Option<Vector2f> {
+    let da = a1 - a0;
+    let db = b1 - b0;
+    let denominator = geometry::cross(da, db);
+    if denominator.abs() < 1e-9 {
+        return None;
+    }
+    let t = geometry::cross(b0 - a0, db) / denominator;
+    let u = geometry::cross(b0 - a0, da) / denominator;
+    if t > 0. && t < 1. && u > 0. && u < 1. {
+        Some(a0 + da * t)
+    } else {
+        None
+    }
+}
+
+#[doc(hidden)]
+fn push_quad(a: Vector2f,
+             b: Vector2f,
+             c: Vector2f,
+             d: Vector2f,
+             triangles: &mut Vec<Vector2f>) -> () {
+    triangles.push(a);
+    triangles.push(b);
+    triangles.push(c);
+    triangles.push(b);
+    triangles.push(d);
+    triangles.push(c);
+}
+
+#[doc(hidden)]
+fn stroke_contour(contour: &Contour,
+                  style: &StrokeStyle,
+                  tolerance: f32,
+                  triangles: &mut Vec<Vector2f>) -> () {
+    let points = &contour.points;
+    let count = points.len();
+    let half_width = style.width * 0.5;
+    if count < 2 || half_width <= 0. {
+        return;
+    }
+    let segment_count = if contour.closed { count } else { count - 1 };
+
+    for i in range(0, segment_count) {
+        let a = points[i];
+        let b = points[(i + 1) % count];
+        let normal = geometry::perpendicular(geometry::normalize(b - a)) * half_width;
+        push_quad(a + normal, a - normal, b + normal, b - normal, triangles);
+    }
+
+    let (first_join, last_join) = if contour.closed { (0, count) } else { (1, count - 1) };
+    for i in range(first_join, last_join) {
+        let previous = points[(i + count - 1) % count];
+        let point = points[i];
+        let next = points[(i + 1) % count];
+        push_join(previous, point, next, style, half_width, tolerance, triangles);
+    }
+
+    if !contour.closed {
+        let start_direction = geometry::normalize(points[1] - points[0]);
+        let end_direction = geometry::normalize(points[count - 1] - points[count - 2]);
+        push_cap(points[0], start_direction * -1., style.cap, half_width,
+                 tolerance, triangles);
+        push_cap(points[count - 1], end_direction, style.cap, half_width,
+                 tolerance, triangles);
+    }
+}
+
+#[doc(hidden)]
+fn push_join(previous: Vector2f,
+             point: Vector2f,
+             next: Vector2f,
+             style: &StrokeStyle,
+             half_width: f32,
+             tolerance: f32,
+             triangles: &mut Vec<Vector2f>) -> () {
+    let incoming = geometry::normalize(point - previous);
+    let outgoing = geometry::normalize(next - point);
+    let turn = geometry::cross(incoming, outgoing);
+    if turn.abs() < 1e-6 && geometry::dot(incoming, outgoing) > 0. {
+        return;
+    }
+
+    // The join is built on the outer side of the turn
+    let side = if turn > 0. { -1. } else { 1. };
+    let normal_in = geometry::perpendicular(incoming) * side;
+    let normal_out = geometry::perpendicular(outgoing) * side;
+    let outer_in = point + normal_in * half_width;
+    let outer_out = point + normal_out * half_width;
+
+    match style.join {
+        BevelJoin => {
+            triangles.push(point);
+            triangles.push(outer_in);
+            triangles.push(outer_out);
+        },
+        MiterJoin => {
+            let miter = geometry::normalize(normal_in + normal_out);
+            let cos_half = geometry::dot(miter, normal_in);
+            if cos_half > 1e-4 && 1. / cos_half <= style.miter_limit {
+                let tip = point + miter * (half_width / cos_half);
+                triangles.push(point);
+                triangles.push(outer_in);
+                triangles.push(tip);
+                triangles.push(point);
+                triangles.push(tip);
+                triangles.push(outer_out);
+            } else {
+                triangles.push(point);
+                triangles.push(outer_in);
+                triangles.push(outer_out);
+            }
+        },
+        RoundJoin => {
+            let start = normal_in.y.atan2(normal_in.x);
+            let sweep = geometry::cross(normal_in, normal_out)
+                .atan2(geometry::dot(normal_in, normal_out));
+            geometry::push_fan(point, half_width, start, sweep, tolerance, triangles);
+        }
+    }
+}
+
+#[doc(hidden)]
+fn push_cap(point: Vector2f,
+            direction: Vector2f,
+            cap: LineCap,
+            half_width: f32,
+            tolerance: f32,
+            triangles: &mut Vec<Vector2f>) -> () {
+    let normal = geometry::perpendicular(direction) * half_width;
+    match cap {
+        ButtCap => {},
+        SquareCap => {
+            let end = point + direction * half_width;
+            push_quad(point + normal, point - normal, end + normal, end - normal,
+                      triangles);
+        },
+        RoundCap => {
+            let start = normal.y.atan2(normal.x);
+            let sweep = if geometry::cross(normal, direction) > 0. { PI } else { -PI };
+            geometry::push_fan(point, half_width, start, sweep, tolerance, triangles);
+        }
+    }
+}
@@ -0,0 +1,399 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Pure functions working on simple polygons
+//!
+//! Polygons are lists of points in SFML's coordinate system, where the y
+//! axis goes down. They are implicitly closed, and may have holes.
+
+use std::num::Float;
+use std::f32;
+use std::cmp::Ordering;
+
+use graphics::geometry;
+use system::vector2::Vector2f;
+
+pub use self::Winding::{Clockwise, CounterClockwise};
+
+/// Order of the points of a polygon, as seen on screen
+#[deriving(Clone, PartialEq, Eq, PartialOrd, Ord, Show, Copy)]
+pub enum Winding {
+    /// The points turn clockwise.
+    Clockwise,
+    /// The points turn counter clockwise.
+    CounterClockwise
+}
+
+/// Compute the signed area of a polygon
+///
+/// # Arguments
+/// * points - The points of the polygon
+///
+/// Return the area, positive if the points turn clockwise on screen
+pub fn signed_area(points: &[Vector2f]) -> f32 {
+    if points.len() < 3 {
+        0.
+    } else {
+        geometry::signed_area(points)
+    }
+}
+
+/// Get the winding of a polygon
+///
+/// # Arguments
+/// * points - The points of the polygon
+///
+/// Return Clockwise or CounterClockwise
+pub fn winding(points: &[Vector2f]) -> Winding {
+    if signed_area(points) < 0. { CounterClockwise } else { Clockwise }
+}
+
+/// Check if a polygon is convex
+///
+/// Collinear points are allowed, a polygon with less than three
+/// points is not convex.
+///
+/// # Arguments
+/// * points - The points of the polygon
+///
+/// Return true if the polygon is convex
+pub fn is_convex(points: &[Vector2f]) -> bool {
+    let count = points.len();
+    if count < 3 {
+        return false;
+    }
+    let mut sign = 0.;
+    for i in range(0, count) {
+        let a = points[i];
+        let b = points[(i + 1) % count];
+        let c = points[(i + 2) % count];
+        let turn = geometry::cross(b - a, c - b);
+        if turn.abs() > 1e-6 {
+            if sign == 0. {
+                sign = turn.signum();
+            } else if turn.signum() != sign {
+                return false;
+            }
+        }
+    }
+    sign != 0.
+}
+
+/// Triangulate a simple polygon with ear clipping
+///
+/// The holes must lie inside the outline and must not overlap.
+/// The windings of the outline and of the holes don't matter.
+///
+/// # Arguments
+/// * outline - The points of the polygon
+/// * holes - The points of each hole of the polygon
+///
+/// Return the list of the points of the triangles, three per triangle
+pub fn triangulate(outline: &[Vector2f], holes: &[Vec<Vector2f>]) -> Vec<Vector2f> {
+    let points = merge_holes(outline, holes);
+    ear_clip(points.as_slice()).iter().map(|&i| points[i]).collect()
+}
+
+/// Decompose a simple polygon into convex polygons
+///
+/// The polygon is triangulated, then adjacent triangles are merged
+/// as long as they stay convex (Hertel-Mehlhorn). The result has at
+/// most four times the optimal number of pieces.
+///
+/// # Arguments
+/// * outline - The points of the polygon
+/// * holes - The points of each hole of the polygon
+///
+/// Return the list of convex polygons, all turning clockwise
+pub fn convex_decomposition(outline: &[Vector2f],
+                            holes: &[Vec<Vector2f>]) -> Vec<Vec<Vector2f>> {
+    let points = merge_holes(outline, holes);
+    if holes.is_empty() && is_convex(points.as_slice()) {
+        return vec![points];
+    }
+
+    let triangles = ear_clip(points.as_slice());
+    let mut pieces: Vec<Vec<uint>> = triangles.as_slice()
+        .chunks(3)
+        .map(|triangle| triangle.to_vec())
+        .collect();
+
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'search: for i in range(0, pieces.len()) {
+            for j in range(i + 1, pieces.len()) {
+                match merge_pieces(pieces[i].as_slice(), pieces[j].as_slice()) {
+                    Some(piece) => {
+                        let candidate: Vec<Vector2f> = piece.iter().map(|&k| points[k]).collect();
+                        if is_convex(candidate.as_slice()) {
+                            pieces[i] = piece;
+                            pieces.remove(j);
+                            merged = true;
+                            break 'search;
+                        }
+                    },
+                    None => {}
+                }
+            }
+        }
+    }
+
+    pieces.iter()
+        .map(|piece| piece.iter().map(|&k| points[k]).collect())
+        .collect()
+}
+
+/// Remove the repeated points of a polygon
+///
+/// Consecutive duplicates and a last point equal to the first one
+/// are removed.
+///
+/// # Arguments
+/// * points - The points of the polygon
+///
+/// Return the cleaned list of points
+pub fn clean(points: &[Vector2f]) -> Vec<Vector2f> {
+    let mut result: Vec<Vector2f> = Vec::with_capacity(points.len());
+    for point in points.iter() {
+        if result.is_empty() || result[result.len() - 1] != *point {
+            result.push(*point);
+        }
+    }
+    while result.len() > 1 && result[0] == result[result.len() - 1] {
+        result.pop();
+    }
+    result
+}
+
+/// Get the points of a polygon with the given winding
+///
+/// # Arguments
+/// * points - The points of the polygon
+/// * winding - The wanted winding
+///
+/// Return the points, reversed if needed
+pub fn with_winding(points: &[Vector2f], winding: Winding) -> Vec<Vector2f> {
+    let mut result = clean(points);
+    if self::winding(result.as_slice()) != winding {
+        result.reverse();
+    }
+    result
+}
+
+// Join the holes to the outline with zero-width bridges, so that the
+// whole polygon can be ear clipped as a single (weakly simple) ring.
+#[doc(hidden)]
+fn merge_holes(outline: &[Vector2f], holes: &[Vec<Vector2f>]) -> Vec<Vector2f> {
+    let mut polygon = with_winding(outline, Clockwise);
+    let mut sorted: Vec<Vec<Vector2f>> = holes.iter()
+        .map(|hole| with_winding(hole.as_slice(), CounterClockwise))
+        .filter(|hole| hole.len() > 2)
+        .collect();
+    sorted.sort_by(|a, b| max_x(b.as_slice()).partial_cmp(&max_x(a.as_slice()))
+                   .unwrap_or(Ordering::Equal));
+    for hole in sorted.iter() {
+        polygon = bridge_hole(polygon.as_slice(), hole.as_slice());
+    }
+    polygon
+}
+
+#[doc(hidden)]
+fn max_x(points: &[Vector2f]) -> f32 {
+    points.iter().fold(f32::NEG_INFINITY, |x, point| x.max(point.x))
+}
+
+#[doc(hidden)]
+fn bridge_hole(polygon: &[Vector2f], hole: &[Vector2f]) -> Vec<Vector2f> {
+    let count = polygon.len();
+    let mut hole_index = 0;
+    for i in range(1, hole.len()) {
+        if hole[i].x > hole[hole_index].x {
+            hole_index = i;
+        }
+    }
+    let origin = hole[hole_index];
+
+    // Cast a ray toward +x and find the closest edge of the polygon
+    let mut closest_x = f32::INFINITY;
+    let mut bridge = None;
+    for i in range(0, count) {
+        let a = polygon[i];
+        let b = polygon[(i + 1) % count];
+        if a.y == b.y || origin.y < a.y.min(b.y) || origin.y > a.y.max(b.y) {
+            continue;
+        }
+        let x = a.x + (origin.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if x >= origin.x && x < closest_x {
+            closest_x = x;
+            bridge = Some(if a.x > b.x { i } else { (i + 1) % count });
+        }
+    }
+    let mut bridge = match bridge {
+        Some(index) => index,
+        None        => return polygon.to_vec()
+    };
+
+    // A reflex vertex inside the triangle formed by the ray may hide the
+    // candidate, the visible one is the closest in angle to the ray
+    let hit = Vector2f::new(closest_x, origin.y);
+    let candidate = polygon[bridge];
+    let mut best_tangent = f32::INFINITY;
+    for i in range(0, count) {
+        let point = polygon[i];
+        if i == bridge || point.x <= origin.x || !is_reflex(polygon, i) {
+            continue;
+        }
+        if point_in_triangle(point, origin, hit, candidate, true) {
+            let tangent = (point.y - origin.y).abs() / (point.x - origin.x);
+            if tangent < best_tangent {
+                best_tangent = tangent;
+                bridge = i;
+            }
+        }
+    }
+
+    let mut result = Vec::with_capacity(count + hole.len() + 2);
+    for i in range(0, bridge + 1) {
+        result.push(polygon[i]);
+    }
+    for i in range(0, hole.len() + 1) {
+        result.push(hole[(hole_index + i) % hole.len()]);
+    }
+    result.push(polygon[bridge]);
+    for i in range(bridge + 1, count) {
+        result.push(polygon[i]);
+    }
+    result
+}
+
+#[doc(hidden)]
+fn is_reflex(polygon: &[Vector2f], index: uint) -> bool {
+    let count = polygon.len();
+    let previous = polygon[(index + count - 1) % count];
+    let next = polygon[(index + 1) % count];
+    geometry::cross(polygon[index] - previous, next - polygon[index]) < 0.
+}
+
+#[doc(hidden)]
+fn point_in_triangle(point: Vector2f,
+                     a: Vector2f,
+                     b: Vector2f,
+                     c: Vector2f,
+                     inclusive: bool) -> bool {
+    let d1 = geometry::cross(b - a, point - a);
+    let d2 = geometry::cross(c - b, point - b);
+    let d3 = geometry::cross(a - c, point - c);
+    if inclusive {
+        (d1 >= 0. && d2 >= 0. && d3 >= 0.) || (d1 <= 0. && d2 <= 0. && d3 <= 0.)
+    } else {
+        (d1 > 0. && d2 > 0. && d3 > 0.) || (d1 < 0. && d2 < 0. && d3 < 0.)
+    }
+}
+
+// Return the indices of the triangles, the polygon must turn clockwise
+#[doc(hidden)]
+fn ear_clip(points: &[Vector2f]) -> Vec<uint> {
+    let mut remaining: Vec<uint> = range(0, points.len()).collect();
+    let mut triangles = Vec::new();
+    if remaining.len() < 3 {
+        return triangles;
+    }
+
+    let mut current = 0;
+    let mut attempts = 0;
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        current = current % count;
+        let previous = remaining[(current + count - 1) % count];
+        let vertex = remaining[current];
+        let next = remaining[(current + 1) % count];
+
+        // When no ear can be found the polygon is degenerate, clip anyway
+        if attempts > count || is_ear(points, remaining.as_slice(), previous, vertex, next) {
+            triangles.push(previous);
+            triangles.push(vertex);
+            triangles.push(next);
+            remaining.remove(current);
+            attempts = 0;
+        } else {
+            current += 1;
+            attempts += 1;
+        }
+    }
+    triangles.push(remaining[0]);
+    triangles.push(remaining[1]);
+    triangles.push(remaining[2]);
+    triangles
+}
+
+#[doc(hidden)]
+fn is_ear(points: &[Vector2f],
+          remaining: &[uint],
+          previous: uint,
+          vertex: uint,
+          next: uint) -> bool {
+    let a = points[previous];
+    let b = points[vertex];
+    let c = points[next];
+    if geometry::cross(b - a, c - b) <= 0. {
+        return false;
+    }
+    for &i in remaining.iter() {
+        let point = points[i];
+        if i == previous || i == vertex || i == next
+            || point == a || point == b || point == c {
+            continue;
+        }
+        if point_in_triangle(point, a, b, c, true) {
+            return false;
+        }
+    }
+    true
+}
+
+// Merge two pieces sharing an edge, the shared edge is traversed in
+// opposite directions by the two pieces.
+#[doc(hidden)]
+fn merge_pieces(first: &[uint], second: &[uint]) -> Option<Vec<uint>> {
+    let first_count = first.len();
+    let second_count = second.len();
+    for i in range(0, first_count) {
+        let a = first[i];
+        let b = first[(i + 1) % first_count];
+        for j in range(0, second_count) {
+            if second[j] == b && second[(j + 1) % second_count] == a {
+                let mut piece = Vec::with_capacity(first_count + second_count - 2);
+                for k in range(0, first_count) {
+                    piece.push(first[(i + 1 + k) % first_count]);
+                }
+                for k in range(2, second_count) {
+                    piece.push(second[(j + k) % second_count]);
+                }
+                return Some(piece);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,626 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+#![allow(missing_copy_implementations)]
+
+//! Specialized shape representing any simple polygon
+//!
+//! Unlike ConvexShape, a PolygonShape can be concave and can have holes.
+//! The polygon is triangulated with ear clipping into a VertexArray,
+//! each time its points change.
+
+use std::rc::Rc;
+use std::cell::{Cell, RefCell};
+
+use traits::{Drawable, Bounded};
+use graphics::{Color, Texture, RenderTarget, FloatRect, IntRect, Transform,
+               Transformable, rc};
+use graphics::polygon_shape::PolygonGeometry;
+use system::vector2::Vector2f;
+
+/// Specialized shape representing any simple polygon
+///
+/// Unlike ConvexShape, a PolygonShape can be concave and can have holes.
+/// The points of the outline and of the holes must be defined in order,
+/// and the holes must lie inside the outline without overlapping.
+pub struct PolygonShape {
+    #[doc(hidden)]
+    points: Vec<Vector2f>,
+    #[doc(hidden)]
+    holes: Vec<Vec<Vector2f>>,
+    #[doc(hidden)]
+    transformable: Transformable,
+    #[doc(hidden)]
+    texture: Option<Rc<RefCell<Texture>>>,
+    #[doc(hidden)]
+    texture_rect: IntRect,
+    #[doc(hidden)]
+    fill_color: Color,
+    #[doc(hidden)]
+    outline_color: Color,
+    #[doc(hidden)]
+    outline_thickness: f32,
+    #[doc(hidden)]
+    geometry: RefCell<PolygonGeometry>,
+    #[doc(hidden)]
+    dirty: Cell<bool>
+}
+
+impl PolygonShape {
+    /// Create a new empty polygon shape
+    ///
+    /// Return Some(PolygonShape) or None
+    pub fn new() -> Option<PolygonShape> {
+        PolygonShape::new_init(&[])
+    }
+
+    /// Create a new polygon shape from a list of points
+    ///
+    /// # Arguments
+    /// * points - The points of the outline of the polygon
+    ///
+    /// Return Some(PolygonShape) or None
+    pub fn new_init(points: &[Vector2f]) -> Option<PolygonShape> {
+        let transformable = match Transformable::new() {
+            Some(transformable) => transformable,
+            None                => return None
+        };
+        let geometry = match PolygonGeometry::new() {
+            Some(geometry) => geometry,
+            None           => return None
+        };
+        Some(PolygonShape {
+                points: points.to_vec(),
+                holes: Vec::new(),
+                transformable: transformable,
+                texture: None,
+                texture_rect: IntRect::new(0, 0, 0, 0),
+                fill_color: Color::white(),
+                outline_color: Color::white(),
+                outline_thickness: 0.,
+                geometry: RefCell::new(geometry),
+                dirty: Cell::new(true)
+            })
+    }
+
+    /// Create a new polygon shape with a texture
+    ///
+    /// # Arguments
+    /// * texture - The texture to apply to the polygon shape
+    /// * points - The points of the outline of the polygon
+    ///
+    /// Return Some(PolygonShape) or None
+    pub fn new_with_texture(texture: Rc<RefCell<Texture>>,
+                            points: &[Vector2f]) -> Option<PolygonShape> {
+        match PolygonShape::new_init(points) {
+            Some(mut shape) => {
+                shape.set_texture(texture, true);
+                Some(shape)
+            },
+            None => None
+        }
+    }
+
+    /// Clone an existing polygon shape
+    ///
+    /// Return Some(PolygonShape) or None
+    pub fn clone_opt(&self) -> Option<PolygonShape> {
+        let transformable = match self.transformable.clone_opt() {
+            Some(transformable) => transformable,
+            None                => return None
+        };
+        let geometry = match PolygonGeometry::new() {
+            Some(geometry) => geometry,
+            None           => return None
+        };
+        Some(PolygonShape {
+                points: self.points.clone(),
+                holes: self.holes.clone(),
+                transformable: transformable,
+                texture: self.texture.clone(),
+                texture_rect: self.texture_rect,
+                fill_color: self.fill_color,
+                outline_color: self.outline_color,
+                outline_thickness: self.outline_thickness,
+                geometry: RefCell::new(geometry),
+                dirty: Cell::new(true)
+            })
+    }
+
+    /// Set all the points of the outline of a polygon shape
+    ///
+    /// # Arguments
+    /// * points - The new points of the outline
+    pub fn set_points(&mut self, points: &[Vector2f]) -> () {
+        self.points = points.to_vec();
+        self.dirty.set(true)
+    }
+
+    /// Get the points of the outline of a polygon shape
+    ///
+    /// Return the points of the outline
+    pub fn get_points(&self) -> &[Vector2f] {
+        self.points.as_slice()
+    }
+
+    /// Set the number of points of the outline of a polygon shape
+    ///
+    /// New points are placed at (0, 0).
+    ///
+    /// # Arguments
+    /// * count - New number of points of the polygon
+    pub fn set_point_count(&mut self, count: uint) -> () {
+        self.points.truncate(count);
+        while self.points.len() < count {
+            self.points.push(Vector2f::new(0., 0.));
+        }
+        self.dirty.set(true)
+    }
+
+    /// Get the total number of points of the outline of a polygon shape
+    ///
+    /// Return the number of points of the shape
+    pub fn get_point_count(&self) -> uint {
+        self.points.len()
+    }
+
+    /// Set the position of a point of the outline
+    ///
+    /// set_point_count must be called first in order to set the total
+    /// number of points. Panic if index is out of the valid range.
+    ///
+    /// # Arguments
+    /// * index - Index of the point to change, in range [0 .. get_point_count() - 1]
+    /// * point - New position of the point
+    pub fn set_point(&mut self, index: uint, point: &Vector2f) -> () {
+        self.points[index] = *point;
+        self.dirty.set(true)
+    }
+
+    /// Get a point of the outline of a polygon shape
+    ///
+    /// Panic if index is out of the valid range.
+    ///
+    /// # Arguments
+    /// * index - Index of the point to get, in range [0 .. get_point_count() - 1]
+    ///
+    /// Return the index-th point of the shape
+    pub fn get_point(&self, index: uint) -> Vector2f {
+        self.points[index]
+    }
+
+    /// Add a hole to a polygon shape
+    ///
+    /// # Arguments
+    /// * points - The points of the hole
+    pub fn add_hole(&mut self, points: &[Vector2f]) -> () {
+        self.holes.push(points.to_vec());
+        self.dirty.set(true)
+    }
+
+    /// Remove all the holes of a polygon shape
+    pub fn clear_holes(&mut self) -> () {
+        self.holes.clear();
+        self.dirty.set(true)
+    }
+
+    /// Get the holes of a polygon shape
+    ///
+    /// Return the points of each hole
+    pub fn get_holes(&self) -> &[Vec<Vector2f>] {
+        self.holes.as_slice()
+    }
+
+    /// Set the position of a polygon shape
+    ///
+    /// This function completely overwrites the previous position.
+    /// See move to apply an offset based on the previous position instead.
+    /// The default position of a polygon Shape object is (0, 0).
+    ///
+    /// # Arguments
+    /// * position - New position
+    pub fn set_position(&mut self, position: &Vector2f) -> () {
+        self.transformable.set_position(position)
+    }
+
+    /// Set the position of a polygon shape
+    ///
+    /// This function completely overwrites the previous position.
+    /// See move to apply an offset based on the previous position instead.
+    /// The default position of a polygon Shape object is (0, 0).
+    ///
+    /// # Arguments
+    /// * x - New x coordinate
+    /// * y - New y coordinate
+    pub fn set_position2f(&mut self, x: f32, y: f32) -> () {
+        self.transformable.set_position(&Vector2f::new(x, y))
+    }
+
+    /// Set the scale factors of a polygon shape
+    ///
+    /// This function completely overwrites the previous scale.
+    /// See scale to add a factor based on the previous scale instead.
+    /// The default scale of a polygon Shape object is (1, 1).
+    ///
+    /// # Arguments
+    /// * scale - New scale factors
+    pub fn set_scale(&mut self, scale: &Vector2f) -> () {
+        self.transformable.set_scale(scale)
+    }
+
+    /// Set the scale factors of a polygon shape
+    ///
+    /// This function completely overwrites the previous scale.
+    /// See scale to add a factor based on the previous scale instead.
+    /// The default scale of a polygon Shape object is (1, 1).
+    ///
+    /// # Arguments
+    /// * scale_x - New x scale factor
+    /// * scale_y - New y scale factor
+    pub fn set_scale2f(&mut self, scale_x: f32, scale_y: f32) -> () {
+        self.transformable.set_scale(&Vector2f::new(scale_x, scale_y))
+    }
+
+    /// Set the local origin of a polygon shape
+    ///
+    /// The origin of an object defines the center point for
+    /// all transformations (position, scale, rotation).
+    /// The coordinates of this point must be relative to the
+    /// top-left corner of the object, and ignore all
+    /// transformations (position, scale, rotation).
+    /// The default origin of a polygon Shape object is (0, 0).
+    ///
+    /// # Arguments
+    /// * origin - New origin
+    pub fn set_origin(&mut self, origin: &Vector2f) -> () {
+        self.transformable.set_origin(origin)
+    }
+
+    /// Set the local origin of a polygon shape
+    ///
+    /// The origin of an object defines the center point for
+    /// all transformations (position, scale, rotation).
+    /// The coordinates of this point must be relative to the
+    /// top-left corner of the object, and ignore all
+    /// transformations (position, scale, rotation).
+    /// The default origin of a polygon Shape object is (0, 0).
+    ///
+    /// # Arguments
+    /// * x - New x origin coordinate
+    /// * y - New y origin coordinate
+    pub fn set_origin2f(&mut self, x: f32, y: f32) -> () {
+        self.transformable.set_origin(&Vector2f::new(x, y))
+    }
+
+    /// Move a polygon shape by a given offset
+    ///
+    /// This function adds to the current position of the object,
+    /// unlike set_position which overwrites it.
+    ///
+    /// # Arguments
+    /// * offset - Offset
+    pub fn move_(&mut self, offset: &Vector2f) -> () {
+        self.transformable.move_(offset)
+    }
+
+    /// Move a polygon shape by a given offset
+    ///
+    /// This function adds to the current position of the object,
+    /// unlike set_position which overwrites it.
+    ///
+    /// # Arguments
+    /// * offset_x - Offset x
+    /// * offset_y - Offset y
+    pub fn move2f(&mut self, offset_x: f32, offset_y: f32) -> () {
+        self.transformable.move_(&Vector2f::new(offset_x, offset_y))
+    }
+
+    /// Scale a polygon shape
+    ///
+    /// This function multiplies the current scale of the object,
+    /// unlike set_scale which overwrites it.
+    ///
+    /// # Arguments
+    /// * factors - Scale factors
+    pub fn scale(&mut self, factors: &Vector2f) -> () {
+        self.transformable.scale(factors)
+    }
+
+    /// Scale a polygon shape
+    ///
+    /// This function multiplies the current scale of the object,
+    /// unlike set_scale which overwrites it.
+    ///
+    /// # Arguments
+    /// * factor_x - Scale factor x
+    /// * factor_y - Scale factor y
+    pub fn scale2f(&mut self, factor_x: f32, factor_y: f32) -> () {
+        self.transformable.scale(&Vector2f::new(factor_x, factor_y))
+    }
+
+    /// Set the orientation of a polygon shape
+    ///
+    /// This function completely overwrites the previous rotation.
+    /// See rotate to add an angle based on the previous rotation instead.
+    /// The default rotation of a polygon Shape object is 0.
+    ///
+    /// # Arguments
+    /// * angle - New rotation, in degrees
+    pub fn set_rotation(&mut self, angle: f32) -> () {
+        self.transformable.set_rotation(angle)
+    }
+
+    /// Rotate a polygon shape
+    ///
+    /// This function adds to the current rotation of the object,
+    /// unlike set_rotation which overwrites it.
+    ///
+    /// # Arguments
+    /// * angle - Angle of rotation, in degrees
+    pub fn rotate(&mut self, angle: f32) -> () {
+        self.transformable.rotate(angle)
+    }
+
+    /// Get the position of a polygon shape
+    ///
+    /// Return the current position
+    pub fn get_position(&self) -> Vector2f {
+        self.transformable.get_position()
+    }
+
+    /// Get the orientation of a polygon shape
+    ///
+    /// The rotation is always in the range [0, 360].
+    ///
+    /// Return the current rotation, in degrees
+    pub fn get_rotation(&self) -> f32 {
+        self.transformable.get_rotation()
+    }
+
+    /// Get the current scale of a polygon shape
+    ///
+    /// Return the current scale factors
+    pub fn get_scale(&self) -> Vector2f {
+        self.transformable.get_scale()
+    }
+
+    /// Get the local origin of a polygon shape
+    ///
+    /// Return the current origin
+    pub fn get_origin(&self) -> Vector2f {
+        self.transformable.get_origin()
+    }
+
+    /// Get the combined transform of a polygon shape
+    ///
+    /// Return transform combining the position/rotation/scale/origin of the object
+    pub fn get_transform(&self) -> Transform {
+        self.transformable.get_transform()
+    }
+
+    /// Get the inverse of the combined transform of a polygon shape
+    ///
+    /// Return inverse of the combined transformations applied to the object
+    pub fn get_inverse_transform(&self) -> Transform {
+        self.transformable.get_inverse_transform()
+    }
+
+    /// Change the source texture of a polygon shape
+    ///
+    /// The texture argument refers to a texture that must
+    /// exist as long as the shape uses it.
+    /// If reset_rect is true, the TextureRect property of
+    /// the shape is automatically adjusted to the size of the new
+    /// texture. If it is false, the texture rect is left unchanged.
+    ///
+    /// # Arguments
+    /// * texture - New texture
+    /// * reset_rect - Should the texture rect be reset to the size of the new texture?
+    pub fn set_texture(&mut self,
+                       texture: Rc<RefCell<Texture>>,
+                       reset_rect: bool) -> () {
+        if reset_rect || self.texture.is_none() {
+            let size = texture.borrow().get_size();
+            self.set_texture_rect(&IntRect::new(0, 0, size.x as i32, size.y as i32));
+        }
+        self.texture = Some(texture);
+    }
+
+    /// Disable Texturing
+    ///
+    /// Disable the current texture and reset the texture rect
+    pub fn disable_texture(&mut self) -> () {
+        self.texture = None;
+        self.set_texture_rect(&IntRect::new(0, 0, 0, 0))
+    }
+
+    /// Get the source texture of a polygon shape
+    ///
+    /// Return the shape's texture
+    pub fn get_texture(&self) -> Option<Rc<RefCell<Texture>>> {
+        self.texture.clone()
+    }
+
+    /// Set the sub-rectangle of the texture that a polygon shape will display
+    ///
+    /// The texture rect is mapped on the bounding rectangle of the
+    /// points of the shape.
+    ///
+    /// # Arguments
+    /// * rect - The rectangle defining the region of the texture to display
+    pub fn set_texture_rect(&mut self, rect: &IntRect) -> () {
+        self.texture_rect = *rect;
+        self.dirty.set(true)
+    }
+
+    /// Get the sub-rectangle of the texture displayed by a polygon shape
+    ///
+    /// Return the texture rectangle of the shape
+    pub fn get_texture_rect(&self) -> IntRect {
+        self.texture_rect
+    }
+
+    /// Set the fill color of a polygon shape
+    ///
+    /// This color is modulated (multiplied) with the shape's
+    /// texture if any. By default, the shape's fill color is opaque white.
+    ///
+    /// # Arguments
+    /// * color - New color of the shape
+    pub fn set_fill_color(&mut self, color: &Color) -> () {
+        self.fill_color = *color;
+        self.dirty.set(true)
+    }
+
+    /// Set the outline color of a polygon shape
+    ///
+    /// By default, the shape's outline color is opaque white.
+    ///
+    /// # Arguments
+    /// * color - New outline color of the shape
+    pub fn set_outline_color(&mut self, color: &Color) -> () {
+        self.outline_color = *color;
+        self.dirty.set(true)
+    }
+
+    /// Set the thickness of a polygon shape's outline
+    ///
+    /// Using zero disables the outline.
+    /// By default, the outline thickness is 0.
+    ///
+    /// # Arguments
+    /// * thickness - New outline thickness
+    pub fn set_outline_thickness(&mut self, thickness: f32) -> () {
+        self.outline_thickness = thickness;
+        self.dirty.set(true)
+    }
+
+    /// Get the fill color of a polygon shape
+    ///
+    /// Return the fill color of the shape
+    pub fn get_fill_color(&self) -> Color {
+        self.fill_color
+    }
+
+    /// Get the outline color of a polygon shape
+    ///
+    /// Return the outline color of the shape
+    pub fn get_outline_color(&self) -> Color {
+        self.outline_color
+    }
+
+    /// Get the outline thickness of a polygon shape
+    ///
+    /// Return the outline thickness of the shape
+    pub fn get_outline_thickness(&self) -> f32 {
+        self.outline_thickness
+    }
+
+    /// Get the local bounding rectangle of a polygon shape
+    ///
+    /// The returned rectangle is in local coordinates, which means
+    /// that it ignores the transformations (translation, rotation,
+    /// scale, ...) that are applied to the entity.
+    ///
+    /// Return the local bounding rectangle of the entity
+    pub fn get_local_bounds(&self) -> FloatRect {
+        self.update_geometry();
+        self.geometry.borrow().bounds
+    }
+
+    /// Get the global bounding rectangle of a polygon shape
+    ///
+    /// The returned rectangle is in global coordinates, which means
+    /// that it takes in account the transformations (translation,
+    /// rotation, scale, ...) that are applied to the entity.
+    ///
+    /// Return the global bounding rectangle of the entity
+    pub fn get_global_bounds(&self) -> FloatRect {
+        self.get_transform().transform_rect(&self.get_local_bounds())
+    }
+
+    #[doc(hidden)]
+    fn update_geometry(&self) -> () {
+        if self.dirty.get() {
+            self.geometry.borrow_mut().update(self.points.as_slice(),
+                                              self.holes.as_slice(),
+                                              &self.texture_rect,
+                                              &self.fill_color,
+                                              &self.outline_color,
+                                              self.outline_thickness);
+            self.dirty.set(false);
+        }
+    }
+}
+
+impl Clone for PolygonShape {
+    /// Return a new PolygonShape or panic! if there is not enough memory
+    fn clone(&self) -> PolygonShape {
+        match self.clone_opt() {
+            Some(shape) => shape,
+            None        => panic!("Not enough memory to clone PolygonShape")
+        }
+    }
+}
+
+impl_transformable!(PolygonShape);
+
+impl Bounded for PolygonShape {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
+impl Drawable for PolygonShape {
+    fn draw<RT: RenderTarget>(&self, render_target: &mut RT) -> () {
+        let mut render_states = rc::RenderStates::default();
+        self.draw_rs_rc(render_target, &mut render_states)
+    }
+
+    fn draw_rs_rc<RT: RenderTarget>(&self,
+                                    render_target: &mut RT,
+                                    render_states: &mut rc::RenderStates) -> () {
+        self.update_geometry();
+        let geometry = self.geometry.borrow();
+        let mut transform = render_states.transform;
+        transform.combine(&mut self.get_transform());
+
+        let mut fill_states = rc::RenderStates::new(render_states.blendMode,
+                                                    transform,
+                                                    self.texture.clone(),
+                                                    render_states.shader.clone());
+        render_target.draw_vertex_array_rs_rc(&geometry.fill, &mut fill_states);
+        if self.outline_thickness != 0. {
+            let mut outline_states = rc::RenderStates::new(render_states.blendMode,
+                                                           transform,
+                                                           None,
+                                                           render_states.shader.clone());
+            render_target.draw_vertex_array_rs_rc(&geometry.outline, &mut outline_states);
+        }
+    }
+}
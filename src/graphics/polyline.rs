@@ -0,0 +1,615 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+#![allow(missing_copy_implementations)]
+
+//! Thick line strip with per-vertex colors
+//!
+//! Unlike the Lines and LinesStrip primitives which are always one pixel
+//! wide, a Polyline has a thickness, joins, caps and an optional dash
+//! pattern. It is built into a TrianglesStrip VertexArray which is only
+//! recomputed when the polyline changes.
+
+use std::cell::{Cell, RefCell};
+use std::num::Float;
+use std::f32::consts::PI;
+
+use traits::Drawable;
+use graphics::{Color, Vertex, VertexArray, FloatRect, RenderTarget, RenderStates,
+               TrianglesStrip, LineJoin, MiterJoin, RoundJoin, BevelJoin,
+               LineCap, ButtCap, SquareCap, RoundCap, rc};
+use graphics::geometry;
+use system::vector2::Vector2f;
+
+/// Thick line strip with per-vertex colors
+pub struct Polyline {
+    #[doc(hidden)]
+    points: Vec<Vector2f>,
+    #[doc(hidden)]
+    colors: Vec<Color>,
+    #[doc(hidden)]
+    thickness: f32,
+    #[doc(hidden)]
+    join: LineJoin,
+    #[doc(hidden)]
+    miter_limit: f32,
+    #[doc(hidden)]
+    cap: LineCap,
+    #[doc(hidden)]
+    closed: bool,
+    #[doc(hidden)]
+    dash_pattern: Vec<f32>,
+    #[doc(hidden)]
+    dash_offset: f32,
+    #[doc(hidden)]
+    vertex_array: RefCell<VertexArray>,
+    #[doc(hidden)]
+    dirty: Cell<bool>
+}
+
+impl Polyline {
+    /// Create a new empty polyline
+    ///
+    /// # Default
+    /// * thickness - 1
+    /// * join - MiterJoin, with a miter limit of 4
+    /// * cap - ButtCap
+    /// * closed - false
+    /// * no dash pattern
+    ///
+    /// Return Some(Polyline) or None
+    pub fn new() -> Option<Polyline> {
+        let mut vertex_array = match VertexArray::new() {
+            Some(vertex_array) => vertex_array,
+            None               => return None
+        };
+        vertex_array.set_primitive_type(TrianglesStrip);
+        Some(Polyline {
+                points: Vec::new(),
+                colors: Vec::new(),
+                thickness: 1.,
+                join: MiterJoin,
+                miter_limit: 4.,
+                cap: ButtCap,
+                closed: false,
+                dash_pattern: Vec::new(),
+                dash_offset: 0.,
+                vertex_array: RefCell::new(vertex_array),
+                dirty: Cell::new(true)
+            })
+    }
+
+    /// Create a new polyline from a list of points
+    ///
+    /// # Arguments
+    /// * points - The points of the polyline
+    /// * color - The color of all the points
+    /// * thickness - The thickness of the line
+    ///
+    /// Return Some(Polyline) or None
+    pub fn new_init(points: &[Vector2f],
+                    color: &Color,
+                    thickness: f32) -> Option<Polyline> {
+        match Polyline::new() {
+            Some(mut polyline) => {
+                for point in points.iter() {
+                    polyline.add_point(point, color);
+                }
+                polyline.set_thickness(thickness);
+                Some(polyline)
+            },
+            None => None
+        }
+    }
+
+    /// Add a point at the end of the polyline
+    ///
+    /// # Arguments
+    /// * point - Position of the point
+    /// * color - Color of the line at this point
+    pub fn add_point(&mut self, point: &Vector2f, color: &Color) -> () {
+        self.points.push(*point);
+        self.colors.push(*color);
+        self.dirty.set(true)
+    }
+
+    /// Remove all the points of the polyline
+    pub fn clear(&mut self) -> () {
+        self.points.clear();
+        self.colors.clear();
+        self.dirty.set(true)
+    }
+
+    /// Get the number of points of the polyline
+    ///
+    /// Return the number of points
+    pub fn get_point_count(&self) -> uint {
+        self.points.len()
+    }
+
+    /// Set the position of a point
+    ///
+    /// Panic if index is out of the valid range.
+    ///
+    /// # Arguments
+    /// * index - Index of the point, in range [0 .. get_point_count() - 1]
+    /// * point - New position of the point
+    pub fn set_point(&mut self, index: uint, point: &Vector2f) -> () {
+        self.points[index] = *point;
+        self.dirty.set(true)
+    }
+
+    /// Get the position of a point
+    ///
+    /// Panic if index is out of the valid range.
+    ///
+    /// # Arguments
+    /// * index - Index of the point, in range [0 .. get_point_count() - 1]
+    ///
+    /// Return the position of the index-th point
+    pub fn get_point(&self, index: uint) -> Vector2f {
+        self.points[index]
+    }
+
+    /// Set the color of a point
+    ///
+    /// Panic if index is out of the valid range.
+    ///
+    /// # Arguments
+    /// * index - Index of the point, in range [0 .. get_point_count() - 1]
+    /// * color - New color of the point
+    pub fn set_point_color(&mut self, index: uint, color: &Color) -> () {
+        self.colors[index] = *color;
+        self.dirty.set(true)
+    }
+
+    /// Get the color of a point
+    ///
+    /// Panic if index is out of the valid range.
+    ///
+    /// # Arguments
+    /// * index - Index of the point, in range [0 .. get_point_count() - 1]
+    ///
+    /// Return the color of the index-th point
+    pub fn get_point_color(&self, index: uint) -> Color {
+        self.colors[index]
+    }
+
+    /// Set the color of all the points
+    ///
+    /// # Arguments
+    /// * color - New color of the polyline
+    pub fn set_color(&mut self, color: &Color) -> () {
+        for point_color in self.colors.iter_mut() {
+            *point_color = *color;
+        }
+        self.dirty.set(true)
+    }
+
+    /// Set the thickness of the polyline
+    ///
+    /// # Arguments
+    /// * thickness - New thickness
+    pub fn set_thickness(&mut self, thickness: f32) -> () {
+        self.thickness = thickness;
+        self.dirty.set(true)
+    }
+
+    /// Get the thickness of the polyline
+    ///
+    /// Return the thickness
+    pub fn get_thickness(&self) -> f32 {
+        self.thickness
+    }
+
+    /// Set the shape of the corners of the polyline
+    ///
+    /// # Arguments
+    /// * join - New join style
+    pub fn set_join(&mut self, join: LineJoin) -> () {
+        self.join = join;
+        self.dirty.set(true)
+    }
+
+    /// Get the shape of the corners of the polyline
+    ///
+    /// Return the join style
+    pub fn get_join(&self) -> LineJoin {
+        self.join
+    }
+
+    /// Set the miter limit of the polyline
+    ///
+    /// A miter join longer than the limit, relative to half the
+    /// thickness, is replaced by a bevel join.
+    ///
+    /// # Arguments
+    /// * limit - New miter limit
+    pub fn set_miter_limit(&mut self, limit: f32) -> () {
+        self.miter_limit = limit;
+        self.dirty.set(true)
+    }
+
+    /// Get the miter limit of the polyline
+    ///
+    /// Return the miter limit
+    pub fn get_miter_limit(&self) -> f32 {
+        self.miter_limit
+    }
+
+    /// Set the shape of the ends of the polyline and of its dashes
+    ///
+    /// # Arguments
+    /// * cap - New cap style
+    pub fn set_cap(&mut self, cap: LineCap) -> () {
+        self.cap = cap;
+        self.dirty.set(true)
+    }
+
+    /// Get the shape of the ends of the polyline
+    ///
+    /// Return the cap style
+    pub fn get_cap(&self) -> LineCap {
+        self.cap
+    }
+
+    /// Close or open the polyline
+    ///
+    /// A closed polyline joins its last point to its first one.
+    ///
+    /// # Arguments
+    /// * closed - Should the polyline be a closed loop?
+    pub fn set_closed(&mut self, closed: bool) -> () {
+        self.closed = closed;
+        self.dirty.set(true)
+    }
+
+    /// Tell if the polyline is a closed loop
+    ///
+    /// Return true if the polyline is closed
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Set the dash pattern of the polyline
+    ///
+    /// The pattern alternates the lengths of the drawn and of the
+    /// skipped parts of the line. An empty pattern draws a solid line.
+    ///
+    /// # Arguments
+    /// * pattern - The lengths of the dashes and of the gaps
+    pub fn set_dash_pattern(&mut self, pattern: &[f32]) -> () {
+        self.dash_pattern = pattern.to_vec();
+        self.dirty.set(true)
+    }
+
+    /// Get the dash pattern of the polyline
+    ///
+    /// Return the lengths of the dashes and of the gaps
+    pub fn get_dash_pattern(&self) -> &[f32] {
+        self.dash_pattern.as_slice()
+    }
+
+    /// Set the distance into the dash pattern at which the polyline starts
+    ///
+    /// Animating the offset makes the dashes move along the line.
+    ///
+    /// # Arguments
+    /// * offset - New dash offset
+    pub fn set_dash_offset(&mut self, offset: f32) -> () {
+        self.dash_offset = offset;
+        self.dirty.set(true)
+    }
+
+    /// Get the dash offset of the polyline
+    ///
+    /// Return the dash offset
+    pub fn get_dash_offset(&self) -> f32 {
+        self.dash_offset
+    }
+
+    /// Compute the bounding rectangle of the polyline
+    ///
+    /// Return the bounding rectangle, including the thickness
+    pub fn get_bounds(&self) -> FloatRect {
+        self.update_geometry();
+        self.vertex_array.borrow().get_bounds()
+    }
+
+    #[doc(hidden)]
+    fn update_geometry(&self) -> () {
+        if !self.dirty.get() {
+            return;
+        }
+        let mut points: Vec<Vertex> = Vec::with_capacity(self.points.len());
+        for (point, color) in self.points.iter().zip(self.colors.iter()) {
+            if points.is_empty() || points[points.len() - 1].position != *point {
+                points.push(Vertex::new_with_pos_color(point, color));
+            }
+        }
+        // A closed polyline already joins its last point to the first one
+        if self.closed && points.len() > 2 &&
+           points[0].position == points[points.len() - 1].position {
+            points.pop();
+        }
+
+        let mut strip = Vec::new();
+        if self.has_dashes() {
+            for dash in self.split_dashes(points.as_slice()).iter() {
+                self.build_piece(dash.as_slice(), false, &mut strip);
+            }
+        } else {
+            self.build_piece(points.as_slice(), self.closed, &mut strip);
+        }
+
+        let mut vertex_array = self.vertex_array.borrow_mut();
+        vertex_array.clear();
+        for vertex in strip.iter() {
+            vertex_array.append(vertex);
+        }
+        self.dirty.set(false);
+    }
+
+    #[doc(hidden)]
+    fn has_dashes(&self) -> bool {
+        let total = self.dash_pattern.iter().fold(0., |total, length| total + *length);
+        total > 0. && self.dash_pattern.iter().all(|length| *length >= 0.)
+    }
+
+    // Cut the polyline in open pieces, one per dash of the pattern
+    #[doc(hidden)]
+    fn split_dashes(&self, points: &[Vertex]) -> Vec<Vec<Vertex>> {
+        let mut path = points.to_vec();
+        if self.closed && path.len() > 2 {
+            path.push(points[0]);
+        }
+        let mut dashes = Vec::new();
+        if path.len() < 2 {
+            return dashes;
+        }
+
+        let pattern = self.dash_pattern.as_slice();
+        let total = pattern.iter().fold(0., |total, length| total + *length);
+        let mut offset = self.dash_offset % total;
+        if offset < 0. {
+            offset += total;
+        }
+        let mut index = 0;
+        while offset >= pattern[index] {
+            offset -= pattern[index];
+            index = (index + 1) % pattern.len();
+        }
+        let mut remaining = pattern[index] - offset;
+
+        let mut current: Vec<Vertex> = Vec::new();
+        if index % 2 == 0 {
+            current.push(path[0]);
+        }
+        for i in range(0, path.len() - 1) {
+            let (a, b) = (path[i], path[i + 1]);
+            let length = geometry::distance(a.position, b.position);
+            let mut travelled = 0.;
+            while length - travelled > remaining {
+                travelled += remaining;
+                current.push(lerp_vertex(&a, &b, travelled / length));
+                if index % 2 == 0 {
+                    dashes.push(current.clone());
+                    current.clear();
+                }
+                index = (index + 1) % pattern.len();
+                remaining = pattern[index];
+            }
+            remaining -= length - travelled;
+            if index % 2 == 0 {
+                current.push(b);
+            }
+        }
+        if index % 2 == 0 {
+            dashes.push(current);
+        }
+        dashes
+    }
+
+    #[doc(hidden)]
+    fn build_piece(&self,
+                   points: &[Vertex],
+                   closed: bool,
+                   strip: &mut Vec<Vertex>) -> () {
+        let mut cleaned: Vec<Vertex> = Vec::with_capacity(points.len());
+        for point in points.iter() {
+            if cleaned.is_empty() || cleaned[cleaned.len() - 1].position != point.position {
+                cleaned.push(*point);
+            }
+        }
+        let points = cleaned.as_slice();
+        let count = points.len();
+        let half_width = self.thickness * 0.5;
+        if count < 2 || half_width <= 0. {
+            return;
+        }
+
+        let mut piece = Vec::new();
+        if closed && count > 2 {
+            for i in range(0, count) {
+                self.push_join(points, i, half_width, &mut piece);
+            }
+            self.push_join(points, 0, half_width, &mut piece);
+        } else {
+            self.push_cap(&points[0], &points[1], true, half_width, &mut piece);
+            for i in range(1, count - 1) {
+                self.push_join(points, i, half_width, &mut piece);
+            }
+            self.push_cap(&points[count - 2], &points[count - 1], false, half_width,
+                          &mut piece);
+        }
+
+        // Separate pieces are linked by degenerate triangles
+        if !strip.is_empty() && !piece.is_empty() {
+            let last = strip[strip.len() - 1];
+            strip.push(last);
+            strip.push(piece[0]);
+        }
+        strip.push_all(piece.as_slice());
+    }
+
+    #[doc(hidden)]
+    fn push_join(&self,
+                 points: &[Vertex],
+                 index: uint,
+                 half_width: f32,
+                 piece: &mut Vec<Vertex>) -> () {
+        let count = points.len();
+        let previous = points[(index + count - 1) % count].position;
+        let vertex = points[index];
+        let point = vertex.position;
+        let next = points[(index + 1) % count].position;
+
+        let incoming = geometry::normalize(point - previous);
+        let outgoing = geometry::normalize(next - point);
+        let normal_in = geometry::perpendicular(incoming);
+        let normal_out = geometry::perpendicular(outgoing);
+        let turn = geometry::cross(incoming, outgoing);
+        if turn.abs() < 1e-6 && geometry::dot(incoming, outgoing) > 0. {
+            push_pair(point, normal_in * half_width, &vertex.color, piece);
+            return;
+        }
+
+        match self.join {
+            MiterJoin => {
+                let miter = geometry::normalize(normal_in + normal_out);
+                let cos_half = geometry::dot(miter, normal_in);
+                if cos_half > 1e-4 && 1. / cos_half <= self.miter_limit {
+                    push_pair(point, miter * (half_width / cos_half), &vertex.color, piece);
+                    return;
+                }
+                push_pair(point, normal_in * half_width, &vertex.color, piece);
+                push_pair(point, normal_out * half_width, &vertex.color, piece);
+            },
+            BevelJoin => {
+                push_pair(point, normal_in * half_width, &vertex.color, piece);
+                push_pair(point, normal_out * half_width, &vertex.color, piece);
+            },
+            RoundJoin => {
+                // Every vertex lies within half the width of the point,
+                // so the extra triangles stay inside the round join
+                push_pair(point, normal_in * half_width, &vertex.color, piece);
+                let side = if turn > 0. { -1. } else { 1. };
+                let outer_in = normal_in * side;
+                let outer_out = normal_out * side;
+                let start = outer_in.y.atan2(outer_in.x);
+                let sweep = geometry::cross(outer_in, outer_out)
+                    .atan2(geometry::dot(outer_in, outer_out));
+                push_arc(point, half_width, start, sweep, &vertex.color, piece);
+                push_pair(point, normal_out * half_width, &vertex.color, piece);
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    fn push_cap(&self,
+                from: &Vertex,
+                to: &Vertex,
+                start: bool,
+                half_width: f32,
+                piece: &mut Vec<Vertex>) -> () {
+        let direction = geometry::normalize(to.position - from.position);
+        let normal = geometry::perpendicular(direction) * half_width;
+        let (vertex, outward) = if start { (from, direction * -1.) } else { (to, direction) };
+        let point = vertex.position;
+
+        match self.cap {
+            ButtCap => push_pair(point, normal, &vertex.color, piece),
+            SquareCap => push_pair(point + outward * half_width, normal,
+                                   &vertex.color, piece),
+            RoundCap => {
+                // The half circle goes from one side of the line to the
+                // other, around the end point
+                let from_side = if start { normal * -1. } else { normal };
+                let angle = from_side.y.atan2(from_side.x);
+                let sweep = if geometry::cross(from_side, outward) > 0. { PI } else { -PI };
+                if start {
+                    push_arc(point, half_width, angle, sweep, &vertex.color, piece);
+                    push_pair(point, normal, &vertex.color, piece);
+                } else {
+                    push_pair(point, normal, &vertex.color, piece);
+                    push_arc(point, half_width, angle, sweep, &vertex.color, piece);
+                }
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+fn push_pair(point: Vector2f,
+             offset: Vector2f,
+             color: &Color,
+             piece: &mut Vec<Vertex>) -> () {
+    piece.push(Vertex::new_with_pos_color(&(point + offset), color));
+    piece.push(Vertex::new_with_pos_color(&(point - offset), color));
+}
+
+// Alternate the center and the points of the arc, which builds a fan
+// inside a triangle strip
+#[doc(hidden)]
+fn push_arc(center: Vector2f,
+            radius: f32,
+            start: f32,
+            sweep: f32,
+            color: &Color,
+            piece: &mut Vec<Vertex>) -> () {
+    let count = geometry::arc_segments(radius, sweep, 0.25);
+    for i in range(0, count + 1) {
+        let angle = start + sweep * i as f32 / count as f32;
+        piece.push(Vertex::new_with_pos_color(&center, color));
+        piece.push(Vertex::new_with_pos_color(&geometry::point_on_circle(center, radius, angle),
+                                              color));
+    }
+}
+
+#[doc(hidden)]
+fn lerp_vertex(a: &Vertex, b: &Vertex, t: f32) -> Vertex {
+    Vertex::new_with_pos_color(&geometry::lerp(a.position, b.position, t),
+                               &geometry::lerp_color(&a.color, &b.color, t))
+}
+
+impl Drawable for Polyline {
+    fn draw<RT: RenderTarget>(&self, render_target: &mut RT) -> () {
+        self.update_geometry();
+        render_target.draw_vertex_array(&*self.vertex_array.borrow())
+    }
+
+    fn draw_rs<RT: RenderTarget>(&self,
+                                 render_target: &mut RT,
+                                 render_states: &mut RenderStates) -> () {
+        self.update_geometry();
+        render_target.draw_vertex_array_rs(&*self.vertex_array.borrow(), render_states)
+    }
+
+    fn draw_rs_rc<RT: RenderTarget>(&self,
+                                    render_target: &mut RT,
+                                    render_states: &mut rc::RenderStates) -> () {
+        self.update_geometry();
+        render_target.draw_vertex_array_rs_rc(&*self.vertex_array.borrow(), render_states)
+    }
+}
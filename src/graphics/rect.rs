@@ -26,46 +26,41 @@
 //!
 //! Utility class for manipulating 2D axis aligned rectangles
 
-use libc::c_int;
+use std::num::{Int, Float};
 
-use ffi::sfml_types::{SFTRUE, SFFALSE};
-use ffi::graphics::rect as ffi;
+use system::vector2::{Vector2f, Vector2i};
 
-/// Utility classes for manipulating rectangles of int.
+/// Utility class for manipulating 2D axis aligned rectangles
+///
+/// A rectangle is defined by its top-left corner and its size. Like in
+/// SFML, the size may be negative, and the methods work on the normalized
+/// rectangle.
 #[repr(C)]
 #[deriving(Clone, PartialEq, Eq, PartialOrd, Ord, Show, Copy)]
-pub struct IntRect {
+pub struct Rect<T> {
     /// Left coordinate of the rectangle.
-    pub left: i32,
+    pub left: T,
     /// Top coordinate of the rectangle.
-    pub top: i32,
+    pub top: T,
     /// Width of the rectangle.
-    pub width: i32,
-    /// Height coordinate of the rectangle.
-    pub height: i32
+    pub width: T,
+    /// Height of the rectangle.
+    pub height: T
 }
 
+/// Utility classes for manipulating rectangles of int.
+pub type IntRect = Rect<i32>;
+
 /// Utility classes for manipulating rectangles of f32.
-#[repr(C)]
-#[deriving(Clone, PartialEq, PartialOrd, Show, Copy)]
-pub struct FloatRect {
-    /// Left coordinate of the rectangle.
-    pub left: f32,
-    /// Top coordinate of the rectangle.
-    pub top: f32,
-    /// Width of the rectangle.
-    pub width: f32,
-    /// Height of the rectangle.
-    pub height: f32
-}
+pub type FloatRect = Rect<f32>;
 
-impl IntRect {
-    /// Construct a new IntRect
-    pub fn new(left: i32,
-               top: i32,
-               width: i32,
-               height: i32) -> IntRect {
-        IntRect {
+impl<T: Copy + PartialOrd + Add<T, T> + Sub<T, T>> Rect<T> {
+    /// Construct a new Rect
+    pub fn new(left: T,
+               top: T,
+               width: T,
+               height: T) -> Rect<T> {
+        Rect {
             left: left,
             top: top,
             width: width,
@@ -73,17 +68,52 @@ impl IntRect {
         }
     }
 
-    ///  Check if a point is inside a rectangle's area
+    /// Check if a point is inside a rectangle's area
+    ///
+    /// The left and top sides are inside, the right and bottom ones are not.
     ///
     /// # Arguments
     /// * x - X coordinate of the point to test
     /// * y - Y coordinate of the point to test
     ///
     /// Return true if the point is inside
-    pub fn contains(self, x: int, y: int) -> bool {
-        match unsafe { ffi::sfIntRect_contains(&self, x as c_int, y as c_int) } {
-            SFFALSE => false,
-            SFTRUE  => true
+    pub fn contains(self, x: T, y: T) -> bool {
+        let (min_x, min_y, max_x, max_y) = self.extents();
+        x >= min_x && x < max_x && y >= min_y && y < max_y
+    }
+
+    /// Check if a rectangle is entirely inside a rectangle's area
+    ///
+    /// # Arguments
+    /// * other - The rectangle to test
+    ///
+    /// Return true if other is inside
+    pub fn contains_rect(&self, other: &Rect<T>) -> bool {
+        let (min_x, min_y, max_x, max_y) = self.extents();
+        let (other_min_x, other_min_y, other_max_x, other_max_y) = other.extents();
+        other_min_x >= min_x && other_min_y >= min_y &&
+        other_max_x <= max_x && other_max_y <= max_y
+    }
+
+    /// Compute the intersection of two rectangles
+    ///
+    /// Rectangles which only share a side don't intersect.
+    ///
+    /// # Arguments
+    /// * other - The rectangle to intersect with
+    ///
+    /// Return Some(Rect) of the overlapping area, or None
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let (min_x, min_y, max_x, max_y) = self.extents();
+        let (other_min_x, other_min_y, other_max_x, other_max_y) = other.extents();
+        let left = max(min_x, other_min_x);
+        let top = max(min_y, other_min_y);
+        let right = min(max_x, other_max_x);
+        let bottom = min(max_y, other_max_y);
+        if left < right && top < bottom {
+            Some(Rect::new(left, top, right - left, bottom - top))
+        } else {
+            None
         }
     }
 
@@ -94,61 +124,229 @@ impl IntRect {
     /// * rect2 - Second rectangle to test
     /// * intersection - Rectangle to be filled with overlapping rect
     ///
-    /// Return strue if rectangles overlap
-    pub fn intersects(rect1: &IntRect,
-                      rect2: &IntRect,
-                      intersections: &IntRect) -> bool {
-        match unsafe { ffi::sfIntRect_intersects(rect1, rect2, intersections) } {
-            SFFALSE => false,
-            SFTRUE  => true
+    /// Return true if rectangles overlap
+    pub fn intersects(rect1: &Rect<T>,
+                      rect2: &Rect<T>,
+                      intersection: &mut Rect<T>) -> bool {
+        match rect1.intersection(rect2) {
+            Some(rect) => {
+                *intersection = rect;
+                true
+            },
+            None => false
         }
     }
+
+    /// Compute the smallest rectangle containing two rectangles
+    ///
+    /// # Arguments
+    /// * other - The rectangle to merge with
+    ///
+    /// Return the union of the rectangles
+    pub fn union(&self, other: &Rect<T>) -> Rect<T> {
+        let (min_x, min_y, max_x, max_y) = self.extents();
+        let (other_min_x, other_min_y, other_max_x, other_max_y) = other.extents();
+        let left = min(min_x, other_min_x);
+        let top = min(min_y, other_min_y);
+        Rect::new(left,
+                  top,
+                  max(max_x, other_max_x) - left,
+                  max(max_y, other_max_y) - top)
+    }
+
+    /// Grow a rectangle on every side
+    ///
+    /// Use negative amounts to shrink the rectangle.
+    ///
+    /// # Arguments
+    /// * dx - The amount added to the left and the right sides
+    /// * dy - The amount added to the top and the bottom sides
+    ///
+    /// Return the inflated rectangle
+    pub fn inflate(&self, dx: T, dy: T) -> Rect<T> {
+        let (min_x, min_y, max_x, max_y) = self.extents();
+        Rect::new(min_x - dx,
+                  min_y - dy,
+                  max_x + dx - (min_x - dx),
+                  max_y + dy - (min_y - dy))
+    }
+
+    #[doc(hidden)]
+    fn extents(&self) -> (T, T, T, T) {
+        let right = self.left + self.width;
+        let bottom = self.top + self.height;
+        (min(self.left, right), min(self.top, bottom),
+         max(self.left, right), max(self.top, bottom))
+    }
 }
 
-impl FloatRect {
-    /// Construct a new FloatRect
-    pub fn new(left: f32,
-        top: f32,
-        width: f32,
-        height: f32) -> FloatRect {
+impl Rect<i32> {
+    /// Check if a point is inside a rectangle's area
+    ///
+    /// # Arguments
+    /// * point - The point to test
+    ///
+    /// Return true if the point is inside
+    pub fn contains_point(&self, point: &Vector2i) -> bool {
+        self.contains(point.x, point.y)
+    }
 
-        FloatRect {
-            left: left,
-            top: top,
-            width: width,
-            height: height
+    /// Get the position of the top-left corner of a rectangle
+    ///
+    /// Return the position of the rectangle
+    pub fn get_position(&self) -> Vector2i {
+        Vector2i::new(self.left, self.top)
+    }
+
+    /// Get the size of a rectangle
+    ///
+    /// Return the size of the rectangle
+    pub fn get_size(&self) -> Vector2i {
+        Vector2i::new(self.width, self.height)
+    }
+
+    /// Get the center of a rectangle
+    ///
+    /// Return the center of the rectangle
+    pub fn get_center(&self) -> Vector2f {
+        Vector2f::new(self.left as f32 + self.width as f32 / 2.,
+                      self.top as f32 + self.height as f32 / 2.)
+    }
+
+    /// Create the rectangle between two corners
+    ///
+    /// # Arguments
+    /// * a - A corner of the rectangle
+    /// * b - The opposite corner of the rectangle
+    ///
+    /// Return a rectangle with a positive size
+    pub fn from_points(a: &Vector2i, b: &Vector2i) -> IntRect {
+        let left = min(a.x, b.x);
+        let top = min(a.y, b.y);
+        Rect::new(left, top, max(a.x, b.x) - left, max(a.y, b.y) - top)
+    }
+
+    /// Convert a rectangle of int to a rectangle of f32
+    ///
+    /// Beyond 2^24, not every int is a f32, so the conversion fails
+    /// instead of rounding the coordinates.
+    ///
+    /// Return Some(FloatRect) with the same coordinates, or None if one of
+    /// them can't be represented exactly by a f32
+    pub fn to_float_rect(&self) -> Option<FloatRect> {
+        match (exact_f32(self.left), exact_f32(self.top),
+               exact_f32(self.width), exact_f32(self.height)) {
+            (Some(left), Some(top), Some(width), Some(height)) => {
+                Some(Rect::new(left, top, width, height))
+            },
+            _ => None
         }
     }
+}
 
-    ///  Check if a point is inside a rectangle's area
+impl Rect<f32> {
+    /// Check if a point is inside a rectangle's area
     ///
     /// # Arguments
-    /// * x - X coordinate of the point to test
-    /// * y - Y coordinate of the point to test
+    /// * point - The point to test
     ///
     /// Return true if the point is inside
-    pub fn contains(self, x: f32, y: f32) -> bool {
-        match unsafe { ffi::sfFloatRect_contains(&self, x, y) } {
-            SFFALSE => false,
-            SFTRUE  => true
-        }
+    pub fn contains_point(&self, point: &Vector2f) -> bool {
+        self.contains(point.x, point.y)
     }
 
-    /// Check intersection between two rectangles
+    /// Get the position of the top-left corner of a rectangle
+    ///
+    /// Return the position of the rectangle
+    pub fn get_position(&self) -> Vector2f {
+        Vector2f::new(self.left, self.top)
+    }
+
+    /// Get the size of a rectangle
+    ///
+    /// Return the size of the rectangle
+    pub fn get_size(&self) -> Vector2f {
+        Vector2f::new(self.width, self.height)
+    }
+
+    /// Get the center of a rectangle
+    ///
+    /// Return the center of the rectangle
+    pub fn get_center(&self) -> Vector2f {
+        Vector2f::new(self.left + self.width / 2., self.top + self.height / 2.)
+    }
+
+    /// Create the rectangle between two corners
     ///
     /// # Arguments
-    /// * rect1 - First rectangle to test
-    /// * rect2 - Second rectangle to test
-    /// * intersection - Rectangle to be filled with overlapping rect
+    /// * a - A corner of the rectangle
+    /// * b - The opposite corner of the rectangle
     ///
-    /// Return true if rectangles overlap
-    pub fn intersects(rect1: &FloatRect,
-        rect2: &FloatRect,
-        intersections: &FloatRect) -> bool {
+    /// Return a rectangle with a positive size
+    pub fn from_points(a: &Vector2f, b: &Vector2f) -> FloatRect {
+        let left = a.x.min(b.x);
+        let top = a.y.min(b.y);
+        Rect::new(left, top, a.x.max(b.x) - left, a.y.max(b.y) - top)
+    }
 
-        match unsafe { ffi::sfFloatRect_intersects(rect1, rect2, intersections) } {
-            SFFALSE => false,
-            SFTRUE  => true
+    /// Convert a rectangle of f32 to a rectangle of int
+    ///
+    /// Return Some(IntRect) with the same coordinates, or None if one of
+    /// them is not an int
+    pub fn to_int_rect(&self) -> Option<IntRect> {
+        match (exact_i32(self.left), exact_i32(self.top),
+               exact_i32(self.width), exact_i32(self.height)) {
+            (Some(left), Some(top), Some(width), Some(height)) => {
+                Some(Rect::new(left, top, width, height))
+            },
+            _ => None
         }
     }
+
+    /// Get the smallest rectangle of int containing a rectangle of f32
+    ///
+    /// The conversion is lossy: the sides are rounded outward.
+    ///
+    /// Return Some(IntRect) with a positive size, or None if the rectangle
+    /// is not finite or doesn't fit in the range of int
+    pub fn to_enclosing_int_rect(&self) -> Option<IntRect> {
+        let (min_x, min_y, max_x, max_y) = self.extents();
+        match (exact_i32(min_x.floor()), exact_i32(min_y.floor()),
+               exact_i32(max_x.ceil()), exact_i32(max_y.ceil())) {
+            (Some(left), Some(top), Some(right), Some(bottom)) => {
+                match (right.checked_sub(left), bottom.checked_sub(top)) {
+                    (Some(width), Some(height)) => Some(Rect::new(left, top, width, height)),
+                    _ => None
+                }
+            },
+            _ => None
+        }
+    }
+}
+
+// The f32 equal to an int, if there is one
+#[doc(hidden)]
+fn exact_f32(value: i32) -> Option<f32> {
+    let converted = value as f32;
+    if converted as i64 == value as i64 { Some(converted) } else { None }
+}
+
+// The int equal to a f32, if there is one
+#[doc(hidden)]
+fn exact_i32(value: f32) -> Option<i32> {
+    if value.floor() == value && value >= -2147483648. && value < 2147483648. {
+        Some(value as i32)
+    } else {
+        None
+    }
+}
+
+#[doc(hidden)]
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a { b } else { a }
+}
+
+#[doc(hidden)]
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a { b } else { a }
 }
@@ -27,7 +27,7 @@
 use libc::{c_float, c_uint};
 use std::ptr;
 
-use traits::{Drawable, Wrappable};
+use traits::{Drawable, Wrappable, Bounded};
 use system::vector2::Vector2f;
 use graphics::{FloatRect, IntRect, Color, Texture,
                RenderTarget, Transform, RenderStates};
@@ -616,6 +616,18 @@ impl<'s> Wrappable<*mut ffi::sfRectangleShape> for RectangleShape<'s> {
     }
 }
 
+impl_transformable!(RectangleShape<'s>, 's);
+
+impl<'s> Bounded for RectangleShape<'s> {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
 impl<'s> Drawable for RectangleShape<'s> {
     fn draw<RT: RenderTarget>(&self, render_target: &mut RT) -> () {
         render_target.draw_rectangle_shape(self);
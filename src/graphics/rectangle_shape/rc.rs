@@ -29,7 +29,7 @@ use std::cell::RefCell;
 use libc::{c_float, c_uint};
 use std::ptr;
 
-use traits::{Drawable, Wrappable};
+use traits::{Drawable, Wrappable, Bounded};
 use system::vector2::Vector2f;
 use graphics::{FloatRect, IntRect, Color, Texture,
                RenderTarget, Transform, rc};
@@ -618,6 +618,18 @@ impl Wrappable<*mut ffi::sfRectangleShape> for RectangleShape {
     }
 }
 
+impl_transformable!(RectangleShape);
+
+impl Bounded for RectangleShape {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
 impl Drawable for RectangleShape {
     fn draw<RT:RenderTarget>(&self, render_target: &mut RT) -> () {
         render_target.draw_rectangle_shape_rc(self);
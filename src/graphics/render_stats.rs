@@ -0,0 +1,719 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+#![allow(non_snake_case)]
+
+//! RenderTarget wrapper counting what is drawn
+//!
+//! An InstrumentedTarget forwards every call to the target it wraps and
+//! counts the draw calls, the vertices, the primitive types, the texture
+//! and shader switches, the clears and the view changes of each frame.
+//! The statistics are available as a RenderStats struct and can be drawn
+//! on screen as an overlay.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use traits::{Drawable, Wrappable};
+use graphics::{Color, Text, Shape, Sprite, VertexArray, View, RenderStates, RenderTarget,
+               RenderWindow, RenderTexture, ConvexShape, RectangleShape, CircleShape,
+               IntRect, Texture, Font, Shader, rc, Vertex, PrimitiveType, TrianglesStrip,
+               TrianglesFan, Quads};
+use system::vector2::{Vector2f, Vector2i, Vector2u};
+
+use self::DrawKind::{SpriteDraw, TextDraw, ShapeDraw, VertexArrayDraw, PrimitivesDraw};
+
+/// Statistics of the drawing of a frame
+#[deriving(Clone, PartialEq, Show, Copy)]
+pub struct RenderStats {
+    /// Number of draw calls
+    pub draw_calls: uint,
+    /// Number of vertices of sprites, shapes and their outlines, texts,
+    /// vertex arrays and primitives
+    ///
+    /// The underlines of texts are not counted.
+    pub vertices: uint,
+    /// Number of sprites drawn
+    pub sprites: uint,
+    /// Number of texts drawn
+    pub texts: uint,
+    /// Number of shapes drawn
+    pub shapes: uint,
+    /// Number of vertex arrays drawn
+    pub vertex_arrays: uint,
+    /// Number of arrays of primitives drawn
+    pub primitives: uint,
+    /// Number of draw calls per primitive type, indexed by PrimitiveType
+    pub primitive_types: [uint, ..7],
+    /// Number of draw calls using a different texture than the previous one
+    pub texture_switches: uint,
+    /// Number of draw calls using a different shader than the previous one
+    pub shader_switches: uint,
+    /// Number of calls to clear
+    pub clears: uint,
+    /// Number of calls to set_view
+    pub view_changes: uint
+}
+
+impl RenderStats {
+    /// Create empty statistics
+    ///
+    /// Return a RenderStats with every counter at 0
+    pub fn new() -> RenderStats {
+        RenderStats {
+            draw_calls: 0,
+            vertices: 0,
+            sprites: 0,
+            texts: 0,
+            shapes: 0,
+            vertex_arrays: 0,
+            primitives: 0,
+            primitive_types: [0, ..7],
+            texture_switches: 0,
+            shader_switches: 0,
+            clears: 0,
+            view_changes: 0
+        }
+    }
+
+    /// Get the number of draw calls using a primitive type
+    ///
+    /// # Arguments
+    /// * primitive_type - The primitive type
+    ///
+    /// Return the number of draw calls
+    pub fn get_primitive_type_count(&self, primitive_type: PrimitiveType) -> uint {
+        self.primitive_types[primitive_type as uint]
+    }
+
+    /// Format the statistics, one counter per line
+    ///
+    /// Return the formatted statistics
+    pub fn get_summary(&self) -> String {
+        format!("draw calls: {}\nvertices: {}\nsprites: {}\ntexts: {}\nshapes: {}\n\
+                 vertex arrays: {}\nprimitives: {}\ntexture switches: {}\n\
+                 shader switches: {}\nclears: {}\nview changes: {}",
+                self.draw_calls, self.vertices, self.sprites, self.texts, self.shapes,
+                self.vertex_arrays, self.primitives, self.texture_switches,
+                self.shader_switches, self.clears, self.view_changes)
+    }
+}
+
+// Kind of object drawn
+#[doc(hidden)]
+enum DrawKind {
+    SpriteDraw,
+    TextDraw,
+    ShapeDraw,
+    VertexArrayDraw,
+    PrimitivesDraw
+}
+
+/// RenderTarget wrapper counting what is drawn
+pub struct InstrumentedTarget<RT> {
+    #[doc(hidden)]
+    target: RT,
+    #[doc(hidden)]
+    current: Cell<RenderStats>,
+    #[doc(hidden)]
+    last_frame: RenderStats,
+    #[doc(hidden)]
+    frame_count: uint,
+    #[doc(hidden)]
+    last_texture: Cell<uint>,
+    #[doc(hidden)]
+    last_shader: Cell<uint>
+}
+
+impl<RT: RenderTarget> InstrumentedTarget<RT> {
+    /// Wrap a render target
+    ///
+    /// # Arguments
+    /// * target - The target to instrument
+    ///
+    /// Return a new InstrumentedTarget
+    pub fn new(target: RT) -> InstrumentedTarget<RT> {
+        InstrumentedTarget {
+            target: target,
+            current: Cell::new(RenderStats::new()),
+            last_frame: RenderStats::new(),
+            frame_count: 0,
+            last_texture: Cell::new(0),
+            last_shader: Cell::new(0)
+        }
+    }
+
+    /// Get the wrapped target
+    ///
+    /// Return a reference to the target
+    pub fn get_target(&self) -> &RT {
+        &self.target
+    }
+
+    /// Get the wrapped target, to use what RenderTarget doesn't offer
+    ///
+    /// What is drawn directly on it is not counted.
+    ///
+    /// Return a mutable reference to the target
+    pub fn get_target_mut(&mut self) -> &mut RT {
+        &mut self.target
+    }
+
+    /// Stop instrumenting the target
+    ///
+    /// Return the wrapped target
+    pub fn into_target(self) -> RT {
+        self.target
+    }
+
+    /// Get the statistics of the frame in progress
+    ///
+    /// Return the statistics counted since the last end of frame
+    pub fn get_stats(&self) -> RenderStats {
+        self.current.get()
+    }
+
+    /// Get the statistics of the last complete frame
+    ///
+    /// Return the statistics of the last frame
+    pub fn get_last_frame_stats(&self) -> RenderStats {
+        self.last_frame
+    }
+
+    /// Get the number of complete frames
+    ///
+    /// Return the number of frames ended since the creation
+    pub fn get_frame_count(&self) -> uint {
+        self.frame_count
+    }
+
+    /// End the current frame and reset the counters
+    ///
+    /// display calls it, call it yourself for other targets.
+    pub fn end_frame(&mut self) -> () {
+        self.last_frame = self.current.get();
+        self.current.set(RenderStats::new());
+        self.last_texture.set(0);
+        self.last_shader.set(0);
+        self.frame_count += 1;
+    }
+
+    /// Draw the statistics of the last frame as an overlay
+    ///
+    /// The overlay is drawn with the default view, which is restored
+    /// afterwards, and is not counted.
+    ///
+    /// # Arguments
+    /// * font - The font of the overlay
+    /// * character_size - The character size of the overlay
+    /// * position - The position of the overlay, in pixels
+    pub fn draw_overlay(&mut self, font: &Font, character_size: uint, position: &Vector2f) -> () {
+        let mut text = match Text::new_init(self.last_frame.get_summary().as_slice(),
+                                            font,
+                                            character_size) {
+            Some(text) => text,
+            None => return
+        };
+        text.set_position(position);
+        text.set_color(&Color::white());
+        let previous = self.target.get_view();
+        let default = self.target.get_default_view();
+        self.target.set_view(&default);
+        self.target.draw(&text);
+        self.target.set_view(&previous);
+    }
+
+    #[doc(hidden)]
+    fn record(&self,
+              kind: DrawKind,
+              primitive_type: Option<PrimitiveType>,
+              vertices: uint,
+              texture: uint,
+              shader: uint) -> () {
+        let mut stats = self.current.get();
+        match kind {
+            SpriteDraw => stats.sprites += 1,
+            TextDraw => stats.texts += 1,
+            ShapeDraw => stats.shapes += 1,
+            VertexArrayDraw => stats.vertex_arrays += 1,
+            PrimitivesDraw => stats.primitives += 1
+        }
+        self.current.set(stats);
+        self.record_call(primitive_type, vertices, texture, shader)
+    }
+
+    // Record a draw call of SFML, without counting a new object
+    #[doc(hidden)]
+    fn record_call(&self,
+                   primitive_type: Option<PrimitiveType>,
+                   vertices: uint,
+                   texture: uint,
+                   shader: uint) -> () {
+        let mut stats = self.current.get();
+        stats.draw_calls += 1;
+        stats.vertices += vertices;
+        match primitive_type {
+            Some(primitive_type) => stats.primitive_types[primitive_type as uint] += 1,
+            None => {}
+        }
+        if texture != self.last_texture.get() {
+            stats.texture_switches += 1;
+            self.last_texture.set(texture);
+        }
+        if shader != self.last_shader.get() {
+            stats.shader_switches += 1;
+            self.last_shader.set(shader);
+        }
+        self.current.set(stats);
+    }
+
+    // Record a shape: SFML draws its fill, then its outline without texture
+    // in a second draw call when it has one
+    #[doc(hidden)]
+    fn record_shape(&self,
+                    point_count: uint,
+                    outline_thickness: f32,
+                    texture: uint,
+                    shader: uint) -> () {
+        self.record(ShapeDraw, Some(TrianglesFan), point_count + 2, texture, shader);
+        if outline_thickness != 0. {
+            self.record_call(Some(TrianglesStrip), (point_count + 1) * 2, 0, shader);
+        }
+    }
+
+    #[doc(hidden)]
+    fn record_shape_rs(&self,
+                       point_count: uint,
+                       outline_thickness: f32,
+                       texture: uint,
+                       render_states: &RenderStates) -> () {
+        let texture = if texture != 0 { texture } else { texture_id(render_states.texture) };
+        self.record_shape(point_count, outline_thickness, texture,
+                          shader_id(render_states.shader))
+    }
+
+    #[doc(hidden)]
+    fn record_shape_rs_rc(&self,
+                          point_count: uint,
+                          outline_thickness: f32,
+                          texture: uint,
+                          render_states: &rc::RenderStates) -> () {
+        let texture = if texture != 0 { texture } else { rc_texture_id(&render_states.texture) };
+        self.record_shape(point_count, outline_thickness, texture,
+                          rc_shader_id(&render_states.shader))
+    }
+
+    #[doc(hidden)]
+    fn record_rs(&self,
+                 kind: DrawKind,
+                 primitive_type: Option<PrimitiveType>,
+                 vertices: uint,
+                 texture: uint,
+                 render_states: &RenderStates) -> () {
+        let texture = if texture != 0 { texture } else { texture_id(render_states.texture) };
+        self.record(kind, primitive_type, vertices, texture, shader_id(render_states.shader))
+    }
+
+    #[doc(hidden)]
+    fn record_rs_rc(&self,
+                    kind: DrawKind,
+                    primitive_type: Option<PrimitiveType>,
+                    vertices: uint,
+                    texture: uint,
+                    render_states: &rc::RenderStates) -> () {
+        let texture = if texture != 0 { texture } else { rc_texture_id(&render_states.texture) };
+        self.record(kind, primitive_type, vertices, texture, rc_shader_id(&render_states.shader))
+    }
+}
+
+impl InstrumentedTarget<RenderWindow> {
+    /// Display on screen what has been rendered to the window so far,
+    /// then end the frame
+    pub fn display(&mut self) -> () {
+        self.target.display();
+        self.end_frame();
+    }
+}
+
+impl InstrumentedTarget<RenderTexture> {
+    /// Update the contents of the target texture, then end the frame
+    pub fn display(&mut self) -> () {
+        self.target.display();
+        self.end_frame();
+    }
+}
+
+impl<RT: RenderTarget> RenderTarget for InstrumentedTarget<RT> {
+    fn push_GL_states(&mut self) -> () {
+        self.target.push_GL_states()
+    }
+
+    fn pop_GL_states(&mut self) -> () {
+        self.target.pop_GL_states()
+    }
+
+    fn reset_GL_states(&mut self) -> () {
+        self.target.reset_GL_states()
+    }
+
+    fn set_view(&mut self, view: &View) -> () {
+        let mut stats = self.current.get();
+        stats.view_changes += 1;
+        self.current.set(stats);
+        self.target.set_view(view)
+    }
+
+    fn get_view(&self) -> View {
+        self.target.get_view()
+    }
+
+    fn get_default_view(&self) -> View {
+        self.target.get_default_view()
+    }
+
+    fn map_pixel_to_coords(&self, point: &Vector2i, view: &View) -> Vector2f {
+        self.target.map_pixel_to_coords(point, view)
+    }
+
+    fn map_pixel_to_coords_current_view(&self, point: &Vector2i) -> Vector2f {
+        self.target.map_pixel_to_coords_current_view(point)
+    }
+
+    fn map_coords_to_pixel(&self, point: &Vector2f, view: &View) -> Vector2i {
+        self.target.map_coords_to_pixel(point, view)
+    }
+
+    fn map_coords_to_pixel_current_view(&self, point: &Vector2f) -> Vector2i {
+        self.target.map_coords_to_pixel_current_view(point)
+    }
+
+    fn get_viewport(&self, view: &View) -> IntRect {
+        self.target.get_viewport(view)
+    }
+
+    fn push_clip(&mut self, rect: &IntRect) -> () {
+        self.target.push_clip(rect)
+    }
+
+    fn pop_clip(&mut self) -> () {
+        self.target.pop_clip()
+    }
+
+    fn get_clip(&self) -> Option<IntRect> {
+        self.target.get_clip()
+    }
+
+    fn get_size(&self) -> Vector2u {
+        self.target.get_size()
+    }
+
+    fn clear(&mut self, color: &Color) -> () {
+        let mut stats = self.current.get();
+        stats.clears += 1;
+        self.current.set(stats);
+        self.target.clear(color)
+    }
+
+    fn draw<T: Drawable>(&mut self, object: &T) -> () {
+        object.draw(self);
+    }
+
+    fn draw_with_renderstates<T: Drawable>(&mut self,
+                                           object: &T,
+                                           render_states: &mut RenderStates) -> () {
+        object.draw_rs(self, render_states);
+    }
+
+    fn draw_with_renderstates_rc<T: Drawable>(&mut self,
+                                              object: &T,
+                                              render_states: &mut rc::RenderStates) -> () {
+        object.draw_rs_rc(self, render_states);
+    }
+
+    fn draw_text(&self, text: &Text) -> () {
+        self.record(TextDraw, Some(Quads),
+                    glyph_vertex_count(text.get_unicode_string().as_slice()),
+                    font_id(text.get_font()), 0);
+        self.target.draw_text(text)
+    }
+
+    fn draw_text_rc(&self, text: &rc::Text) -> () {
+        self.record(TextDraw, Some(Quads),
+                    glyph_vertex_count(text.get_unicode_string().as_slice()),
+                    rc_font_id(&text.get_font()), 0);
+        self.target.draw_text_rc(text)
+    }
+
+    fn draw_shape(&self, shape: &Shape) -> () {
+        self.record_shape(shape.get_point_count(),
+                          shape.get_outline_thickness(),
+                          texture_id(shape.get_texture()), 0);
+        self.target.draw_shape(shape)
+    }
+
+    fn draw_shape_rc(&self, shape: &rc::Shape) -> () {
+        self.record_shape(shape.get_point_count(),
+                          shape.get_outline_thickness(),
+                          rc_texture_id(&shape.get_texture()), 0);
+        self.target.draw_shape_rc(shape)
+    }
+
+    fn draw_sprite(&self, sprite: &Sprite) -> () {
+        self.record(SpriteDraw, Some(TrianglesStrip), 4, texture_id(sprite.get_texture()), 0);
+        self.target.draw_sprite(sprite)
+    }
+
+    fn draw_sprite_rc(&self, sprite: &rc::Sprite) -> () {
+        self.record(SpriteDraw, Some(TrianglesStrip), 4, rc_texture_id(&sprite.get_texture()), 0);
+        self.target.draw_sprite_rc(sprite)
+    }
+
+    fn draw_circle_shape(&self, circle_shape: &CircleShape) -> () {
+        self.record_shape(circle_shape.get_point_count(),
+                          circle_shape.get_outline_thickness(),
+                          texture_id(circle_shape.get_texture()), 0);
+        self.target.draw_circle_shape(circle_shape)
+    }
+
+    fn draw_circle_shape_rc(&self, circle_shape: &rc::CircleShape) -> () {
+        self.record_shape(circle_shape.get_point_count(),
+                          circle_shape.get_outline_thickness(),
+                          rc_texture_id(&circle_shape.get_texture()), 0);
+        self.target.draw_circle_shape_rc(circle_shape)
+    }
+
+    fn draw_rectangle_shape(&self, rectangle_shape: &RectangleShape) -> () {
+        self.record_shape(rectangle_shape.get_point_count(),
+                          rectangle_shape.get_outline_thickness(),
+                          texture_id(rectangle_shape.get_texture()), 0);
+        self.target.draw_rectangle_shape(rectangle_shape)
+    }
+
+    fn draw_rectangle_shape_rc(&self, rectangle_shape: &rc::RectangleShape) -> () {
+        self.record_shape(rectangle_shape.get_point_count(),
+                          rectangle_shape.get_outline_thickness(),
+                          rc_texture_id(&rectangle_shape.get_texture()), 0);
+        self.target.draw_rectangle_shape_rc(rectangle_shape)
+    }
+
+    fn draw_convex_shape(&self, convex_shape: &ConvexShape) -> () {
+        self.record_shape(convex_shape.get_point_count(),
+                          convex_shape.get_outline_thickness(),
+                          texture_id(convex_shape.get_texture()), 0);
+        self.target.draw_convex_shape(convex_shape)
+    }
+
+    fn draw_convex_shape_rc(&self, convex_shape: &rc::ConvexShape) -> () {
+        self.record_shape(convex_shape.get_point_count(),
+                          convex_shape.get_outline_thickness(),
+                          rc_texture_id(&convex_shape.get_texture()), 0);
+        self.target.draw_convex_shape_rc(convex_shape)
+    }
+
+    fn draw_vertex_array(&self, vertex_array: &VertexArray) -> () {
+        self.record(VertexArrayDraw, Some(vertex_array.get_primitive_type()),
+                    vertex_array.get_vertex_count(), 0, 0);
+        self.target.draw_vertex_array(vertex_array)
+    }
+
+    fn draw_text_rs(&self, text: &Text, render_states: &mut RenderStates) -> () {
+        self.record_rs(TextDraw, Some(Quads),
+                       glyph_vertex_count(text.get_unicode_string().as_slice()),
+                       font_id(text.get_font()), render_states);
+        self.target.draw_text_rs(text, render_states)
+    }
+
+    fn draw_text_rs_rc(&self, text: &rc::Text, render_states: &mut rc::RenderStates) -> () {
+        self.record_rs_rc(TextDraw, Some(Quads),
+                          glyph_vertex_count(text.get_unicode_string().as_slice()),
+                          rc_font_id(&text.get_font()), render_states);
+        self.target.draw_text_rs_rc(text, render_states)
+    }
+
+    fn draw_shape_rs(&self, shape: &Shape, render_states: &mut RenderStates) -> () {
+        self.record_shape_rs(shape.get_point_count(),
+                             shape.get_outline_thickness(),
+                             texture_id(shape.get_texture()), render_states);
+        self.target.draw_shape_rs(shape, render_states)
+    }
+
+    fn draw_shape_rs_rc(&self, shape: &rc::Shape, render_states: &mut rc::RenderStates) -> () {
+        self.record_shape_rs_rc(shape.get_point_count(),
+                                shape.get_outline_thickness(),
+                                rc_texture_id(&shape.get_texture()), render_states);
+        self.target.draw_shape_rs_rc(shape, render_states)
+    }
+
+    fn draw_sprite_rs(&self, sprite: &Sprite, render_states: &mut RenderStates) -> () {
+        self.record_rs(SpriteDraw, Some(TrianglesStrip), 4,
+                       texture_id(sprite.get_texture()), render_states);
+        self.target.draw_sprite_rs(sprite, render_states)
+    }
+
+    fn draw_sprite_rs_rc(&self, sprite: &rc::Sprite, render_states: &mut rc::RenderStates) -> () {
+        self.record_rs_rc(SpriteDraw, Some(TrianglesStrip), 4,
+                          rc_texture_id(&sprite.get_texture()), render_states);
+        self.target.draw_sprite_rs_rc(sprite, render_states)
+    }
+
+    fn draw_circle_shape_rs(&self,
+                            circle_shape: &CircleShape,
+                            render_states: &mut RenderStates) -> () {
+        self.record_shape_rs(circle_shape.get_point_count(),
+                             circle_shape.get_outline_thickness(),
+                             texture_id(circle_shape.get_texture()), render_states);
+        self.target.draw_circle_shape_rs(circle_shape, render_states)
+    }
+
+    fn draw_circle_shape_rs_rc(&self,
+                               circle_shape: &rc::CircleShape,
+                               render_states: &mut rc::RenderStates) -> () {
+        self.record_shape_rs_rc(circle_shape.get_point_count(),
+                                circle_shape.get_outline_thickness(),
+                                rc_texture_id(&circle_shape.get_texture()), render_states);
+        self.target.draw_circle_shape_rs_rc(circle_shape, render_states)
+    }
+
+    fn draw_rectangle_shape_rs(&self,
+                               rectangle_shape: &RectangleShape,
+                               render_states: &mut RenderStates) -> () {
+        self.record_shape_rs(rectangle_shape.get_point_count(),
+                             rectangle_shape.get_outline_thickness(),
+                             texture_id(rectangle_shape.get_texture()), render_states);
+        self.target.draw_rectangle_shape_rs(rectangle_shape, render_states)
+    }
+
+    fn draw_rectangle_shape_rs_rc(&self,
+                                  rectangle_shape: &rc::RectangleShape,
+                                  render_states: &mut rc::RenderStates) -> () {
+        self.record_shape_rs_rc(rectangle_shape.get_point_count(),
+                                rectangle_shape.get_outline_thickness(),
+                                rc_texture_id(&rectangle_shape.get_texture()), render_states);
+        self.target.draw_rectangle_shape_rs_rc(rectangle_shape, render_states)
+    }
+
+    fn draw_convex_shape_rs(&self,
+                            convex_shape: &ConvexShape,
+                            render_states: &mut RenderStates) -> () {
+        self.record_shape_rs(convex_shape.get_point_count(),
+                             convex_shape.get_outline_thickness(),
+                             texture_id(convex_shape.get_texture()), render_states);
+        self.target.draw_convex_shape_rs(convex_shape, render_states)
+    }
+
+    fn draw_convex_shape_rs_rc(&self,
+                               convex_shape: &rc::ConvexShape,
+                               render_states: &mut rc::RenderStates) -> () {
+        self.record_shape_rs_rc(convex_shape.get_point_count(),
+                                convex_shape.get_outline_thickness(),
+                                rc_texture_id(&convex_shape.get_texture()), render_states);
+        self.target.draw_convex_shape_rs_rc(convex_shape, render_states)
+    }
+
+    fn draw_vertex_array_rs(&self,
+                            vertex_array: &VertexArray,
+                            render_states: &mut RenderStates) -> () {
+        self.record_rs(VertexArrayDraw, Some(vertex_array.get_primitive_type()),
+                       vertex_array.get_vertex_count(), 0, render_states);
+        self.target.draw_vertex_array_rs(vertex_array, render_states)
+    }
+
+    fn draw_vertex_array_rs_rc(&self,
+                               vertex_array: &VertexArray,
+                               render_states: &mut rc::RenderStates) -> () {
+        self.record_rs_rc(VertexArrayDraw, Some(vertex_array.get_primitive_type()),
+                          vertex_array.get_vertex_count(), 0, render_states);
+        self.target.draw_vertex_array_rs_rc(vertex_array, render_states)
+    }
+
+    fn draw_primitives(&self, vertices: &[Vertex], ty: PrimitiveType) -> () {
+        self.record(PrimitivesDraw, Some(ty), vertices.len(), 0, 0);
+        self.target.draw_primitives(vertices, ty)
+    }
+
+    fn draw_primitives_rs(&self,
+                          vertices: &[Vertex],
+                          ty: PrimitiveType,
+                          render_states: &mut RenderStates) -> () {
+        self.record_rs(PrimitivesDraw, Some(ty), vertices.len(), 0, render_states);
+        self.target.draw_primitives_rs(vertices, ty, render_states)
+    }
+}
+
+#[doc(hidden)]
+fn texture_id(texture: Option<&Texture>) -> uint {
+    match texture {
+        Some(texture) => texture.unwrap() as uint,
+        None => 0
+    }
+}
+
+#[doc(hidden)]
+fn rc_texture_id(texture: &Option<Rc<RefCell<Texture>>>) -> uint {
+    match *texture {
+        Some(ref texture) => texture.borrow().unwrap() as uint,
+        None => 0
+    }
+}
+
+// Vertices of the quads of the glyphs of a string, like SFML which gives no
+// quad to the spaces, tabs and line breaks
+#[doc(hidden)]
+fn glyph_vertex_count(string: &[u32]) -> uint {
+    4 * string.iter().filter(|&&code| {
+        code != ' ' as u32 && code != '\t' as u32 && code != '\n' as u32 && code != 0x0b
+    }).count()
+}
+
+#[doc(hidden)]
+fn font_id(font: Option<&Font>) -> uint {
+    match font {
+        Some(font) => font.unwrap() as uint,
+        None => 0
+    }
+}
+
+#[doc(hidden)]
+fn rc_font_id(font: &Option<Rc<RefCell<Font>>>) -> uint {
+    match *font {
+        Some(ref font) => font.borrow().unwrap() as uint,
+        None => 0
+    }
+}
+
+#[doc(hidden)]
+fn shader_id(shader: Option<&Shader>) -> uint {
+    match shader {
+        Some(shader) => shader.unwrap() as uint,
+        None => 0
+    }
+}
+
+#[doc(hidden)]
+fn rc_shader_id(shader: &Option<Rc<RefCell<rc::Shader>>>) -> uint {
+    match *shader {
+        Some(ref shader) => shader.borrow().unwrap() as uint,
+        None => 0
+    }
+}
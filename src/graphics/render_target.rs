@@ -51,6 +51,26 @@ pub trait RenderTarget {
     /// get the viewport of the render target
     fn get_viewport(&self, view: &View) -> IntRect;
 
+    /// Restrict the drawing to a rectangle
+    ///
+    /// The clips are stacked: the drawing is restricted to the
+    /// intersection of the rectangle with the current clip, until
+    /// pop_clip is called. Every draw method is clipped, whatever the
+    /// view, but clear still clears the whole target.
+    ///
+    /// # Arguments
+    /// * rect - The clipping rectangle, in pixels
+    fn push_clip(&mut self, rect: &IntRect);
+
+    /// Remove the current clip and restore the previous one
+    fn pop_clip(&mut self);
+
+    /// Get the current clip
+    ///
+    /// Return Some(rect) in pixels, or None if no clip is active or if
+    /// the nested clips don't intersect
+    fn get_clip(&self) -> Option<IntRect>;
+
 
     /// Convert a point from window coordinates to world coordinates
     ///
@@ -34,6 +34,7 @@ use system::vector2::{Vector2f, Vector2i, Vector2u};
 use graphics::{View, Sprite, Color, IntRect, Texture, CircleShape,
                RectangleShape, VertexArray, ConvexShape, RenderStates,
                Shape, Text, RenderTarget, rc, Vertex, PrimitiveType};
+use graphics::clip_stack::ClipStack;
 
 use ffi::sfml_types::{SFTRUE, SFFALSE};
 use ffi::graphics::render_texture as ffi;
@@ -41,7 +42,9 @@ use ffi::graphics::render_texture as ffi;
 /// Target for off-screen 2D rendering into a texture
 pub struct RenderTexture {
     #[doc(hidden)]
-    render_texture: *mut ffi::sfRenderTexture
+    render_texture: *mut ffi::sfRenderTexture,
+    #[doc(hidden)]
+    clip: ClipStack
 }
 
 impl RenderTexture {
@@ -69,7 +72,8 @@ impl RenderTexture {
             None
         } else {
             Some(RenderTexture {
-                    render_texture: tex
+                    render_texture: tex,
+                    clip: ClipStack::new()
                 })
         }
     }
@@ -137,10 +141,58 @@ impl RenderTexture {
             SFTRUE  => true
         }
     }
+
+    #[doc(hidden)]
+    fn apply_clip(&mut self) -> () {
+        let viewport = match self.clip.get_view() {
+            Some(view) => self.get_viewport(view),
+            None => return
+        };
+        let size = self.get_size();
+        match self.clip.get_clipped_view(&viewport, &size) {
+            Some(view) => unsafe {
+                ffi::sfRenderTexture_setView(self.render_texture, view.unwrap())
+            },
+            None => {}
+        }
+    }
 }
 
 impl RenderTarget for RenderTexture{
 
+    /// Restrict the drawing to a rectangle
+    ///
+    /// The clips are stacked: the drawing is restricted to the
+    /// intersection of the rectangle with the current clip, until
+    /// pop_clip is called. Every draw method is clipped, whatever the
+    /// view, but clear still clears the whole render texture.
+    ///
+    /// # Arguments
+    /// * rect - The clipping rectangle, in pixels
+    fn push_clip(&mut self, rect: &IntRect) -> () {
+        let view = self.get_view();
+        self.clip.push(rect, &view);
+        self.apply_clip();
+    }
+
+    /// Remove the current clip and restore the previous one
+    fn pop_clip(&mut self) -> () {
+        match self.clip.pop() {
+            Some(view) => unsafe {
+                ffi::sfRenderTexture_setView(self.render_texture, view.unwrap())
+            },
+            None => self.apply_clip()
+        }
+    }
+
+    /// Get the current clip
+    ///
+    /// Return Some(rect) in pixels, or None if no clip is active or if
+    /// the nested clips don't intersect
+    fn get_clip(&self) -> Option<IntRect> {
+        self.clip.get_clip()
+    }
+
     /// Get the size of the rendering region of a render texture
     ///
     /// Return the size in pixels
@@ -165,8 +217,13 @@ impl RenderTarget for RenderTexture{
     /// # Arguments
     /// * view - the new view
     fn set_view(&mut self, view: &View) -> () {
-        unsafe {
-            ffi::sfRenderTexture_setView(self.render_texture, view.unwrap())
+        if !self.clip.is_empty() {
+            self.clip.set_view(view);
+            self.apply_clip();
+        } else {
+            unsafe {
+                ffi::sfRenderTexture_setView(self.render_texture, view.unwrap())
+            }
         }
     }
 
@@ -174,6 +231,10 @@ impl RenderTarget for RenderTexture{
     ///
     /// Return the current active view
     fn get_view(&self) -> View {
+        match self.clip.get_view() {
+            Some(view) => return view.clone(),
+            None => {}
+        }
         unsafe {
             Wrappable::wrap(ffi::sfRenderTexture_getView(self.render_texture))
         }
@@ -259,6 +320,10 @@ impl RenderTarget for RenderTexture{
     ///
     /// Return the converted point, in "world" units
     fn map_pixel_to_coords_current_view(&self, point: &Vector2i) -> Vector2f {
+        match self.clip.get_view() {
+            Some(view) => return self.map_pixel_to_coords(point, view),
+            None => {}
+        }
         let view = unsafe { ffi::sfRenderTexture_getView(self.render_texture) };
         unsafe {
             ffi::sfRenderTexture_mapPixelToCoords(self.render_texture,
@@ -314,6 +379,10 @@ impl RenderTarget for RenderTexture{
     /// # Arguments
     /// * point - Point to convert
     fn map_coords_to_pixel_current_view(&self, point: &Vector2f) -> Vector2i {
+        match self.clip.get_view() {
+            Some(view) => return self.map_coords_to_pixel(point, view),
+            None => {}
+        }
         let view = unsafe { ffi::sfRenderTexture_getView(self.render_texture) };
         unsafe {
             ffi::sfRenderTexture_mapCoordsToPixel(self.render_texture,
@@ -40,6 +40,7 @@ use system::vector2::{Vector2f, Vector2i, Vector2u};
 use graphics::{Text, Color, Sprite, CircleShape, RectangleShape, ConvexShape,
                RenderStates, View, Image, IntRect, VertexArray, Shape, RenderTarget,
                rc, Vertex, PrimitiveType};
+use graphics::clip_stack::ClipStack;
 
 use ffi::sfml_types::{SfBool, SFTRUE, SFFALSE};
 use ffi::graphics::render_window as ffi;
@@ -52,6 +53,7 @@ use ffi::graphics::render_window as ffi;
 pub struct RenderWindow {
     render_window: *mut ffi::sfRenderWindow,
     title_length: uint,
+    clip: ClipStack,
 //    current_view: Rc<RefCell<View>>,
 //    default_view: Rc<RefCell<View>>
 }
@@ -101,7 +103,8 @@ impl RenderWindow {
             Some (RenderWindow {
                       render_window: sf_render_win,
                       // event: sf_ev,
-                      title_length: title.len()
+                      title_length: title.len(),
+                      clip: ClipStack::new()
             })
         }
     }
@@ -143,7 +146,8 @@ impl RenderWindow {
             Some (RenderWindow {
                     render_window: sf_render_win,
                     // event: sf_ev,
-                    title_length: title.len()
+                    title_length: title.len(),
+                    clip: ClipStack::new()
             })
         }
     }
@@ -207,7 +211,9 @@ impl RenderWindow {
         if have_event == false {
             event::NoEvent
         } else {
-            event::raw::get_wrapped_event(&mut event)
+            let wrapped = event::raw::get_wrapped_event(&mut event);
+            self.update_clip();
+            wrapped
         }
     }
 
@@ -233,7 +239,9 @@ impl RenderWindow {
         if have_event == false {
             event::NoEvent
         } else {
-            event::raw::get_wrapped_event(&mut event)
+            let wrapped = event::raw::get_wrapped_event(&mut event);
+            self.update_clip();
+            wrapped
         }
     }
 
@@ -474,6 +482,7 @@ impl RenderWindow {
         unsafe {
             ffi::sfRenderWindow_setSize(self.render_window, *size)
         }
+        self.update_clip()
     }
 
     /// Change the size of the rendering region of a window
@@ -487,6 +496,7 @@ impl RenderWindow {
             ffi::sfRenderWindow_setSize(self.render_window,
                                         Vector2u::new(size_x, size_y))
         }
+        self.update_clip()
     }
 
     /// Get the current position of the mouse relatively to a render window
@@ -537,6 +547,30 @@ impl RenderWindow {
         }
     }
 
+    #[doc(hidden)]
+    fn apply_clip(&mut self) -> () {
+        let viewport = match self.clip.get_view() {
+            Some(view) => self.get_viewport(view),
+            None => return
+        };
+        let size = self.get_size();
+        match self.clip.get_clipped_view(&viewport, &size) {
+            Some(view) => unsafe {
+                ffi::sfRenderWindow_setView(self.render_window, view.unwrap())
+            },
+            None => {}
+        }
+    }
+
+    // Apply the clip again if the window was resized since it was applied
+    #[doc(hidden)]
+    fn update_clip(&mut self) -> () {
+        let size = self.get_size();
+        if self.clip.is_outdated(&size) {
+            self.apply_clip();
+        }
+    }
+
     #[doc(hidden)]
     pub fn unwrap(&self) -> *mut ffi::sfRenderWindow {
         self.render_window
@@ -545,6 +579,39 @@ impl RenderWindow {
 
 impl RenderTarget for RenderWindow{
 
+    /// Restrict the drawing to a rectangle
+    ///
+    /// The clips are stacked: the drawing is restricted to the
+    /// intersection of the rectangle with the current clip, until
+    /// pop_clip is called. Every draw method is clipped, whatever the
+    /// view, but clear still clears the whole window.
+    ///
+    /// # Arguments
+    /// * rect - The clipping rectangle, in pixels
+    fn push_clip(&mut self, rect: &IntRect) -> () {
+        let view = self.get_view();
+        self.clip.push(rect, &view);
+        self.apply_clip();
+    }
+
+    /// Remove the current clip and restore the previous one
+    fn pop_clip(&mut self) -> () {
+        match self.clip.pop() {
+            Some(view) => unsafe {
+                ffi::sfRenderWindow_setView(self.render_window, view.unwrap())
+            },
+            None => self.apply_clip()
+        }
+    }
+
+    /// Get the current clip
+    ///
+    /// Return Some(rect) in pixels, or None if no clip is active or if
+    /// the nested clips don't intersect
+    fn get_clip(&self) -> Option<IntRect> {
+        self.clip.get_clip()
+    }
+
     /// Save the current OpenGL render states and matrices
     ///
     /// This function can be used when you mix SFML drawing
@@ -594,9 +661,14 @@ impl RenderTarget for RenderWindow{
     /// * view - The new view
     ////
     fn set_view(&mut self, view: &View) -> () {
-        unsafe {
-            ffi::sfRenderWindow_setView(self.render_window,
-                                        view.unwrap())
+        if !self.clip.is_empty() {
+            self.clip.set_view(view);
+            self.apply_clip();
+        } else {
+            unsafe {
+                ffi::sfRenderWindow_setView(self.render_window,
+                                            view.unwrap())
+            }
         }
     }
 
@@ -605,6 +677,10 @@ impl RenderTarget for RenderWindow{
     /// Return the current active view
     ////
     fn get_view(&self) -> View {
+        match self.clip.get_view() {
+            Some(view) => return view.clone(),
+            None => {}
+        }
         unsafe{
             Wrappable::wrap(ffi::sfRenderWindow_getView(self.render_window))
         }
@@ -681,6 +757,10 @@ impl RenderTarget for RenderWindow{
     /// Return the converted point, in "world" units
     fn map_pixel_to_coords_current_view(&self,
                                             point: &Vector2i) -> Vector2f {
+        match self.clip.get_view() {
+            Some(view) => return self.map_pixel_to_coords(point, view),
+            None => {}
+        }
         let view = unsafe {ffi::sfRenderWindow_getView(self.render_window)};
         unsafe {
             ffi::sfRenderWindow_mapPixelToCoords(self.render_window,
@@ -737,6 +817,10 @@ impl RenderTarget for RenderWindow{
     /// * point - Point to convert
     fn map_coords_to_pixel_current_view(&self,
                                             point: &Vector2f) -> Vector2i {
+        match self.clip.get_view() {
+            Some(view) => return self.map_coords_to_pixel(point, view),
+            None => {}
+        }
         let currView =
             unsafe { ffi::sfRenderWindow_getView(self.render_window) };
         unsafe {
@@ -1103,6 +1187,8 @@ impl RenderTarget for RenderWindow{
 
     /// Clear window with the given color
     fn clear(&mut self, color: &Color) -> () {
+        // Catch the resizes seen through the events iterator
+        self.update_clip();
         unsafe {
             ffi::sfRenderWindow_clear(self.render_window, *color)
         }
@@ -0,0 +1,329 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! View scaled to a window according to a viewport policy
+//!
+//! A ScaledView keeps a View fitted to the size of the window it is
+//! shown on: it consumes the Resized events, computes the viewport and
+//! the visible area, and maps the mouse coordinates to the world.
+
+pub use self::ViewportPolicy::{Stretch, Letterbox, Crop, IntegerScale, Expand};
+
+use std::num::Float;
+
+use graphics::{View, FloatRect, IntRect, RenderTarget};
+use system::vector2::{Vector2f, Vector2i, Vector2u};
+use window::event;
+use window::event::Event;
+
+/// How the logical area is fitted to the window
+#[deriving(Clone, PartialEq, Eq, Show, Copy)]
+pub enum ViewportPolicy {
+    /// Fill the window, distorting the logical area
+    Stretch,
+    /// Show the whole logical area, with bars on the sides if needed
+    Letterbox,
+    /// Fill the window, cropping the logical area if needed
+    Crop,
+    /// Scale the logical area by a whole number, with bars around it.
+    /// Falls back to Letterbox if the window is smaller than the logical area
+    IntegerScale,
+    /// Fill the window, showing more than the logical area if needed
+    Expand
+}
+
+/// View scaled to a window according to a viewport policy
+pub struct ScaledView {
+    #[doc(hidden)]
+    view: View,
+    #[doc(hidden)]
+    policy: ViewportPolicy,
+    #[doc(hidden)]
+    logical_size: Vector2f,
+    #[doc(hidden)]
+    window_size: Vector2u,
+    #[doc(hidden)]
+    center: Vector2f,
+    #[doc(hidden)]
+    visible_size: Vector2f,
+    #[doc(hidden)]
+    scale: Vector2f,
+    #[doc(hidden)]
+    pixel_viewport: FloatRect
+}
+
+impl ScaledView {
+    /// Create a new scaled view
+    ///
+    /// The view is centered on the logical area.
+    ///
+    /// # Arguments
+    /// * logical_size - The size of the area the game is designed for
+    /// * window_size - The current size of the window
+    /// * policy - How the logical area is fitted to the window
+    ///
+    /// Return Some(ScaledView) or None
+    pub fn new(logical_size: &Vector2f,
+               window_size: &Vector2u,
+               policy: ViewportPolicy) -> Option<ScaledView> {
+        let center = *logical_size * 0.5;
+        match View::new_init(&center, logical_size) {
+            Some(view) => {
+                let mut scaled = ScaledView {
+                    view: view,
+                    policy: policy,
+                    logical_size: *logical_size,
+                    window_size: *window_size,
+                    center: center,
+                    visible_size: *logical_size,
+                    scale: Vector2f::new(1., 1.),
+                    pixel_viewport: FloatRect::new(0., 0., 0., 0.)
+                };
+                scaled.update();
+                Some(scaled)
+            },
+            None => None
+        }
+    }
+
+    /// Handle an event
+    ///
+    /// Resized events update the view, the other events are ignored.
+    ///
+    /// # Arguments
+    /// * event - The event received by the window
+    ///
+    /// Return true if the event was a Resized event, false otherwise
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match *event {
+            event::Resized { width, height } => {
+                self.set_window_size(&Vector2u::new(width, height));
+                true
+            },
+            _ => false
+        }
+    }
+
+    /// Apply the view to a render target
+    ///
+    /// # Arguments
+    /// * render_target - The target on which the view is set
+    pub fn apply<RT: RenderTarget>(&self, render_target: &mut RT) -> () {
+        render_target.set_view(&self.view)
+    }
+
+    /// Get the fitted view
+    ///
+    /// Return a reference to the View
+    pub fn get_view(&self) -> &View {
+        &self.view
+    }
+
+    /// Set the size of the window
+    ///
+    /// # Arguments
+    /// * window_size - The new size of the window
+    pub fn set_window_size(&mut self, window_size: &Vector2u) -> () {
+        self.window_size = *window_size;
+        self.update();
+    }
+
+    /// Get the size of the window
+    ///
+    /// Return the size of the window
+    pub fn get_window_size(&self) -> Vector2u {
+        self.window_size
+    }
+
+    /// Set the size of the logical area
+    ///
+    /// # Arguments
+    /// * logical_size - The size of the area the game is designed for
+    pub fn set_logical_size(&mut self, logical_size: &Vector2f) -> () {
+        self.logical_size = *logical_size;
+        self.update();
+    }
+
+    /// Get the size of the logical area
+    ///
+    /// Return the logical size
+    pub fn get_logical_size(&self) -> Vector2f {
+        self.logical_size
+    }
+
+    /// Set the viewport policy
+    ///
+    /// # Arguments
+    /// * policy - How the logical area is fitted to the window
+    pub fn set_policy(&mut self, policy: ViewportPolicy) -> () {
+        self.policy = policy;
+        self.update();
+    }
+
+    /// Get the viewport policy
+    ///
+    /// Return the policy
+    pub fn get_policy(&self) -> ViewportPolicy {
+        self.policy
+    }
+
+    /// Set the center of the view
+    ///
+    /// # Arguments
+    /// * center - The new center, in world coordinates
+    pub fn set_center(&mut self, center: &Vector2f) -> () {
+        self.center = *center;
+        self.view.set_center(center);
+    }
+
+    /// Get the center of the view
+    ///
+    /// Return the center, in world coordinates
+    pub fn get_center(&self) -> Vector2f {
+        self.center
+    }
+
+    /// Get the size of the area visible in the window
+    ///
+    /// It is the logical size, except with Crop (smaller) and
+    /// Expand (larger).
+    ///
+    /// Return the visible size, in world units
+    pub fn get_visible_size(&self) -> Vector2f {
+        self.visible_size
+    }
+
+    /// Get the scale from world units to pixels
+    ///
+    /// Both components are equal unless the policy is Stretch.
+    ///
+    /// Return the number of pixels per world unit, on each axis
+    pub fn get_scale(&self) -> Vector2f {
+        self.scale
+    }
+
+    /// Get the area of the window covered by the view
+    ///
+    /// Return the viewport, in pixels
+    pub fn get_pixel_viewport(&self) -> IntRect {
+        IntRect::new(self.pixel_viewport.left as i32,
+                     self.pixel_viewport.top as i32,
+                     self.pixel_viewport.width as i32,
+                     self.pixel_viewport.height as i32)
+    }
+
+    /// Tell whether a pixel is inside the viewport, and not in the bars
+    ///
+    /// # Arguments
+    /// * pixel - The pixel, relative to the window
+    ///
+    /// Return true if the pixel shows the view, false otherwise
+    pub fn contains_pixel(&self, pixel: &Vector2i) -> bool {
+        self.pixel_viewport.contains(pixel.x as f32 + 0.5, pixel.y as f32 + 0.5)
+    }
+
+    /// Convert a pixel of the window to world coordinates
+    ///
+    /// Pixels in the bars are mapped outside of the visible area.
+    ///
+    /// # Arguments
+    /// * pixel - The pixel, relative to the window
+    ///
+    /// Return the world coordinates of the pixel
+    pub fn map_pixel_to_coords(&self, pixel: &Vector2i) -> Vector2f {
+        let origin = self.center - self.visible_size * 0.5;
+        Vector2f::new(origin.x + (pixel.x as f32 - self.pixel_viewport.left) / self.scale.x,
+                      origin.y + (pixel.y as f32 - self.pixel_viewport.top) / self.scale.y)
+    }
+
+    /// Convert world coordinates to a pixel of the window
+    ///
+    /// # Arguments
+    /// * point - The world coordinates
+    ///
+    /// Return the pixel, relative to the window
+    pub fn map_coords_to_pixel(&self, point: &Vector2f) -> Vector2i {
+        let origin = self.center - self.visible_size * 0.5;
+        Vector2i::new((self.pixel_viewport.left + (point.x - origin.x) * self.scale.x).floor() as i32,
+                      (self.pixel_viewport.top + (point.y - origin.y) * self.scale.y).floor() as i32)
+    }
+
+    /// Get the world coordinates of a mouse event
+    ///
+    /// # Arguments
+    /// * event - The event received by the window
+    ///
+    /// Return Some(coordinates) for mouse events inside the viewport,
+    /// None for the other events
+    pub fn map_mouse_event(&self, event: &Event) -> Option<Vector2f> {
+        let pixel = match *event {
+            event::MouseMoved { x, y } => Vector2i::new(x, y),
+            event::MouseButtonPressed { x, y, .. } => Vector2i::new(x, y),
+            event::MouseButtonReleased { x, y, .. } => Vector2i::new(x, y),
+            event::MouseWheelMoved { x, y, .. } => Vector2i::new(x, y),
+            _ => return None
+        };
+        if self.contains_pixel(&pixel) {
+            Some(self.map_pixel_to_coords(&pixel))
+        } else {
+            None
+        }
+    }
+
+    // Recompute the scale, the viewport and the visible area
+    #[doc(hidden)]
+    fn update(&mut self) -> () {
+        let window = Vector2f::new(self.window_size.x as f32, self.window_size.y as f32);
+        if window.x <= 0. || window.y <= 0. ||
+           self.logical_size.x <= 0. || self.logical_size.y <= 0. {
+            return;
+        }
+        let ratio = window / self.logical_size;
+        let fit = ratio.x.min(ratio.y);
+        let (scale, viewport_size) = match self.policy {
+            Stretch => (ratio, window),
+            Letterbox => (Vector2f::new(fit, fit), self.logical_size * fit),
+            Crop => {
+                let fill = ratio.x.max(ratio.y);
+                (Vector2f::new(fill, fill), window)
+            },
+            IntegerScale => {
+                let whole = if fit >= 1. { fit.floor() } else { fit };
+                (Vector2f::new(whole, whole), self.logical_size * whole)
+            },
+            Expand => (Vector2f::new(fit, fit), window)
+        };
+        let left = ((window.x - viewport_size.x) / 2.).floor();
+        let top = ((window.y - viewport_size.y) / 2.).floor();
+        self.scale = scale;
+        self.visible_size = viewport_size / scale;
+        self.pixel_viewport = FloatRect::new(left, top, viewport_size.x, viewport_size.y);
+        self.view.set_size(&self.visible_size);
+        self.view.set_center(&self.center);
+        self.view.set_viewport(&FloatRect::new(left / window.x,
+                                               top / window.y,
+                                               viewport_size.x / window.x,
+                                               viewport_size.y / window.y));
+    }
+}
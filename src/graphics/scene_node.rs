@@ -0,0 +1,519 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Hierarchy of transformed drawables
+//!
+//! A SceneNode has a local transform, an optional drawable content and
+//! children. The children are drawn relatively to their parent, on top of
+//! it, and sorted by z-index.
+//!
+//! The content type is shared by the whole tree: to mix sprites, texts and
+//! shapes, implement Drawable for an enum of them. When the content is also
+//! Bounded, the bounds of the nodes are computed from it and nodes can be
+//! hit tested.
+
+use graphics::{Transformable, Transform, FloatRect, RenderTarget, RenderStates, rc};
+use system::vector2::Vector2f;
+use traits::{Drawable, Bounded};
+
+/// Hierarchy of transformed drawables
+pub struct SceneNode<D> {
+    #[doc(hidden)]
+    transformable: Transformable,
+    #[doc(hidden)]
+    content: Option<D>,
+    #[doc(hidden)]
+    children: Vec<SceneNode<D>>,
+    #[doc(hidden)]
+    z_index: i32,
+    #[doc(hidden)]
+    visible: bool,
+    #[doc(hidden)]
+    name: String
+}
+
+impl<D: Drawable> SceneNode<D> {
+    /// Create a new empty node
+    ///
+    /// Return Some(SceneNode) or None
+    pub fn new() -> Option<SceneNode<D>> {
+        match Transformable::new() {
+            Some(transformable) => Some(SceneNode {
+                transformable: transformable,
+                content: None,
+                children: Vec::new(),
+                z_index: 0,
+                visible: true,
+                name: String::new()
+            }),
+            None => None
+        }
+    }
+
+    /// Create a new node with a content
+    ///
+    /// # Arguments
+    /// * content - The drawable drawn by the node
+    ///
+    /// Return Some(SceneNode) or None
+    pub fn new_with_content(content: D) -> Option<SceneNode<D>> {
+        match SceneNode::new() {
+            Some(mut node) => {
+                node.content = Some(content);
+                Some(node)
+            },
+            None => None
+        }
+    }
+
+    /// Set the content of the node
+    ///
+    /// # Arguments
+    /// * content - The drawable drawn by the node
+    pub fn set_content(&mut self, content: D) -> () {
+        self.content = Some(content);
+    }
+
+    /// Remove the content of the node
+    ///
+    /// Return Some(content) or None if the node had no content
+    pub fn take_content(&mut self) -> Option<D> {
+        self.content.take()
+    }
+
+    /// Get the content of the node
+    ///
+    /// Return Some(content) or None if the node has no content
+    pub fn get_content(&self) -> Option<&D> {
+        self.content.as_ref()
+    }
+
+    /// Get the content of the node, to modify it
+    ///
+    /// Return Some(content) or None if the node has no content
+    pub fn get_content_mut(&mut self) -> Option<&mut D> {
+        self.content.as_mut()
+    }
+
+    /// Set the name of the node
+    ///
+    /// # Arguments
+    /// * name - The new name
+    pub fn set_name(&mut self, name: &str) -> () {
+        self.name = name.to_string();
+    }
+
+    /// Get the name of the node
+    ///
+    /// Return the name, empty by default
+    pub fn get_name(&self) -> &str {
+        self.name.as_slice()
+    }
+
+    /// Set the z-index of the node
+    ///
+    /// Siblings with a higher z-index are drawn above. Siblings with
+    /// the same z-index are drawn in the order they were added.
+    ///
+    /// # Arguments
+    /// * z_index - The new z-index
+    pub fn set_z_index(&mut self, z_index: i32) -> () {
+        self.z_index = z_index;
+    }
+
+    /// Get the z-index of the node
+    ///
+    /// Return the z-index, 0 by default
+    pub fn get_z_index(&self) -> i32 {
+        self.z_index
+    }
+
+    /// Show or hide the node and its children
+    ///
+    /// Hidden nodes are not drawn, not hit and not included in bounds.
+    ///
+    /// # Arguments
+    /// * visible - true to show the node, false to hide it
+    pub fn set_visible(&mut self, visible: bool) -> () {
+        self.visible = visible;
+    }
+
+    /// Tell whether the node is visible
+    ///
+    /// Return true if the node is visible, false otherwise
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Add a child to the node
+    ///
+    /// # Arguments
+    /// * child - The node to add
+    ///
+    /// Return the index of the child
+    pub fn add_child(&mut self, child: SceneNode<D>) -> uint {
+        self.children.push(child);
+        self.children.len() - 1
+    }
+
+    /// Remove a child from the node
+    ///
+    /// The indices of the following children are shifted.
+    ///
+    /// # Arguments
+    /// * index - The index of the child
+    ///
+    /// Return Some(child) or None if the index is out of range
+    pub fn remove_child(&mut self, index: uint) -> Option<SceneNode<D>> {
+        if index < self.children.len() {
+            Some(self.children.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Get the number of children
+    ///
+    /// Return the number of children
+    pub fn get_child_count(&self) -> uint {
+        self.children.len()
+    }
+
+    /// Get a child of the node
+    ///
+    /// # Arguments
+    /// * index - The index of the child
+    ///
+    /// Return Some(child) or None if the index is out of range
+    pub fn get_child(&self, index: uint) -> Option<&SceneNode<D>> {
+        self.children.get(index)
+    }
+
+    /// Get a child of the node, to modify it
+    ///
+    /// # Arguments
+    /// * index - The index of the child
+    ///
+    /// Return Some(child) or None if the index is out of range
+    pub fn get_child_mut(&mut self, index: uint) -> Option<&mut SceneNode<D>> {
+        self.children.get_mut(index)
+    }
+
+    /// Get a descendant from the indices of the children leading to it
+    ///
+    /// # Arguments
+    /// * path - The child indices, from this node; empty for this node
+    ///
+    /// Return Some(node) or None if the path is invalid
+    pub fn get_node(&self, path: &[uint]) -> Option<&SceneNode<D>> {
+        let mut node = self;
+        for &index in path.iter() {
+            node = match node.children.get(index) {
+                Some(child) => child,
+                None => return None
+            };
+        }
+        Some(node)
+    }
+
+    /// Get a descendant from the indices of the children leading to it, to modify it
+    ///
+    /// # Arguments
+    /// * path - The child indices, from this node; empty for this node
+    ///
+    /// Return Some(node) or None if the path is invalid
+    pub fn get_node_mut(&mut self, path: &[uint]) -> Option<&mut SceneNode<D>> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        match self.children.get_mut(path[0]) {
+            Some(child) => child.get_node_mut(path.slice_from(1)),
+            None => None
+        }
+    }
+
+    /// Find a descendant by name, depth first
+    ///
+    /// # Arguments
+    /// * name - The name of the node
+    ///
+    /// Return Some(path) of the first node with the name, or None
+    pub fn find(&self, name: &str) -> Option<Vec<uint>> {
+        if self.name.as_slice() == name {
+            return Some(Vec::new());
+        }
+        for (index, child) in self.children.iter().enumerate() {
+            match child.find(name) {
+                Some(mut path) => {
+                    path.insert(0, index);
+                    return Some(path);
+                },
+                None => {}
+            }
+        }
+        None
+    }
+
+    /// Set the position of the node, relatively to its parent
+    ///
+    /// # Arguments
+    /// * position - The new position
+    pub fn set_position(&mut self, position: &Vector2f) -> () {
+        self.transformable.set_position(position)
+    }
+
+    /// Set the orientation of the node, relatively to its parent
+    ///
+    /// # Arguments
+    /// * angle - The new rotation, in degrees
+    pub fn set_rotation(&mut self, angle: f32) -> () {
+        self.transformable.set_rotation(angle)
+    }
+
+    /// Set the scale factors of the node, relatively to its parent
+    ///
+    /// # Arguments
+    /// * scale - The new scale factors
+    pub fn set_scale(&mut self, scale: &Vector2f) -> () {
+        self.transformable.set_scale(scale)
+    }
+
+    /// Set the local origin of the node
+    ///
+    /// # Arguments
+    /// * origin - The new origin
+    pub fn set_origin(&mut self, origin: &Vector2f) -> () {
+        self.transformable.set_origin(origin)
+    }
+
+    /// Get the position of the node, relatively to its parent
+    ///
+    /// Return the current position
+    pub fn get_position(&self) -> Vector2f {
+        self.transformable.get_position()
+    }
+
+    /// Get the orientation of the node, relatively to its parent
+    ///
+    /// Return the current rotation, in degrees
+    pub fn get_rotation(&self) -> f32 {
+        self.transformable.get_rotation()
+    }
+
+    /// Get the scale factors of the node, relatively to its parent
+    ///
+    /// Return the current scale factors
+    pub fn get_scale(&self) -> Vector2f {
+        self.transformable.get_scale()
+    }
+
+    /// Get the local origin of the node
+    ///
+    /// Return the current origin
+    pub fn get_origin(&self) -> Vector2f {
+        self.transformable.get_origin()
+    }
+
+    /// Move the node by a given offset
+    ///
+    /// # Arguments
+    /// * offset - The offset
+    pub fn move_(&mut self, offset: &Vector2f) -> () {
+        self.transformable.move_(offset)
+    }
+
+    /// Rotate the node
+    ///
+    /// # Arguments
+    /// * angle - The angle of rotation, in degrees
+    pub fn rotate(&mut self, angle: f32) -> () {
+        self.transformable.rotate(angle)
+    }
+
+    /// Scale the node
+    ///
+    /// # Arguments
+    /// * factors - The scale factors
+    pub fn scale(&mut self, factors: &Vector2f) -> () {
+        self.transformable.scale(factors)
+    }
+
+    /// Get the transform of the node, relatively to its parent
+    ///
+    /// Return the local transform
+    pub fn get_transform(&self) -> Transform {
+        self.transformable.get_transform()
+    }
+
+    /// Get the inverse of the transform of the node, relatively to its parent
+    ///
+    /// Return the inverse of the local transform
+    pub fn get_inverse_transform(&self) -> Transform {
+        self.transformable.get_inverse_transform()
+    }
+
+    /// Get the transform of a descendant, relatively to the parent of this node
+    ///
+    /// Called on the root, it gives the world transform of the descendant.
+    ///
+    /// # Arguments
+    /// * path - The child indices, from this node; empty for this node
+    ///
+    /// Return Some(transform) or None if the path is invalid
+    pub fn get_world_transform(&self, path: &[uint]) -> Option<Transform> {
+        let mut transform = self.transformable.get_transform();
+        let mut node = self;
+        for &index in path.iter() {
+            node = match node.children.get(index) {
+                Some(child) => child,
+                None => return None
+            };
+            let mut local = node.transformable.get_transform();
+            transform.combine(&mut local);
+        }
+        Some(transform)
+    }
+
+    // Indices of the children, sorted by z-index
+    #[doc(hidden)]
+    fn draw_order(&self) -> Vec<uint> {
+        let mut order: Vec<uint> = range(0, self.children.len()).collect();
+        order.sort_by(|&a, &b| self.children[a].z_index.cmp(&self.children[b].z_index));
+        order
+    }
+}
+
+impl<D: Drawable + Bounded> SceneNode<D> {
+    /// Get the bounds of the content, in the local space of the node
+    ///
+    /// They are the global bounds of the content, which has its own
+    /// transform inside the node.
+    ///
+    /// Return Some(bounds) or None if the node has no content
+    pub fn get_content_bounds(&self) -> Option<FloatRect> {
+        self.content.as_ref().map(|content| content.get_global_bounds())
+    }
+
+    /// Get the bounds of the visible node and its visible descendants
+    ///
+    /// The bounds are expressed relatively to the parent of the node,
+    /// which is the world for the root. Only the nodes with content are
+    /// included.
+    ///
+    /// Return Some(bounds) or None if nothing visible has bounds
+    pub fn get_bounds(&self) -> Option<FloatRect> {
+        if !self.visible {
+            return None;
+        }
+        let mut transform = self.transformable.get_transform();
+        let mut bounds = self.get_content_bounds();
+        for child in self.children.iter() {
+            bounds = match (bounds, child.get_bounds()) {
+                (Some(bounds), Some(child_bounds)) => Some(bounds.union(&child_bounds)),
+                (None, child_bounds) => child_bounds,
+                (bounds, None) => bounds
+            };
+        }
+        match bounds {
+            Some(bounds) => Some(transform.transform_rect(&bounds)),
+            None => None
+        }
+    }
+
+    /// Find the topmost visible node under a point
+    ///
+    /// A node is hit when the point is inside the bounds of its content.
+    /// The nodes drawn last are tested first.
+    ///
+    /// # Arguments
+    /// * point - The point, relatively to the parent of the node
+    ///
+    /// Return Some(path) of the node hit, or None
+    pub fn hit_test(&self, point: &Vector2f) -> Option<Vec<uint>> {
+        if !self.visible {
+            return None;
+        }
+        let mut inverse = self.transformable.get_inverse_transform();
+        let local = inverse.transform_point(point);
+        for &index in self.draw_order().iter().rev() {
+            match self.children[index].hit_test(&local) {
+                Some(mut path) => {
+                    path.insert(0, index);
+                    return Some(path);
+                },
+                None => {}
+            }
+        }
+        match self.get_content_bounds() {
+            Some(bounds) if bounds.contains(local.x, local.y) => Some(Vec::new()),
+            _ => None
+        }
+    }
+}
+
+impl_transformable!(SceneNode<D>, D: Drawable);
+
+impl<D: Drawable> Drawable for SceneNode<D> {
+    fn draw<RT: RenderTarget>(&self, render_target: &mut RT) -> () {
+        self.draw_rs(render_target, &mut RenderStates::default())
+    }
+
+    fn draw_rs<RT: RenderTarget>(&self,
+                                 render_target: &mut RT,
+                                 render_states: &mut RenderStates) -> () {
+        if !self.visible {
+            return;
+        }
+        let parent = render_states.transform;
+        let mut local = self.transformable.get_transform();
+        render_states.transform.combine(&mut local);
+        match self.content {
+            Some(ref content) => content.draw_rs(render_target, render_states),
+            None => {}
+        }
+        for &index in self.draw_order().iter() {
+            self.children[index].draw_rs(render_target, render_states);
+        }
+        render_states.transform = parent;
+    }
+
+    fn draw_rs_rc<RT: RenderTarget>(&self,
+                                    render_target: &mut RT,
+                                    render_states: &mut rc::RenderStates) -> () {
+        if !self.visible {
+            return;
+        }
+        let parent = render_states.transform;
+        let mut local = self.transformable.get_transform();
+        render_states.transform.combine(&mut local);
+        match self.content {
+            Some(ref content) => content.draw_rs_rc(render_target, render_states),
+            None => {}
+        }
+        for &index in self.draw_order().iter() {
+            self.children[index].draw_rs_rc(render_target, render_states);
+        }
+        render_states.transform = parent;
+    }
+}
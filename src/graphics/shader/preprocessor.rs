@@ -0,0 +1,119 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Preprocessing of shader sources
+//!
+//! Replaces the #include "name" directives by the sources given for these
+//! names and adds #define directives, after the #version directive if
+//! there is one, since GLSL requires it to come first.
+
+/// Preprocess the source code of a shader
+///
+/// # Arguments
+/// * source - The source code of the shader
+/// * includes - The (name, source) pairs the #include directives refer to
+/// * defines - The (name, value) pairs to define
+///
+/// Return Some(source), or None if an include is unknown, malformed or
+/// includes itself
+pub fn preprocess(source: &str,
+                  includes: &[(&str, &str)],
+                  defines: &[(&str, &str)]) -> Option<String> {
+    let mut expanded = String::new();
+    let mut stack = Vec::new();
+    if !expand(source, includes, &mut stack, &mut expanded) {
+        return None;
+    }
+
+    let mut define_lines = String::new();
+    for &(name, value) in defines.iter() {
+        define_lines.push_str(format!("#define {} {}\n", name, value).as_slice());
+    }
+
+    // The defines go after the #version directive, wherever comments put
+    // it, or first if there is none
+    let version = expanded.as_slice().lines()
+                          .position(|line| line.trim_left().starts_with("#version"));
+    let mut output = String::with_capacity(expanded.len() + define_lines.len());
+    if version.is_none() {
+        output.push_str(define_lines.as_slice());
+    }
+    for (i, line) in expanded.as_slice().lines().enumerate() {
+        output.push_str(line);
+        output.push('\n');
+        if version == Some(i) {
+            output.push_str(define_lines.as_slice());
+        }
+    }
+    Some(output)
+}
+
+// Append the source to output, with its includes expanded recursively
+#[doc(hidden)]
+fn expand<'a>(source: &str,
+              includes: &[(&'a str, &str)],
+              stack: &mut Vec<&'a str>,
+              output: &mut String) -> bool {
+    for line in source.lines() {
+        let directive = line.trim_left();
+        if !directive.starts_with("#include") {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+        let name = match parse_include(directive.slice_from("#include".len())) {
+            Some(name) => name,
+            None => return false
+        };
+        let (name, included) = match includes.iter().find(|&&(include, _)| include == name) {
+            Some(&(include, included)) => (include, included),
+            None => return false
+        };
+        if stack.contains(&name) {
+            return false;
+        }
+        stack.push(name);
+        if !expand(included, includes, stack, output) {
+            return false;
+        }
+        stack.pop();
+    }
+    true
+}
+
+// Get the name of an include between quotes or angle brackets
+#[doc(hidden)]
+fn parse_include(argument: &str) -> Option<&str> {
+    let argument = argument.trim();
+    if argument.len() < 2 {
+        return None;
+    }
+    let last = argument.len() - 1;
+    if (argument.starts_with("\"") && argument.ends_with("\"")) ||
+       (argument.starts_with("<") && argument.ends_with(">")) {
+        Some(argument.slice(1, last))
+    } else {
+        None
+    }
+}
@@ -31,11 +31,15 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::ptr;
+use std::c_str::CString;
+use std::collections::HashMap;
 
 use traits::Wrappable;
 use graphics::{Texture, Color};
 use system::vector2::Vector2f;
 use system::vector3::Vector3f;
+use graphics::shader::{uniform, preprocessor};
+use graphics::shader::uniform::Uniform;
 
 use ffi::sfml_types::{SFTRUE, SFFALSE};
 use ffi::graphics::shader as ffi;
@@ -49,7 +53,9 @@ pub struct Shader {
     #[doc(hidden)]
     shader: *mut ffi::sfShader,
     #[doc(hidden)]
-    texture: Option<Rc<RefCell<Texture>>>
+    texture: Option<Rc<RefCell<Texture>>>,
+    #[doc(hidden)]
+    names: HashMap<String, CString>
 }
 
 impl Shader {
@@ -90,7 +96,8 @@ impl Shader {
         } else {
             Some(Shader {
                     shader: shader,
-                    texture: None
+                    texture: None,
+                    names: HashMap::new()
                 })
         }
     }
@@ -123,18 +130,57 @@ impl Shader {
             } else {
                 fragment_shader.unwrap().to_c_str().into_inner()
             };
-            ffi::sfShader_createFromFile(c_vertex_shader, c_fragment_shader)
+            ffi::sfShader_createFromMemory(c_vertex_shader, c_fragment_shader)
         };
         if shader.is_null() {
             None
         } else {
             Some(Shader {
                     shader: shader,
-                    texture: None
+                    texture: None,
+                    names: HashMap::new()
                 })
         }
     }
 
+    /// Load both the vertex and fragment shaders from preprocessed source codes
+    ///
+    /// The #include "name" directives of the sources are replaced by the
+    /// sources given for these names, recursively, and a #define directive
+    /// is added for each (name, value) pair, after the #version directive
+    /// if there is one.
+    ///
+    /// # Arguments
+    /// * vertexShader - Some(String) containing the source code of the vertex shader, or None to skip this shader
+    /// * fragmentShader - Some(String) containing the source code of the fragment shader, or None to skip this shader
+    /// * includes - The (name, source) pairs the #include directives refer to
+    /// * defines - The (name, value) pairs to define
+    ///
+    /// Return Some(Shader), or None if an include is unknown or recursive,
+    /// or if the shader can't be created
+    pub fn new_from_strings(vertex_shader: Option<&str>,
+                            fragment_shader: Option<&str>,
+                            includes: &[(&str, &str)],
+                            defines: &[(&str, &str)])
+                            -> Option<Shader> {
+        let vertex_shader = match vertex_shader {
+            Some(source) => match preprocessor::preprocess(source, includes, defines) {
+                Some(source) => Some(source),
+                None => return None
+            },
+            None => None
+        };
+        let fragment_shader = match fragment_shader {
+            Some(source) => match preprocessor::preprocess(source, includes, defines) {
+                Some(source) => Some(source),
+                None => return None
+            },
+            None => None
+        };
+        Shader::new_from_memory(vertex_shader.as_ref().map(|source| source.as_slice()),
+                                fragment_shader.as_ref().map(|source| source.as_slice()))
+    }
+
     /// Change a f32 parameter of a shader
     ///
     /// # Arguments
@@ -355,13 +401,45 @@ impl Shader {
                 });
         }
     }
+
+    /// Change a parameter of a shader
+    ///
+    /// The value can be a f32, a tuple or a fixed size array of 1 to 4
+    /// f32 (assigned as a float or a vector), a Vector2f, a Vector3f, a
+    /// Color, a Transform or CurrentTexture.
+    /// A texture given here must live as long as the program, use
+    /// set_texture_parameter for shared textures.
+    ///
+    /// # Arguments
+    /// * name - Name of the parameter in the shader
+    /// * value - Value to assign
+    pub fn set_uniform<U: Uniform<'static>>(&mut self, name: &str, value: U) -> () {
+        let value = value.to_uniform();
+        let c_name = uniform::get_c_name(&mut self.names, name);
+        uniform::set_uniform_value(self.shader, c_name, &value)
+    }
+
+    /// Change the elements of an array parameter of a shader
+    ///
+    /// Each value is assigned to the element name[i] of the array, for
+    /// instance a float[8] or a vec2[4] parameter of GLSL.
+    ///
+    /// # Arguments
+    /// * name - Name of the array parameter in the shader
+    /// * values - Values to assign to the first elements of the array
+    pub fn set_uniform_array<U: Uniform<'static>>(&mut self, name: &str, values: &[U]) -> () {
+        for (i, value) in values.iter().enumerate() {
+            self.set_uniform(uniform::get_element_name(name, i).as_slice(), value.to_uniform())
+        }
+    }
 }
 
 impl Wrappable<*mut ffi::sfShader> for Shader {
     fn wrap(shader: *mut ffi::sfShader) -> Shader {
         Shader {
             shader:    shader,
-            texture:   None
+            texture:   None,
+            names:     HashMap::new()
         }
     }
 
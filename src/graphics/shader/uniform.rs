@@ -0,0 +1,214 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Values which can be assigned to the parameters of a shader
+//!
+//! The Uniform trait converts a value to a UniformValue, which set_uniform
+//! assigns with the matching CSFML function.
+
+pub use self::UniformValue::{FloatUniform, Float2Uniform, Float3Uniform, Float4Uniform,
+                             ColorUniform, TransformUniform, TextureUniform,
+                             CurrentTextureUniform};
+
+use libc::c_char;
+use std::c_str::CString;
+use std::collections::HashMap;
+
+use traits::Wrappable;
+use graphics::{Texture, Color, Transform};
+use system::vector2::Vector2f;
+use system::vector3::Vector3f;
+
+use ffi::graphics::shader as ffi;
+
+/// Marker of the texture of the object being drawn
+///
+/// Assigned to a sampler2D parameter, it maps the parameter to the
+/// texture of the object being drawn, which cannot be known in advance.
+#[deriving(Clone, PartialEq, Eq, Show, Copy)]
+pub struct CurrentTexture;
+
+/// Value of a shader parameter
+#[deriving(Copy)]
+pub enum UniformValue<'s> {
+    /// A float (float GLSL type)
+    FloatUniform(f32),
+    /// A 2-components vector (vec2 GLSL type)
+    Float2Uniform(f32, f32),
+    /// A 3-components vector (vec3 GLSL type)
+    Float3Uniform(f32, f32, f32),
+    /// A 4-components vector (vec4 GLSL type)
+    Float4Uniform(f32, f32, f32, f32),
+    /// A color, normalized to a vec4 with components in [0 .. 1]
+    ColorUniform(Color),
+    /// A transform (mat4 GLSL type)
+    TransformUniform(Transform),
+    /// A texture (sampler2D GLSL type)
+    TextureUniform(&'s Texture),
+    /// The texture of the object being drawn (sampler2D GLSL type)
+    CurrentTextureUniform
+}
+
+/// Value which can be assigned to a shader parameter
+///
+/// Implemented by f32, Vector2f, Vector3f, Color, Transform, &Texture,
+/// CurrentTexture, and tuples and fixed size arrays of 1 to 4 f32, which
+/// are assigned as a float or a vector. Uniform arrays of GLSL are set
+/// with set_uniform_array.
+pub trait Uniform<'s> {
+    /// Convert the value to a UniformValue
+    ///
+    /// Return the value to assign to the parameter
+    fn to_uniform(&self) -> UniformValue<'s>;
+}
+
+impl<'s> Uniform<'s> for UniformValue<'s> {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        *self
+    }
+}
+
+impl<'s> Uniform<'s> for f32 {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        FloatUniform(*self)
+    }
+}
+
+impl<'s> Uniform<'s> for (f32, f32) {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        let (x, y) = *self;
+        Float2Uniform(x, y)
+    }
+}
+
+impl<'s> Uniform<'s> for (f32, f32, f32) {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        let (x, y, z) = *self;
+        Float3Uniform(x, y, z)
+    }
+}
+
+impl<'s> Uniform<'s> for (f32, f32, f32, f32) {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        let (x, y, z, w) = *self;
+        Float4Uniform(x, y, z, w)
+    }
+}
+
+impl<'s> Uniform<'s> for [f32, ..1] {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        FloatUniform(self[0])
+    }
+}
+
+impl<'s> Uniform<'s> for [f32, ..2] {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        Float2Uniform(self[0], self[1])
+    }
+}
+
+impl<'s> Uniform<'s> for [f32, ..3] {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        Float3Uniform(self[0], self[1], self[2])
+    }
+}
+
+impl<'s> Uniform<'s> for [f32, ..4] {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        Float4Uniform(self[0], self[1], self[2], self[3])
+    }
+}
+
+impl<'s> Uniform<'s> for Vector2f {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        Float2Uniform(self.x, self.y)
+    }
+}
+
+impl<'s> Uniform<'s> for Vector3f {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        Float3Uniform(self.x, self.y, self.z)
+    }
+}
+
+impl<'s> Uniform<'s> for Color {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        ColorUniform(*self)
+    }
+}
+
+impl<'s> Uniform<'s> for Transform {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        TransformUniform(*self)
+    }
+}
+
+impl<'s> Uniform<'s> for &'s Texture {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        TextureUniform(*self)
+    }
+}
+
+impl<'s> Uniform<'s> for CurrentTexture {
+    fn to_uniform(&self) -> UniformValue<'s> {
+        CurrentTextureUniform
+    }
+}
+
+// Get the C string of a parameter name, converted on the first lookup only
+#[doc(hidden)]
+pub fn get_c_name(names: &mut HashMap<String, CString>, name: &str) -> *const c_char {
+    if !names.contains_key(name) {
+        names.insert(name.to_string(), name.to_c_str());
+    }
+    names.get(name).unwrap().as_ptr()
+}
+
+// Get the name of an element of an array parameter
+#[doc(hidden)]
+pub fn get_element_name(name: &str, index: uint) -> String {
+    format!("{}[{}]", name, index)
+}
+
+// Assign a value to a parameter of a shader
+#[doc(hidden)]
+pub fn set_uniform_value(shader: *mut ffi::sfShader,
+                         name: *const c_char,
+                         value: &UniformValue) -> () {
+    unsafe {
+        match *value {
+            FloatUniform(x) => ffi::sfShader_setFloatParameter(shader, name, x),
+            Float2Uniform(x, y) => ffi::sfShader_setFloat2Parameter(shader, name, x, y),
+            Float3Uniform(x, y, z) => ffi::sfShader_setFloat3Parameter(shader, name, x, y, z),
+            Float4Uniform(x, y, z, w) =>
+                ffi::sfShader_setFloat4Parameter(shader, name, x, y, z, w),
+            ColorUniform(color) => ffi::sfShader_setColorParameter(shader, name, color),
+            TransformUniform(transform) =>
+                ffi::sfShader_setTransformParameter(shader, name, transform),
+            TextureUniform(texture) =>
+                ffi::sfShader_setTextureParameter(shader, name, texture.unwrap()),
+            CurrentTextureUniform => ffi::sfShader_setCurrentTextureParameter(shader, name)
+        }
+    }
+}
@@ -27,7 +27,7 @@
 use libc::{c_void, c_float, c_uint};
 use std::{ptr, mem};
 
-use traits::{Drawable, ShapeImpl, Wrappable};
+use traits::{Drawable, ShapeImpl, Wrappable, Bounded};
 use graphics::{RenderTarget, RenderStates, Texture, Color,
                Transform, IntRect, FloatRect};
 use system::vector2::Vector2f;
@@ -560,6 +560,18 @@ impl<'s> Shape<'s> {
     }
 }
 
+impl_transformable!(Shape<'s>, 's);
+
+impl<'s> Bounded for Shape<'s> {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
 impl<'s> Drawable for Shape<'s> {
     fn draw<RT:RenderTarget>(&self, render_target: &mut RT) {
         render_target.draw_shape(self)
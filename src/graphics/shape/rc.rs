@@ -29,7 +29,7 @@ use std::cell::RefCell;
 use libc::{c_void, c_float, c_uint};
 use std::{ptr, mem};
 
-use traits::{Drawable, ShapeImpl, Wrappable};
+use traits::{Drawable, ShapeImpl, Wrappable, Bounded};
 use graphics::{RenderTarget, rc, Texture, Color,
                Transform, IntRect, FloatRect};
 use system::vector2::Vector2f;
@@ -565,6 +565,18 @@ impl Shape {
     }
 }
 
+impl_transformable!(Shape);
+
+impl Bounded for Shape {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
 impl Drawable for Shape {
     fn draw<RT: RenderTarget>(&self, render_target: &mut RT) {
         render_target.draw_shape_rc(self)
@@ -0,0 +1,1233 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Ready-made shapes
+//!
+//! Every shape of this module computes its points from its geometry, which
+//! can be changed at any time. The styling API (texture, colors, outline,
+//! transformations) is available directly on the shape, like on
+//! RectangleShape and CircleShape.
+//!
+//! The convex shapes wrap a Shape, filled as a fan around its center. Stars
+//! and arcs are concave, so they wrap a PolygonShape, triangulated each time
+//! their geometry changes.
+
+use std::num::Float;
+use std::cmp::max;
+use std::f32::consts::PI;
+
+use traits::{Drawable, ShapeImpl, Bounded};
+use graphics::{Shape, PolygonShape, Texture, Color, IntRect, FloatRect, Transform,
+               RenderTarget, RenderStates};
+use system::vector2::Vector2f;
+
+// Geometry read by the callbacks of a Shape
+//
+// The geometry is boxed by the ready-made shape, so its address doesn't
+// change, and the Shape is declared before it, so it is destroyed first.
+#[doc(hidden)]
+struct GeometryRef<G> {
+    geometry: *const G
+}
+
+impl<G: ShapeImpl> ShapeImpl for GeometryRef<G> {
+    fn get_point_count(&self) -> u32 {
+        unsafe { (*self.geometry).get_point_count() }
+    }
+
+    fn get_point(&self, point: u32) -> Vector2f {
+        unsafe { (*self.geometry).get_point(point) }
+    }
+}
+
+// Create the Shape drawing a geometry
+#[doc(hidden)]
+fn create_shape<'s, G: ShapeImpl + Send + 'static>(geometry: &Box<G>,
+                                                   texture: Option<&'s Texture>)
+                                                   -> Option<Shape<'s>> {
+    let shape_impl = box GeometryRef { geometry: &**geometry as *const G };
+    match texture {
+        Some(texture) => Shape::new_with_texture(shape_impl, texture),
+        None => Shape::new(shape_impl)
+    }
+}
+
+// Geometry of a concave shape, drawn by a PolygonShape
+#[doc(hidden)]
+trait Outline {
+    // The points of the outline, in order
+    fn get_outline(&self) -> Vec<Vector2f>;
+
+    // The points of each hole
+    fn get_holes(&self) -> Vec<Vec<Vector2f>> {
+        Vec::new()
+    }
+}
+
+// Implement the constructor of a ready-made shape drawn by a Shape reading
+// its geometry
+macro_rules! ready_made_shape(
+    ($name:ident, $geometry:ident) => (
+        impl<'s> $name<'s> {
+            #[doc(hidden)]
+            fn from_geometry(geometry: $geometry,
+                             texture: Option<&'s Texture>) -> Option<$name<'s>> {
+                let geometry = box geometry;
+                match create_shape(&geometry, texture) {
+                    Some(shape) => Some($name {
+                        shape: shape,
+                        geometry: geometry
+                    }),
+                    None => None
+                }
+            }
+
+            #[doc(hidden)]
+            fn update_points(&mut self) -> () {
+                self.shape.update()
+            }
+        }
+
+        shape_methods!($name);
+    )
+)
+
+// Implement the constructor of a ready-made shape drawn by a PolygonShape
+// given the outline of its geometry
+macro_rules! triangulated_shape(
+    ($name:ident, $geometry:ident) => (
+        impl<'s> $name<'s> {
+            #[doc(hidden)]
+            fn from_geometry(geometry: $geometry,
+                             texture: Option<&'s Texture>) -> Option<$name<'s>> {
+                let shape = match texture {
+                    Some(texture) => PolygonShape::new_with_texture(texture, &[]),
+                    None => PolygonShape::new()
+                };
+                match shape {
+                    Some(shape) => {
+                        let mut shape = $name {
+                            shape: shape,
+                            geometry: geometry
+                        };
+                        shape.update_points();
+                        Some(shape)
+                    },
+                    None => None
+                }
+            }
+
+            #[doc(hidden)]
+            fn update_points(&mut self) -> () {
+                self.shape.set_points(self.geometry.get_outline().as_slice());
+                self.shape.clear_holes();
+                for hole in self.geometry.get_holes().iter() {
+                    self.shape.add_hole(hole.as_slice());
+                }
+            }
+        }
+
+        shape_methods!($name);
+    )
+)
+
+// Implement the styling and transformation methods forwarded to the wrapped
+// shape, and the traits of a ready-made shape
+macro_rules! shape_methods(
+    ($name:ident) => (
+        impl<'s> $name<'s> {
+            /// Set the position of the shape
+            ///
+            /// # Arguments
+            /// * position - The new position
+            pub fn set_position(&mut self, position: &Vector2f) -> () {
+                self.shape.set_position(position)
+            }
+
+            /// Set the position of the shape
+            ///
+            /// # Arguments
+            /// * x - The new x coordinate
+            /// * y - The new y coordinate
+            pub fn set_position2f(&mut self, x: f32, y: f32) -> () {
+                self.shape.set_position2f(x, y)
+            }
+
+            /// Set the orientation of the shape
+            ///
+            /// # Arguments
+            /// * angle - The new rotation, in degrees
+            pub fn set_rotation(&mut self, angle: f32) -> () {
+                self.shape.set_rotation(angle)
+            }
+
+            /// Set the scale factors of the shape
+            ///
+            /// # Arguments
+            /// * scale - The new scale factors
+            pub fn set_scale(&mut self, scale: &Vector2f) -> () {
+                self.shape.set_scale(scale)
+            }
+
+            /// Set the scale factors of the shape
+            ///
+            /// # Arguments
+            /// * scale_x - The new x scale factor
+            /// * scale_y - The new y scale factor
+            pub fn set_scale2f(&mut self, scale_x: f32, scale_y: f32) -> () {
+                self.shape.set_scale2f(scale_x, scale_y)
+            }
+
+            /// Set the local origin of the shape
+            ///
+            /// # Arguments
+            /// * origin - The new origin
+            pub fn set_origin(&mut self, origin: &Vector2f) -> () {
+                self.shape.set_origin(origin)
+            }
+
+            /// Set the local origin of the shape
+            ///
+            /// # Arguments
+            /// * x - The new x origin coordinate
+            /// * y - The new y origin coordinate
+            pub fn set_origin2f(&mut self, x: f32, y: f32) -> () {
+                self.shape.set_origin2f(x, y)
+            }
+
+            /// Get the position of the shape
+            ///
+            /// Return the current position
+            pub fn get_position(&self) -> Vector2f {
+                self.shape.get_position()
+            }
+
+            /// Get the orientation of the shape
+            ///
+            /// Return the current rotation, in degrees
+            pub fn get_rotation(&self) -> f32 {
+                self.shape.get_rotation()
+            }
+
+            /// Get the scale factors of the shape
+            ///
+            /// Return the current scale factors
+            pub fn get_scale(&self) -> Vector2f {
+                self.shape.get_scale()
+            }
+
+            /// Get the local origin of the shape
+            ///
+            /// Return the current origin
+            pub fn get_origin(&self) -> Vector2f {
+                self.shape.get_origin()
+            }
+
+            /// Move the shape by a given offset
+            ///
+            /// # Arguments
+            /// * offset - The offset
+            pub fn move_(&mut self, offset: &Vector2f) -> () {
+                self.shape.move_(offset)
+            }
+
+            /// Move the shape by a given offset
+            ///
+            /// # Arguments
+            /// * offset_x - The x offset
+            /// * offset_y - The y offset
+            pub fn move2f(&mut self, offset_x: f32, offset_y: f32) -> () {
+                self.shape.move2f(offset_x, offset_y)
+            }
+
+            /// Rotate the shape
+            ///
+            /// # Arguments
+            /// * angle - The angle of rotation, in degrees
+            pub fn rotate(&mut self, angle: f32) -> () {
+                self.shape.rotate(angle)
+            }
+
+            /// Scale the shape
+            ///
+            /// # Arguments
+            /// * factors - The scale factors
+            pub fn scale(&mut self, factors: &Vector2f) -> () {
+                self.shape.scale(factors)
+            }
+
+            /// Scale the shape
+            ///
+            /// # Arguments
+            /// * factor_x - The x scale factor
+            /// * factor_y - The y scale factor
+            pub fn scale2f(&mut self, factor_x: f32, factor_y: f32) -> () {
+                self.shape.scale2f(factor_x, factor_y)
+            }
+
+            /// Get the combined transform of the shape
+            ///
+            /// Return the transform combining the position/rotation/scale/origin of the shape
+            pub fn get_transform(&self) -> Transform {
+                self.shape.get_transform()
+            }
+
+            /// Get the inverse of the combined transform of the shape
+            ///
+            /// Return the inverse of the combined transformations applied to the shape
+            pub fn get_inverse_transform(&self) -> Transform {
+                self.shape.get_inverse_transform()
+            }
+
+            /// Change the source texture of the shape
+            ///
+            /// # Arguments
+            /// * texture - The new texture
+            /// * reset_rect - Should the texture rect be reset to the size of the new texture?
+            pub fn set_texture(&mut self, texture: &'s Texture, reset_rect: bool) -> () {
+                self.shape.set_texture(texture, reset_rect)
+            }
+
+            /// Disable the texturing of the shape
+            pub fn disable_texture(&mut self) -> () {
+                self.shape.disable_texture()
+            }
+
+            /// Get the source texture of the shape
+            ///
+            /// Return Some(texture), or None if the shape has no texture
+            pub fn get_texture(&self) -> Option<&'s Texture> {
+                self.shape.get_texture()
+            }
+
+            /// Set the sub-rectangle of the texture that the shape will display
+            ///
+            /// # Arguments
+            /// * rect - The rectangle defining the region of the texture to display
+            pub fn set_texture_rect(&mut self, rect: &IntRect) -> () {
+                self.shape.set_texture_rect(rect)
+            }
+
+            /// Get the sub-rectangle of the texture displayed by the shape
+            ///
+            /// Return the texture rectangle of the shape
+            pub fn get_texture_rect(&self) -> IntRect {
+                self.shape.get_texture_rect()
+            }
+
+            /// Set the fill color of the shape
+            ///
+            /// # Arguments
+            /// * color - The new color of the shape
+            pub fn set_fill_color(&mut self, color: &Color) -> () {
+                self.shape.set_fill_color(color)
+            }
+
+            /// Get the fill color of the shape
+            ///
+            /// Return the fill color of the shape
+            pub fn get_fill_color(&self) -> Color {
+                self.shape.get_fill_color()
+            }
+
+            /// Set the outline color of the shape
+            ///
+            /// # Arguments
+            /// * color - The new outline color of the shape
+            pub fn set_outline_color(&mut self, color: &Color) -> () {
+                self.shape.set_outline_color(color)
+            }
+
+            /// Get the outline color of the shape
+            ///
+            /// Return the outline color of the shape
+            pub fn get_outline_color(&self) -> Color {
+                self.shape.get_outline_color()
+            }
+
+            /// Set the thickness of the outline of the shape
+            ///
+            /// # Arguments
+            /// * thickness - The new outline thickness
+            pub fn set_outline_thickness(&mut self, thickness: f32) -> () {
+                self.shape.set_outline_thickness(thickness)
+            }
+
+            /// Get the thickness of the outline of the shape
+            ///
+            /// Return the outline thickness of the shape
+            pub fn get_outline_thickness(&self) -> f32 {
+                self.shape.get_outline_thickness()
+            }
+
+            /// Get the total number of points of the shape
+            ///
+            /// Return the number of points of the shape
+            pub fn get_point_count(&self) -> uint {
+                self.shape.get_point_count()
+            }
+
+            /// Get a point of the shape
+            ///
+            /// # Arguments
+            /// * index - The index of the point to get, in range [0 .. get_point_count() - 1]
+            ///
+            /// Return the index-th point of the shape, in local coordinates
+            pub fn get_point(&self, index: uint) -> Vector2f {
+                self.shape.get_point(index)
+            }
+
+            /// Get the local bounding rectangle of the shape
+            ///
+            /// Return the local bounding rectangle of the entity
+            pub fn get_local_bounds(&self) -> FloatRect {
+                self.shape.get_local_bounds()
+            }
+
+            /// Get the global bounding rectangle of the shape
+            ///
+            /// Return the global bounding rectangle of the entity
+            pub fn get_global_bounds(&self) -> FloatRect {
+                self.shape.get_global_bounds()
+            }
+        }
+
+        impl_transformable!($name<'s>, 's);
+
+        impl<'s> Bounded for $name<'s> {
+            fn get_local_bounds(&self) -> FloatRect {
+                self.get_local_bounds()
+            }
+
+            fn get_global_bounds(&self) -> FloatRect {
+                self.get_global_bounds()
+            }
+        }
+
+        impl<'s> Drawable for $name<'s> {
+            fn draw<RT: RenderTarget>(&self, render_target: &mut RT) -> () {
+                self.shape.draw(render_target)
+            }
+
+            fn draw_rs<RT: RenderTarget>(&self,
+                                         render_target: &mut RT,
+                                         render_states: &mut RenderStates) -> () {
+                self.shape.draw_rs(render_target, render_states)
+            }
+        }
+    )
+)
+
+/// Rectangle with rounded corners
+pub struct RoundedRectangleShape<'s> {
+    #[doc(hidden)]
+    shape: Shape<'s>,
+    #[doc(hidden)]
+    geometry: Box<RoundedRectangle>
+}
+
+impl<'s> RoundedRectangleShape<'s> {
+    /// Create a new rounded rectangle
+    ///
+    /// # Arguments
+    /// * size - The size of the rectangle
+    /// * radius - The radius of the corners
+    /// * corner_point_count - The number of points of each corner
+    ///
+    /// Return Some(RoundedRectangleShape) or None
+    pub fn new(size: &Vector2f,
+               radius: f32,
+               corner_point_count: u32) -> Option<RoundedRectangleShape<'s>> {
+        RoundedRectangleShape::from_geometry(RoundedRectangle {
+            size: *size,
+            radius: radius,
+            corner_point_count: corner_point_count
+        }, None)
+    }
+
+    /// Create a new textured rounded rectangle
+    ///
+    /// # Arguments
+    /// * size - The size of the rectangle
+    /// * radius - The radius of the corners
+    /// * corner_point_count - The number of points of each corner
+    /// * texture - The texture to bind to the shape
+    ///
+    /// Return Some(RoundedRectangleShape) or None
+    pub fn new_with_texture(size: &Vector2f,
+                            radius: f32,
+                            corner_point_count: u32,
+                            texture: &'s Texture) -> Option<RoundedRectangleShape<'s>> {
+        RoundedRectangleShape::from_geometry(RoundedRectangle {
+            size: *size,
+            radius: radius,
+            corner_point_count: corner_point_count
+        }, Some(texture))
+    }
+
+    /// Set the size of the rectangle
+    ///
+    /// # Arguments
+    /// * size - The new size of the rectangle
+    pub fn set_size(&mut self, size: &Vector2f) -> () {
+        self.geometry.size = *size;
+        self.update_points()
+    }
+
+    /// Get the size of the rectangle
+    ///
+    /// Return the size of the rectangle
+    pub fn get_size(&self) -> Vector2f {
+        self.geometry.size
+    }
+
+    /// Set the radius of the corners
+    ///
+    /// The radius is clamped to half the smallest side of the rectangle.
+    ///
+    /// # Arguments
+    /// * radius - The new radius of the corners
+    pub fn set_radius(&mut self, radius: f32) -> () {
+        self.geometry.radius = radius;
+        self.update_points()
+    }
+
+    /// Get the radius of the corners
+    ///
+    /// Return the radius of the corners
+    pub fn get_radius(&self) -> f32 {
+        self.geometry.radius
+    }
+
+    /// Set the number of points of each corner
+    ///
+    /// # Arguments
+    /// * count - The new number of points of each corner
+    pub fn set_corner_point_count(&mut self, count: u32) -> () {
+        self.geometry.corner_point_count = count;
+        self.update_points()
+    }
+
+    /// Get the number of points of each corner
+    ///
+    /// Return the number of points of each corner
+    pub fn get_corner_point_count(&self) -> u32 {
+        self.geometry.corner_point_count
+    }
+}
+
+ready_made_shape!(RoundedRectangleShape, RoundedRectangle);
+
+/// Ellipse, defined by its two radii
+pub struct EllipseShape<'s> {
+    #[doc(hidden)]
+    shape: Shape<'s>,
+    #[doc(hidden)]
+    geometry: Box<Ellipse>
+}
+
+impl<'s> EllipseShape<'s> {
+    /// Create a new ellipse
+    ///
+    /// # Arguments
+    /// * radius - The horizontal and vertical radii of the ellipse
+    /// * point_count - The number of points of the ellipse
+    ///
+    /// Return Some(EllipseShape) or None
+    pub fn new(radius: &Vector2f, point_count: uint) -> Option<EllipseShape<'s>> {
+        EllipseShape::from_geometry(Ellipse {
+            radius: *radius,
+            point_count: point_count as u32
+        }, None)
+    }
+
+    /// Create a new textured ellipse
+    ///
+    /// # Arguments
+    /// * radius - The horizontal and vertical radii of the ellipse
+    /// * point_count - The number of points of the ellipse
+    /// * texture - The texture to bind to the shape
+    ///
+    /// Return Some(EllipseShape) or None
+    pub fn new_with_texture(radius: &Vector2f,
+                            point_count: uint,
+                            texture: &'s Texture) -> Option<EllipseShape<'s>> {
+        EllipseShape::from_geometry(Ellipse {
+            radius: *radius,
+            point_count: point_count as u32
+        }, Some(texture))
+    }
+
+    /// Set the radii of the ellipse
+    ///
+    /// # Arguments
+    /// * radius - The new horizontal and vertical radii
+    pub fn set_radius(&mut self, radius: &Vector2f) -> () {
+        self.geometry.radius = *radius;
+        self.update_points()
+    }
+
+    /// Get the radii of the ellipse
+    ///
+    /// Return the horizontal and vertical radii
+    pub fn get_radius(&self) -> Vector2f {
+        self.geometry.radius
+    }
+
+    /// Set the number of points of the ellipse
+    ///
+    /// # Arguments
+    /// * count - The new number of points of the ellipse, at least 3
+    pub fn set_point_count(&mut self, count: uint) -> () {
+        self.geometry.point_count = count as u32;
+        self.update_points()
+    }
+}
+
+ready_made_shape!(EllipseShape, Ellipse);
+
+/// Regular polygon inscribed in a circle
+pub struct RegularPolygonShape<'s> {
+    #[doc(hidden)]
+    shape: Shape<'s>,
+    #[doc(hidden)]
+    geometry: Box<RegularPolygon>
+}
+
+impl<'s> RegularPolygonShape<'s> {
+    /// Create a new regular polygon
+    ///
+    /// The first point of the polygon is at the top.
+    ///
+    /// # Arguments
+    /// * radius - The radius of the circumscribed circle
+    /// * side_count - The number of sides of the polygon
+    ///
+    /// Return Some(RegularPolygonShape) or None
+    pub fn new(radius: f32, side_count: u32) -> Option<RegularPolygonShape<'s>> {
+        RegularPolygonShape::from_geometry(RegularPolygon {
+            radius: radius,
+            side_count: side_count
+        }, None)
+    }
+
+    /// Create a new textured regular polygon
+    ///
+    /// # Arguments
+    /// * radius - The radius of the circumscribed circle
+    /// * side_count - The number of sides of the polygon
+    /// * texture - The texture to bind to the shape
+    ///
+    /// Return Some(RegularPolygonShape) or None
+    pub fn new_with_texture(radius: f32,
+                            side_count: u32,
+                            texture: &'s Texture) -> Option<RegularPolygonShape<'s>> {
+        RegularPolygonShape::from_geometry(RegularPolygon {
+            radius: radius,
+            side_count: side_count
+        }, Some(texture))
+    }
+
+    /// Set the radius of the circumscribed circle
+    ///
+    /// # Arguments
+    /// * radius - The new radius
+    pub fn set_radius(&mut self, radius: f32) -> () {
+        self.geometry.radius = radius;
+        self.update_points()
+    }
+
+    /// Get the radius of the circumscribed circle
+    ///
+    /// Return the radius
+    pub fn get_radius(&self) -> f32 {
+        self.geometry.radius
+    }
+
+    /// Set the number of sides of the polygon
+    ///
+    /// # Arguments
+    /// * count - The new number of sides, at least 3
+    pub fn set_side_count(&mut self, count: u32) -> () {
+        self.geometry.side_count = count;
+        self.update_points()
+    }
+
+    /// Get the number of sides of the polygon
+    ///
+    /// Return the number of sides
+    pub fn get_side_count(&self) -> u32 {
+        self.geometry.get_side_count()
+    }
+}
+
+ready_made_shape!(RegularPolygonShape, RegularPolygon);
+
+/// Star with a given number of branches
+pub struct StarShape<'s> {
+    #[doc(hidden)]
+    shape: PolygonShape<'s>,
+    #[doc(hidden)]
+    geometry: Star
+}
+
+impl<'s> StarShape<'s> {
+    /// Create a new star
+    ///
+    /// The first branch of the star points to the top.
+    ///
+    /// # Arguments
+    /// * inner_radius - The distance from the center to the hollows
+    /// * outer_radius - The distance from the center to the tips
+    /// * branch_count - The number of branches of the star
+    ///
+    /// Return Some(StarShape) or None
+    pub fn new(inner_radius: f32,
+               outer_radius: f32,
+               branch_count: u32) -> Option<StarShape<'s>> {
+        StarShape::from_geometry(Star {
+            inner_radius: inner_radius,
+            outer_radius: outer_radius,
+            branch_count: branch_count
+        }, None)
+    }
+
+    /// Create a new textured star
+    ///
+    /// # Arguments
+    /// * inner_radius - The distance from the center to the hollows
+    /// * outer_radius - The distance from the center to the tips
+    /// * branch_count - The number of branches of the star
+    /// * texture - The texture to bind to the shape
+    ///
+    /// Return Some(StarShape) or None
+    pub fn new_with_texture(inner_radius: f32,
+                            outer_radius: f32,
+                            branch_count: u32,
+                            texture: &'s Texture) -> Option<StarShape<'s>> {
+        StarShape::from_geometry(Star {
+            inner_radius: inner_radius,
+            outer_radius: outer_radius,
+            branch_count: branch_count
+        }, Some(texture))
+    }
+
+    /// Set the distance from the center to the hollows
+    ///
+    /// # Arguments
+    /// * radius - The new inner radius
+    pub fn set_inner_radius(&mut self, radius: f32) -> () {
+        self.geometry.inner_radius = radius;
+        self.update_points()
+    }
+
+    /// Get the distance from the center to the hollows
+    ///
+    /// Return the inner radius
+    pub fn get_inner_radius(&self) -> f32 {
+        self.geometry.inner_radius
+    }
+
+    /// Set the distance from the center to the tips
+    ///
+    /// # Arguments
+    /// * radius - The new outer radius
+    pub fn set_outer_radius(&mut self, radius: f32) -> () {
+        self.geometry.outer_radius = radius;
+        self.update_points()
+    }
+
+    /// Get the distance from the center to the tips
+    ///
+    /// Return the outer radius
+    pub fn get_outer_radius(&self) -> f32 {
+        self.geometry.outer_radius
+    }
+
+    /// Set the number of branches of the star
+    ///
+    /// # Arguments
+    /// * count - The new number of branches, at least 2
+    pub fn set_branch_count(&mut self, count: u32) -> () {
+        self.geometry.branch_count = count;
+        self.update_points()
+    }
+
+    /// Get the number of branches of the star
+    ///
+    /// Return the number of branches
+    pub fn get_branch_count(&self) -> u32 {
+        self.geometry.get_branch_count()
+    }
+}
+
+triangulated_shape!(StarShape, Star);
+
+/// Pie or ring segment
+///
+/// With an inner radius of 0 the arc is a pie slice, otherwise it is a
+/// segment of a ring. Angles are in degrees, clockwise from the right. A
+/// sweep of 360 degrees or more draws a full disc or ring.
+pub struct ArcShape<'s> {
+    #[doc(hidden)]
+    shape: PolygonShape<'s>,
+    #[doc(hidden)]
+    geometry: Arc
+}
+
+impl<'s> ArcShape<'s> {
+    /// Create a new arc
+    ///
+    /// The center of the arc is at (radius, radius) in local coordinates.
+    ///
+    /// # Arguments
+    /// * radius - The outer radius of the arc
+    /// * inner_radius - The inner radius of the arc, 0 for a pie slice
+    /// * start_angle - The angle where the arc starts, in degrees
+    /// * sweep_angle - The angle covered by the arc, in degrees
+    /// * point_count - The number of points of each curved side
+    ///
+    /// Return Some(ArcShape) or None
+    pub fn new(radius: f32,
+               inner_radius: f32,
+               start_angle: f32,
+               sweep_angle: f32,
+               point_count: u32) -> Option<ArcShape<'s>> {
+        ArcShape::from_geometry(Arc {
+            radius: radius,
+            inner_radius: inner_radius,
+            start_angle: start_angle,
+            sweep_angle: sweep_angle,
+            point_count: point_count
+        }, None)
+    }
+
+    /// Create a new textured arc
+    ///
+    /// # Arguments
+    /// * radius - The outer radius of the arc
+    /// * inner_radius - The inner radius of the arc, 0 for a pie slice
+    /// * start_angle - The angle where the arc starts, in degrees
+    /// * sweep_angle - The angle covered by the arc, in degrees
+    /// * point_count - The number of points of each curved side
+    /// * texture - The texture to bind to the shape
+    ///
+    /// Return Some(ArcShape) or None
+    pub fn new_with_texture(radius: f32,
+                            inner_radius: f32,
+                            start_angle: f32,
+                            sweep_angle: f32,
+                            point_count: u32,
+                            texture: &'s Texture) -> Option<ArcShape<'s>> {
+        ArcShape::from_geometry(Arc {
+            radius: radius,
+            inner_radius: inner_radius,
+            start_angle: start_angle,
+            sweep_angle: sweep_angle,
+            point_count: point_count
+        }, Some(texture))
+    }
+
+    /// Set the outer radius of the arc
+    ///
+    /// # Arguments
+    /// * radius - The new outer radius
+    pub fn set_radius(&mut self, radius: f32) -> () {
+        self.geometry.radius = radius;
+        self.update_points()
+    }
+
+    /// Get the outer radius of the arc
+    ///
+    /// Return the outer radius
+    pub fn get_radius(&self) -> f32 {
+        self.geometry.radius
+    }
+
+    /// Set the inner radius of the arc
+    ///
+    /// # Arguments
+    /// * radius - The new inner radius, 0 for a pie slice
+    pub fn set_inner_radius(&mut self, radius: f32) -> () {
+        self.geometry.inner_radius = radius;
+        self.update_points()
+    }
+
+    /// Get the inner radius of the arc
+    ///
+    /// Return the inner radius
+    pub fn get_inner_radius(&self) -> f32 {
+        self.geometry.inner_radius
+    }
+
+    /// Set the angle where the arc starts
+    ///
+    /// # Arguments
+    /// * angle - The new start angle, in degrees
+    pub fn set_start_angle(&mut self, angle: f32) -> () {
+        self.geometry.start_angle = angle;
+        self.update_points()
+    }
+
+    /// Get the angle where the arc starts
+    ///
+    /// Return the start angle, in degrees
+    pub fn get_start_angle(&self) -> f32 {
+        self.geometry.start_angle
+    }
+
+    /// Set the angle covered by the arc
+    ///
+    /// # Arguments
+    /// * angle - The new sweep angle, in degrees, clamped to a full turn
+    pub fn set_sweep_angle(&mut self, angle: f32) -> () {
+        self.geometry.sweep_angle = angle;
+        self.update_points()
+    }
+
+    /// Get the angle covered by the arc
+    ///
+    /// Return the sweep angle, in degrees
+    pub fn get_sweep_angle(&self) -> f32 {
+        self.geometry.sweep_angle
+    }
+
+    /// Set the number of points of each curved side
+    ///
+    /// # Arguments
+    /// * count - The new number of points, at least 2
+    pub fn set_arc_point_count(&mut self, count: u32) -> () {
+        self.geometry.point_count = count;
+        self.update_points()
+    }
+
+    /// Get the number of points of each curved side
+    ///
+    /// Return the number of points
+    pub fn get_arc_point_count(&self) -> u32 {
+        self.geometry.get_arc_point_count()
+    }
+}
+
+triangulated_shape!(ArcShape, Arc);
+
+/// Rectangle with fully rounded ends
+///
+/// The ends are half circles on the smallest sides of the rectangle.
+pub struct CapsuleShape<'s> {
+    #[doc(hidden)]
+    shape: Shape<'s>,
+    #[doc(hidden)]
+    geometry: Box<Capsule>
+}
+
+impl<'s> CapsuleShape<'s> {
+    /// Create a new capsule
+    ///
+    /// # Arguments
+    /// * size - The size of the rectangle enclosing the capsule
+    /// * cap_point_count - The number of points of each end
+    ///
+    /// Return Some(CapsuleShape) or None
+    pub fn new(size: &Vector2f, cap_point_count: u32) -> Option<CapsuleShape<'s>> {
+        CapsuleShape::from_geometry(Capsule {
+            size: *size,
+            cap_point_count: cap_point_count
+        }, None)
+    }
+
+    /// Create a new textured capsule
+    ///
+    /// # Arguments
+    /// * size - The size of the rectangle enclosing the capsule
+    /// * cap_point_count - The number of points of each end
+    /// * texture - The texture to bind to the shape
+    ///
+    /// Return Some(CapsuleShape) or None
+    pub fn new_with_texture(size: &Vector2f,
+                            cap_point_count: u32,
+                            texture: &'s Texture) -> Option<CapsuleShape<'s>> {
+        CapsuleShape::from_geometry(Capsule {
+            size: *size,
+            cap_point_count: cap_point_count
+        }, Some(texture))
+    }
+
+    /// Set the size of the capsule
+    ///
+    /// # Arguments
+    /// * size - The new size of the rectangle enclosing the capsule
+    pub fn set_size(&mut self, size: &Vector2f) -> () {
+        self.geometry.size = *size;
+        self.update_points()
+    }
+
+    /// Get the size of the capsule
+    ///
+    /// Return the size of the rectangle enclosing the capsule
+    pub fn get_size(&self) -> Vector2f {
+        self.geometry.size
+    }
+
+    /// Set the number of points of each end
+    ///
+    /// # Arguments
+    /// * count - The new number of points of each end, at least 2
+    pub fn set_cap_point_count(&mut self, count: u32) -> () {
+        self.geometry.cap_point_count = count;
+        self.update_points()
+    }
+
+    /// Get the number of points of each end
+    ///
+    /// Return the number of points of each end
+    pub fn get_cap_point_count(&self) -> u32 {
+        self.geometry.get_cap_point_count()
+    }
+}
+
+ready_made_shape!(CapsuleShape, Capsule);
+
+// Geometry of a RoundedRectangleShape
+#[doc(hidden)]
+#[deriving(Clone, Copy)]
+struct RoundedRectangle {
+    size: Vector2f,
+    radius: f32,
+    corner_point_count: u32
+}
+
+impl ShapeImpl for RoundedRectangle {
+    fn get_point_count(&self) -> u32 {
+        4 * max(self.corner_point_count, 1)
+    }
+
+    fn get_point(&self, point: u32) -> Vector2f {
+        let per_corner = max(self.corner_point_count, 1);
+        let radius = self.radius.max(0.).min(self.size.x.abs().min(self.size.y.abs()) / 2.);
+        let corner = (point / per_corner) % 4;
+        let center = match corner {
+            0 => Vector2f::new(self.size.x - radius, radius),
+            1 => Vector2f::new(self.size.x - radius, self.size.y - radius),
+            2 => Vector2f::new(radius, self.size.y - radius),
+            _ => Vector2f::new(radius, radius)
+        };
+        let start = (corner as f32 - 1.) * PI / 2.;
+        corner_point(center, radius, start, point % per_corner, per_corner)
+    }
+}
+
+// Geometry of an EllipseShape
+#[doc(hidden)]
+#[deriving(Clone, Copy)]
+struct Ellipse {
+    radius: Vector2f,
+    point_count: u32
+}
+
+impl ShapeImpl for Ellipse {
+    fn get_point_count(&self) -> u32 {
+        max(self.point_count, 3)
+    }
+
+    fn get_point(&self, point: u32) -> Vector2f {
+        let angle = point as f32 * 2. * PI / self.get_point_count() as f32 - PI / 2.;
+        Vector2f::new(self.radius.x + angle.cos() * self.radius.x,
+                      self.radius.y + angle.sin() * self.radius.y)
+    }
+}
+
+// Geometry of a RegularPolygonShape
+#[doc(hidden)]
+#[deriving(Clone, Copy)]
+struct RegularPolygon {
+    radius: f32,
+    side_count: u32
+}
+
+impl RegularPolygon {
+    fn get_side_count(&self) -> u32 {
+        max(self.side_count, 3)
+    }
+}
+
+impl ShapeImpl for RegularPolygon {
+    fn get_point_count(&self) -> u32 {
+        self.get_side_count()
+    }
+
+    fn get_point(&self, point: u32) -> Vector2f {
+        let angle = point as f32 * 2. * PI / self.get_side_count() as f32 - PI / 2.;
+        Vector2f::new(self.radius + angle.cos() * self.radius,
+                      self.radius + angle.sin() * self.radius)
+    }
+}
+
+// Geometry of a StarShape
+#[doc(hidden)]
+#[deriving(Clone, Copy)]
+struct Star {
+    inner_radius: f32,
+    outer_radius: f32,
+    branch_count: u32
+}
+
+impl Star {
+    fn get_branch_count(&self) -> u32 {
+        max(self.branch_count, 2)
+    }
+}
+
+impl Outline for Star {
+    fn get_outline(&self) -> Vec<Vector2f> {
+        let count = self.get_branch_count();
+        range(0, 2 * count).map(|point| {
+            let angle = point as f32 * PI / count as f32 - PI / 2.;
+            let radius = if point % 2 == 0 { self.outer_radius } else { self.inner_radius };
+            Vector2f::new(self.outer_radius + angle.cos() * radius,
+                          self.outer_radius + angle.sin() * radius)
+        }).collect()
+    }
+}
+
+// Geometry of an ArcShape
+#[doc(hidden)]
+#[deriving(Clone, Copy)]
+struct Arc {
+    radius: f32,
+    inner_radius: f32,
+    start_angle: f32,
+    sweep_angle: f32,
+    point_count: u32
+}
+
+impl Arc {
+    fn get_arc_point_count(&self) -> u32 {
+        max(self.point_count, 2)
+    }
+
+    fn is_full(&self) -> bool {
+        self.sweep_angle.abs() >= 360.
+    }
+
+    // Points of a curved side, from the start to the end of the arc
+    fn get_side(&self, radius: f32) -> Vec<Vector2f> {
+        let count = self.get_arc_point_count();
+        let center = Vector2f::new(self.radius, self.radius);
+        let start = self.start_angle * PI / 180.;
+        let sweep = self.sweep_angle.max(-360.).min(360.) * PI / 180.;
+        // A full turn doesn't repeat its first point
+        let step = if self.is_full() {
+            sweep / count as f32
+        } else {
+            sweep / (count - 1) as f32
+        };
+        range(0, count).map(|point| {
+            let angle = start + step * point as f32;
+            center + Vector2f::new(angle.cos(), angle.sin()) * radius
+        }).collect()
+    }
+}
+
+impl Outline for Arc {
+    fn get_outline(&self) -> Vec<Vector2f> {
+        let mut points = self.get_side(self.radius);
+        if self.is_full() {
+            return points;
+        }
+        if self.inner_radius > 0. {
+            // The inner side goes back to the start of the arc
+            let inner = self.get_side(self.inner_radius);
+            points.extend(inner.into_iter().rev());
+        } else {
+            points.push(Vector2f::new(self.radius, self.radius));
+        }
+        points
+    }
+
+    fn get_holes(&self) -> Vec<Vec<Vector2f>> {
+        if self.is_full() && self.inner_radius > 0. {
+            vec![self.get_side(self.inner_radius)]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+// Geometry of a CapsuleShape
+#[doc(hidden)]
+#[deriving(Clone, Copy)]
+struct Capsule {
+    size: Vector2f,
+    cap_point_count: u32
+}
+
+impl Capsule {
+    fn get_cap_point_count(&self) -> u32 {
+        max(self.cap_point_count, 2)
+    }
+}
+
+impl ShapeImpl for Capsule {
+    fn get_point_count(&self) -> u32 {
+        2 * self.get_cap_point_count()
+    }
+
+    fn get_point(&self, point: u32) -> Vector2f {
+        let count = self.get_cap_point_count();
+        let (width, height) = (self.size.x.abs(), self.size.y.abs());
+        let radius = width.min(height) / 2.;
+        let second = point >= count;
+        let (center, start) = if width >= height {
+            if second {
+                (Vector2f::new(radius, radius), PI / 2.)
+            } else {
+                (Vector2f::new(width - radius, radius), -PI / 2.)
+            }
+        } else {
+            if second {
+                (Vector2f::new(radius, radius), PI)
+            } else {
+                (Vector2f::new(radius, height - radius), 0.)
+            }
+        };
+        let angle = start + PI * (point % count) as f32 / (count - 1) as f32;
+        center + Vector2f::new(angle.cos(), angle.sin()) * radius
+    }
+}
+
+// Point of a quarter circle corner, starting at the given angle
+#[doc(hidden)]
+fn corner_point(center: Vector2f,
+                radius: f32,
+                start: f32,
+                index: u32,
+                count: u32) -> Vector2f {
+    let angle = if count > 1 {
+        start + PI / 2. * index as f32 / (count - 1) as f32
+    } else {
+        start + PI / 4.
+    };
+    center + Vector2f::new(angle.cos(), angle.sin()) * radius
+}
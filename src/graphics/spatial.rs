@@ -0,0 +1,799 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+#![allow(missing_copy_implementations)]
+
+//! Spatial indexes over axis aligned bounding rectangles
+//!
+//! Both indexes store items (ids, handles, ...) with their bounds, usually
+//! the global bounds of a sprite or a shape, and find the items close to a
+//! rectangle, a point, a circle or a ray. They are meant for the broad phase
+//! of collision detection and for culling against the bounds of a View.
+//!
+//! Use a QuadTree when the sizes of the items vary a lot, and a SpatialHash
+//! when they are small and about the same size. Sprites, texts and shapes
+//! can be added with their global bounds by insert_bounded.
+
+use std::num::Float;
+use std::f32;
+use std::i32;
+use std::hash::Hash;
+use std::collections::{HashMap, HashSet};
+use std::cmp::Ordering;
+
+use traits::Bounded;
+use graphics::FloatRect;
+use graphics::geometry;
+use system::vector2::Vector2f;
+
+use self::Query::{RectQuery, PointQuery, CircleQuery, RayQuery};
+
+// Number of cells above which an item of a SpatialHash is not stored in the
+// cells, but tested by every query
+const MAX_ITEM_CELLS: f32 = 1024.;
+
+// Loose quadtree node, whose items may overflow its cell up to half its size
+#[doc(hidden)]
+struct Node<T> {
+    cell: FloatRect,
+    items: Vec<T>,
+    children: Option<uint>
+}
+
+// Region searched by a query
+#[doc(hidden)]
+enum Query {
+    RectQuery(FloatRect),
+    PointQuery(Vector2f),
+    CircleQuery(Vector2f, f32),
+    RayQuery(Vector2f, Vector2f, f32)
+}
+
+impl Query {
+    // Distance along the ray if the bounds match, 0 for the other queries
+    #[doc(hidden)]
+    fn test(&self, bounds: &FloatRect) -> Option<f32> {
+        let found = match *self {
+            RectQuery(ref rect) => rects_intersect(bounds, rect),
+            PointQuery(ref point) => rect_contains(bounds, point),
+            CircleQuery(ref center, radius) => rect_circle_intersect(bounds, center, radius),
+            RayQuery(ref origin, ref direction, max_distance) => {
+                return ray_rect(bounds, origin, direction, max_distance);
+            }
+        };
+        if found { Some(0.) } else { None }
+    }
+}
+
+/// Loose quadtree
+///
+/// Every item is stored in a single node, chosen from the size and the
+/// center of its bounds, so updating an item is cheap. Items outside of
+/// the bounds of the tree are kept in the root node.
+pub struct QuadTree<T> {
+    #[doc(hidden)]
+    nodes: Vec<Node<T>>,
+    #[doc(hidden)]
+    entries: HashMap<T, (FloatRect, uint)>,
+    #[doc(hidden)]
+    max_depth: uint
+}
+
+impl<T: Clone + Eq + Hash> QuadTree<T> {
+    /// Create a new empty quadtree
+    ///
+    /// # Arguments
+    /// * bounds - The area covered by the tree
+    /// * max_depth - The maximum depth of the nodes, the root is at depth 0
+    ///
+    /// Return a new QuadTree
+    pub fn new(bounds: &FloatRect, max_depth: uint) -> QuadTree<T> {
+        QuadTree {
+            nodes: vec![Node { cell: *bounds, items: Vec::new(), children: None }],
+            entries: HashMap::new(),
+            max_depth: max_depth
+        }
+    }
+
+    /// Get the area covered by the tree
+    ///
+    /// Return the bounds given at the creation of the tree
+    pub fn get_bounds(&self) -> FloatRect {
+        self.nodes[0].cell
+    }
+
+    /// Get the number of items in the tree
+    ///
+    /// Return the number of items
+    pub fn len(&self) -> uint {
+        self.entries.len()
+    }
+
+    /// Tell if the tree is empty
+    ///
+    /// Return true if the tree has no item
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all the items of the tree
+    pub fn clear(&mut self) -> () {
+        let bounds = self.get_bounds();
+        self.nodes.clear();
+        self.nodes.push(Node { cell: bounds, items: Vec::new(), children: None });
+        self.entries.clear()
+    }
+
+    /// Add an item to the tree
+    ///
+    /// If the item is already in the tree, its bounds are updated.
+    ///
+    /// # Arguments
+    /// * item - The item to add
+    /// * bounds - The bounds of the item
+    pub fn insert(&mut self, item: T, bounds: &FloatRect) -> () {
+        self.remove(&item);
+        let node = self.find_node(bounds);
+        self.nodes[node].items.push(item.clone());
+        self.entries.insert(item, (*bounds, node));
+    }
+
+    /// Remove an item from the tree
+    ///
+    /// # Arguments
+    /// * item - The item to remove
+    ///
+    /// Return true if the item was in the tree
+    pub fn remove(&mut self, item: &T) -> bool {
+        match self.entries.remove(item) {
+            Some((_, node)) => {
+                let items = &mut self.nodes[node].items;
+                match items.iter().position(|other| other == item) {
+                    Some(index) => { items.swap_remove(index); },
+                    None => {}
+                }
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Change the bounds of an item of the tree
+    ///
+    /// # Arguments
+    /// * item - The item to update
+    /// * bounds - The new bounds of the item
+    ///
+    /// Return false if the item is not in the tree
+    pub fn update(&mut self, item: &T, bounds: &FloatRect) -> bool {
+        if !self.entries.contains_key(item) {
+            return false;
+        }
+        self.insert(item.clone(), bounds);
+        true
+    }
+
+    /// Add an item to the tree with the global bounds of an object
+    ///
+    /// # Arguments
+    /// * item - The item to add
+    /// * object - The sprite, text or shape the item refers to
+    pub fn insert_bounded<B: Bounded>(&mut self, item: T, object: &B) -> () {
+        self.insert(item, &object.get_global_bounds())
+    }
+
+    /// Change the bounds of an item of the tree to the global bounds of an object
+    ///
+    /// # Arguments
+    /// * item - The item to update
+    /// * object - The sprite, text or shape the item refers to
+    ///
+    /// Return false if the item is not in the tree
+    pub fn update_bounded<B: Bounded>(&mut self, item: &T, object: &B) -> bool {
+        self.update(item, &object.get_global_bounds())
+    }
+
+    /// Get the bounds of an item of the tree
+    ///
+    /// # Arguments
+    /// * item - The item to look for
+    ///
+    /// Return Some(FloatRect) or None if the item is not in the tree
+    pub fn get_item_bounds(&self, item: &T) -> Option<FloatRect> {
+        self.entries.get(item).map(|&(bounds, _)| bounds)
+    }
+
+    /// Find the items whose bounds intersect a rectangle
+    ///
+    /// # Arguments
+    /// * rect - The rectangle to test
+    ///
+    /// Return the items, in no particular order
+    pub fn query_rect(&self, rect: &FloatRect) -> Vec<T> {
+        self.query(&RectQuery(*rect)).into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Find the items whose bounds contain a point
+    ///
+    /// # Arguments
+    /// * point - The point to test
+    ///
+    /// Return the items, in no particular order
+    pub fn query_point(&self, point: &Vector2f) -> Vec<T> {
+        self.query(&PointQuery(*point)).into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Find the items whose bounds intersect a circle
+    ///
+    /// # Arguments
+    /// * center - The center of the circle
+    /// * radius - The radius of the circle
+    ///
+    /// Return the items, in no particular order
+    pub fn query_circle(&self, center: &Vector2f, radius: f32) -> Vec<T> {
+        self.query(&CircleQuery(*center, radius)).into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Find the items whose bounds are crossed by a ray
+    ///
+    /// # Arguments
+    /// * origin - The origin of the ray
+    /// * direction - The direction of the ray, it doesn't need to be normalized
+    /// * max_distance - The length of the ray
+    ///
+    /// Return the items, sorted by the distance where the ray enters them
+    pub fn query_ray(&self,
+                     origin: &Vector2f,
+                     direction: &Vector2f,
+                     max_distance: f32) -> Vec<T> {
+        let query = RayQuery(*origin, geometry::normalize(*direction), max_distance);
+        sort_by_distance(self.query(&query))
+    }
+
+    // Walk the nodes whose loose bounds pass the query, and keep the items
+    // whose bounds pass it too
+    #[doc(hidden)]
+    fn query(&self, query: &Query) -> Vec<(f32, T)> {
+        let mut result = Vec::new();
+        let mut stack = vec![0u];
+        loop {
+            let node = match stack.pop() {
+                Some(index) => &self.nodes[index],
+                None => break
+            };
+            for item in node.items.iter() {
+                match self.entries.get(item) {
+                    Some(&(ref bounds, _)) => match query.test(bounds) {
+                        Some(distance) => result.push((distance, item.clone())),
+                        None => {}
+                    },
+                    None => {}
+                }
+            }
+            match node.children {
+                Some(first) => {
+                    for child in range(first, first + 4) {
+                        if query.test(&loose_bounds(&self.nodes[child].cell)).is_some() {
+                            stack.push(child);
+                        }
+                    }
+                },
+                None => {}
+            }
+        }
+        result
+    }
+
+    // Deepest node whose cell contains the center of the bounds and is at
+    // least as large as the bounds, creating it if needed
+    #[doc(hidden)]
+    fn find_node(&mut self, bounds: &FloatRect) -> uint {
+        let center = Vector2f::new(bounds.left + bounds.width / 2.,
+                                   bounds.top + bounds.height / 2.);
+        let size = bounds.width.max(bounds.height);
+        let mut index = 0;
+        if !rect_contains(&self.nodes[0].cell, &center) {
+            return index;
+        }
+        for _ in range(0, self.max_depth) {
+            let cell = self.nodes[index].cell;
+            let half = Vector2f::new(cell.width / 2., cell.height / 2.);
+            if size > half.x.min(half.y) {
+                break;
+            }
+            let first = match self.nodes[index].children {
+                Some(first) => first,
+                None => {
+                    let first = self.nodes.len();
+                    for i in range(0u, 4) {
+                        let child = FloatRect::new(cell.left + half.x * (i % 2) as f32,
+                                                   cell.top + half.y * (i / 2) as f32,
+                                                   half.x,
+                                                   half.y);
+                        self.nodes.push(Node { cell: child, items: Vec::new(), children: None });
+                    }
+                    self.nodes[index].children = Some(first);
+                    first
+                }
+            };
+            let column = if center.x < cell.left + half.x { 0 } else { 1 };
+            let row = if center.y < cell.top + half.y { 0 } else { 1 };
+            index = first + row * 2 + column;
+        }
+        index
+    }
+}
+
+/// Uniform grid of cells stored in a hash map
+///
+/// An item is stored in every cell its bounds overlap, so the cells should
+/// be about as large as the items. The items overlapping too many cells, or
+/// whose bounds are not finite, are kept apart and tested by every query.
+pub struct SpatialHash<T> {
+    #[doc(hidden)]
+    cell_size: f32,
+    #[doc(hidden)]
+    cells: HashMap<(i32, i32), Vec<T>>,
+    #[doc(hidden)]
+    entries: HashMap<T, FloatRect>,
+    #[doc(hidden)]
+    large_items: Vec<T>,
+    // The range of the cells which were given items, to bound the queries
+    #[doc(hidden)]
+    extent: Option<(i32, i32, i32, i32)>
+}
+
+impl<T: Clone + Eq + Hash> SpatialHash<T> {
+    /// Create a new empty spatial hash
+    ///
+    /// # Arguments
+    /// * cell_size - The width and height of a cell
+    ///
+    /// Return a new SpatialHash
+    pub fn new(cell_size: f32) -> SpatialHash<T> {
+        SpatialHash {
+            cell_size: if cell_size > 0. { cell_size } else { 1. },
+            cells: HashMap::new(),
+            entries: HashMap::new(),
+            large_items: Vec::new(),
+            extent: None
+        }
+    }
+
+    /// Get the size of the cells
+    ///
+    /// Return the width and height of a cell
+    pub fn get_cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Get the number of items in the grid
+    ///
+    /// Return the number of items
+    pub fn len(&self) -> uint {
+        self.entries.len()
+    }
+
+    /// Tell if the grid is empty
+    ///
+    /// Return true if the grid has no item
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all the items of the grid
+    pub fn clear(&mut self) -> () {
+        self.cells.clear();
+        self.entries.clear();
+        self.large_items.clear();
+        self.extent = None
+    }
+
+    /// Add an item to the grid
+    ///
+    /// If the item is already in the grid, its bounds are updated.
+    ///
+    /// # Arguments
+    /// * item - The item to add
+    /// * bounds - The bounds of the item
+    pub fn insert(&mut self, item: T, bounds: &FloatRect) -> () {
+        self.remove(&item);
+        let (left, top, right, bottom) = match self.item_cells(bounds) {
+            Some(range) => range,
+            None => {
+                self.large_items.push(item.clone());
+                self.entries.insert(item, *bounds);
+                return;
+            }
+        };
+        self.extent = Some(match self.extent {
+            Some((l, t, r, b)) => (l.min(left), t.min(top), r.max(right), b.max(bottom)),
+            None => (left, top, right, bottom)
+        });
+        for y in range(top, bottom + 1) {
+            for x in range(left, right + 1) {
+                match self.cells.get_mut(&(x, y)) {
+                    Some(cell) => { cell.push(item.clone()); continue; },
+                    None => {}
+                }
+                self.cells.insert((x, y), vec![item.clone()]);
+            }
+        }
+        self.entries.insert(item, *bounds);
+    }
+
+    /// Remove an item from the grid
+    ///
+    /// # Arguments
+    /// * item - The item to remove
+    ///
+    /// Return true if the item was in the grid
+    pub fn remove(&mut self, item: &T) -> bool {
+        let bounds = match self.entries.remove(item) {
+            Some(bounds) => bounds,
+            None => return false
+        };
+        let (left, top, right, bottom) = match self.item_cells(&bounds) {
+            Some(range) => range,
+            None => {
+                match self.large_items.iter().position(|other| other == item) {
+                    Some(index) => { self.large_items.swap_remove(index); },
+                    None => {}
+                }
+                return true;
+            }
+        };
+        for y in range(top, bottom + 1) {
+            for x in range(left, right + 1) {
+                let empty = match self.cells.get_mut(&(x, y)) {
+                    Some(cell) => {
+                        match cell.iter().position(|other| other == item) {
+                            Some(index) => { cell.swap_remove(index); },
+                            None => {}
+                        }
+                        cell.is_empty()
+                    },
+                    None => false
+                };
+                if empty {
+                    self.cells.remove(&(x, y));
+                }
+            }
+        }
+        true
+    }
+
+    /// Change the bounds of an item of the grid
+    ///
+    /// # Arguments
+    /// * item - The item to update
+    /// * bounds - The new bounds of the item
+    ///
+    /// Return false if the item is not in the grid
+    pub fn update(&mut self, item: &T, bounds: &FloatRect) -> bool {
+        if !self.entries.contains_key(item) {
+            return false;
+        }
+        self.insert(item.clone(), bounds);
+        true
+    }
+
+    /// Add an item to the grid with the global bounds of an object
+    ///
+    /// # Arguments
+    /// * item - The item to add
+    /// * object - The sprite, text or shape the item refers to
+    pub fn insert_bounded<B: Bounded>(&mut self, item: T, object: &B) -> () {
+        self.insert(item, &object.get_global_bounds())
+    }
+
+    /// Change the bounds of an item of the grid to the global bounds of an object
+    ///
+    /// # Arguments
+    /// * item - The item to update
+    /// * object - The sprite, text or shape the item refers to
+    ///
+    /// Return false if the item is not in the grid
+    pub fn update_bounded<B: Bounded>(&mut self, item: &T, object: &B) -> bool {
+        self.update(item, &object.get_global_bounds())
+    }
+
+    /// Get the bounds of an item of the grid
+    ///
+    /// # Arguments
+    /// * item - The item to look for
+    ///
+    /// Return Some(FloatRect) or None if the item is not in the grid
+    pub fn get_item_bounds(&self, item: &T) -> Option<FloatRect> {
+        self.entries.get(item).map(|bounds| *bounds)
+    }
+
+    /// Find the items whose bounds intersect a rectangle
+    ///
+    /// # Arguments
+    /// * rect - The rectangle to test
+    ///
+    /// Return the items, in no particular order
+    pub fn query_rect(&self, rect: &FloatRect) -> Vec<T> {
+        self.query(rect, &RectQuery(*rect))
+    }
+
+    /// Find the items whose bounds contain a point
+    ///
+    /// # Arguments
+    /// * point - The point to test
+    ///
+    /// Return the items, in no particular order
+    pub fn query_point(&self, point: &Vector2f) -> Vec<T> {
+        self.query(&FloatRect::new(point.x, point.y, 0., 0.), &PointQuery(*point))
+    }
+
+    /// Find the items whose bounds intersect a circle
+    ///
+    /// # Arguments
+    /// * center - The center of the circle
+    /// * radius - The radius of the circle
+    ///
+    /// Return the items, in no particular order
+    pub fn query_circle(&self, center: &Vector2f, radius: f32) -> Vec<T> {
+        self.query(&FloatRect::new(center.x - radius, center.y - radius,
+                                   radius * 2., radius * 2.),
+                   &CircleQuery(*center, radius))
+    }
+
+    /// Find the items whose bounds are crossed by a ray
+    ///
+    /// # Arguments
+    /// * origin - The origin of the ray
+    /// * direction - The direction of the ray, it doesn't need to be normalized
+    /// * max_distance - The length of the ray, which may be infinite
+    ///
+    /// Return the items, sorted by the distance where the ray enters them
+    pub fn query_ray(&self,
+                     origin: &Vector2f,
+                     direction: &Vector2f,
+                     max_distance: f32) -> Vec<T> {
+        let direction = geometry::normalize(*direction);
+        let query = RayQuery(*origin, direction, max_distance);
+        let mut seen = HashSet::new();
+        let mut result = Vec::new();
+        self.collect(self.large_items.as_slice(), &query, &mut seen, &mut result);
+
+        // Walk the cells crossed by the ray, from where it enters the cells
+        // which were given items to where it leaves them
+        let (left, top, right, bottom) = match self.extent {
+            Some(extent) => extent,
+            None => return sort_by_distance(result)
+        };
+        let area = FloatRect::new(left as f32 * self.cell_size,
+                                  top as f32 * self.cell_size,
+                                  (right - left + 1) as f32 * self.cell_size,
+                                  (bottom - top + 1) as f32 * self.cell_size);
+        let enter = match ray_rect(&area, origin, &direction, max_distance) {
+            Some(enter) if enter.is_finite() => enter,
+            _ => return sort_by_distance(result)
+        };
+        let start = *origin + direction * enter;
+        let (x, y) = self.cell_of(&start);
+        let (mut x, mut y) = (x.max(left).min(right), y.max(top).min(bottom));
+        let step_x = if direction.x < 0. { -1 } else { 1 };
+        let step_y = if direction.y < 0. { -1 } else { 1 };
+        let mut next_x = if direction.x != 0. {
+            (cell_boundary(x, step_x, self.cell_size) - origin.x) / direction.x
+        } else {
+            f32::INFINITY
+        };
+        let mut next_y = if direction.y != 0. {
+            (cell_boundary(y, step_y, self.cell_size) - origin.y) / direction.y
+        } else {
+            f32::INFINITY
+        };
+        let delta_x = self.cell_size / direction.x.abs();
+        let delta_y = self.cell_size / direction.y.abs();
+
+        while x >= left && x <= right && y >= top && y <= bottom {
+            match self.cells.get(&(x, y)) {
+                Some(cell) => self.collect(cell.as_slice(), &query, &mut seen, &mut result),
+                None => {}
+            }
+            if next_x.min(next_y) > max_distance {
+                break;
+            }
+            if next_x < next_y {
+                x += step_x;
+                next_x += delta_x;
+            } else {
+                y += step_y;
+                next_y += delta_y;
+            }
+        }
+        sort_by_distance(result)
+    }
+
+    #[doc(hidden)]
+    fn query(&self, rect: &FloatRect, query: &Query) -> Vec<T> {
+        let mut seen = HashSet::new();
+        let mut result = Vec::new();
+        self.collect(self.large_items.as_slice(), query, &mut seen, &mut result);
+        match self.query_cells(rect) {
+            Some((left, top, right, bottom)) => {
+                for y in range(top, bottom + 1) {
+                    for x in range(left, right + 1) {
+                        match self.cells.get(&(x, y)) {
+                            Some(cell) => self.collect(cell.as_slice(), query,
+                                                       &mut seen, &mut result),
+                            None => {}
+                        }
+                    }
+                }
+            },
+            None => {}
+        }
+        result.into_iter().map(|(_, item)| item).collect()
+    }
+
+    // Keep the items of a cell which pass the query and were not seen in
+    // another cell
+    #[doc(hidden)]
+    fn collect(&self,
+               cell: &[T],
+               query: &Query,
+               seen: &mut HashSet<T>,
+               result: &mut Vec<(f32, T)>) -> () {
+        for item in cell.iter() {
+            if seen.contains(item) {
+                continue;
+            }
+            seen.insert(item.clone());
+            match self.entries.get(item) {
+                Some(bounds) => match query.test(bounds) {
+                    Some(distance) => result.push((distance, item.clone())),
+                    None => {}
+                },
+                None => {}
+            }
+        }
+    }
+
+    // Cell of a point in the cells which were given items
+    #[doc(hidden)]
+    fn cell_of(&self, point: &Vector2f) -> (i32, i32) {
+        let (left, top, right, bottom) = match self.extent {
+            Some(extent) => extent,
+            None => return (0, 0)
+        };
+        let x = (point.x / self.cell_size).floor().max(left as f32).min(right as f32);
+        let y = (point.y / self.cell_size).floor().max(top as f32).min(bottom as f32);
+        (x as i32, y as i32)
+    }
+
+    // Cells overlapped by the bounds of an item, None if they are not finite
+    // or overlap too many cells
+    #[doc(hidden)]
+    fn item_cells(&self, bounds: &FloatRect) -> Option<(i32, i32, i32, i32)> {
+        let left = (bounds.left / self.cell_size).floor();
+        let top = (bounds.top / self.cell_size).floor();
+        let right = ((bounds.left + bounds.width) / self.cell_size).floor();
+        let bottom = ((bounds.top + bounds.height) / self.cell_size).floor();
+        let limit = i32::MAX as f32 / 2.;
+        if [left, top, right, bottom].iter().any(|&cell| !(cell.abs() < limit)) ||
+           right < left || bottom < top ||
+           (right - left + 1.) * (bottom - top + 1.) > MAX_ITEM_CELLS {
+            return None;
+        }
+        Some((left as i32, top as i32, right as i32, bottom as i32))
+    }
+
+    // Cells overlapped by a rectangle among the cells which were given items,
+    // None if there are none
+    #[doc(hidden)]
+    fn query_cells(&self, rect: &FloatRect) -> Option<(i32, i32, i32, i32)> {
+        let (left, top, right, bottom) = match self.extent {
+            Some(extent) => extent,
+            None => return None
+        };
+        let min_x = (rect.left / self.cell_size).floor();
+        let min_y = (rect.top / self.cell_size).floor();
+        let max_x = ((rect.left + rect.width) / self.cell_size).floor();
+        let max_y = ((rect.top + rect.height) / self.cell_size).floor();
+        if !(min_x <= right as f32 && max_x >= left as f32 &&
+             min_y <= bottom as f32 && max_y >= top as f32) {
+            return None;
+        }
+        Some((min_x.max(left as f32) as i32, min_y.max(top as f32) as i32,
+              max_x.min(right as f32) as i32, max_y.min(bottom as f32) as i32))
+    }
+}
+
+#[doc(hidden)]
+fn loose_bounds(cell: &FloatRect) -> FloatRect {
+    FloatRect::new(cell.left - cell.width / 2.,
+                   cell.top - cell.height / 2.,
+                   cell.width * 2.,
+                   cell.height * 2.)
+}
+
+#[doc(hidden)]
+fn rects_intersect(a: &FloatRect, b: &FloatRect) -> bool {
+    a.left <= b.left + b.width && b.left <= a.left + a.width &&
+    a.top <= b.top + b.height && b.top <= a.top + a.height
+}
+
+#[doc(hidden)]
+fn rect_contains(rect: &FloatRect, point: &Vector2f) -> bool {
+    point.x >= rect.left && point.x <= rect.left + rect.width &&
+    point.y >= rect.top && point.y <= rect.top + rect.height
+}
+
+#[doc(hidden)]
+fn rect_circle_intersect(rect: &FloatRect, center: &Vector2f, radius: f32) -> bool {
+    let closest = Vector2f::new(center.x.max(rect.left).min(rect.left + rect.width),
+                                center.y.max(rect.top).min(rect.top + rect.height));
+    geometry::distance(closest, *center) <= radius
+}
+
+// Distance where a ray enters a rectangle, 0 if it starts inside
+#[doc(hidden)]
+fn ray_rect(rect: &FloatRect,
+            origin: &Vector2f,
+            direction: &Vector2f,
+            max_distance: f32) -> Option<f32> {
+    let mut enter = 0f32;
+    let mut exit = max_distance;
+    let slabs = [(origin.x, direction.x, rect.left, rect.left + rect.width),
+                 (origin.y, direction.y, rect.top, rect.top + rect.height)];
+    for &(start, speed, min, max) in slabs.iter() {
+        if speed == 0. {
+            if start < min || start > max {
+                return None;
+            }
+        } else {
+            let (mut near, mut far) = ((min - start) / speed, (max - start) / speed);
+            if near > far {
+                let swap = near;
+                near = far;
+                far = swap;
+            }
+            enter = enter.max(near);
+            exit = exit.min(far);
+            if enter > exit {
+                return None;
+            }
+        }
+    }
+    Some(enter)
+}
+
+#[doc(hidden)]
+fn sort_by_distance<T>(hits: Vec<(f32, T)>) -> Vec<T> {
+    let mut hits = hits;
+    hits.sort_by(|&(a, _), &(b, _)| a.partial_cmp(&b).unwrap_or(Ordering::Equal));
+    hits.into_iter().map(|(_, item)| item).collect()
+}
+
+// Coordinate of the side of a cell crossed first when moving by step
+#[doc(hidden)]
+fn cell_boundary(cell: i32, step: i32, cell_size: f32) -> f32 {
+    (if step > 0 { cell + 1 } else { cell }) as f32 * cell_size
+}
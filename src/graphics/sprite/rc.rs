@@ -32,9 +32,12 @@ use std::cell::RefCell;
 use libc::{c_float};
 use std::ptr;
 
-use traits::{Drawable, Wrappable};
+use traits::{Drawable, Wrappable, Bounded};
 use graphics::{FloatRect, IntRect, Color, Texture,
                RenderTarget, Transform, rc};
+use graphics::CollisionMask;
+use graphics::collision_mask;
+use graphics::collision_mask::MaskedSprite;
 use system::vector2::Vector2f;
 
 use ffi::sfml_types::{SFTRUE, SFFALSE};
@@ -462,6 +465,37 @@ impl Sprite {
             ffi::sfSprite_getInverseTransform(self.sprite)
         }
     }
+
+    /// Test the overlap of two sprites pixel by pixel
+    ///
+    /// The bounding rectangles of the sprites are tested first, then the
+    /// solid pixels of their masks, taking their transform and texture
+    /// rect into account. The masks must be built from the textures of
+    /// the sprites.
+    ///
+    /// # Arguments
+    /// * other - The sprite to test against
+    /// * mask - The collision mask of the texture of this sprite
+    /// * other_mask - The collision mask of the texture of the other sprite
+    ///
+    /// Return true if solid pixels of both sprites overlap
+    pub fn pixel_collides(&self,
+                          other: &Sprite,
+                          mask: &CollisionMask,
+                          other_mask: &CollisionMask) -> bool {
+        collision_mask::pixel_collides(&MaskedSprite {
+                                           bounds: self.get_global_bounds(),
+                                           inverse_transform: self.get_inverse_transform(),
+                                           texture_rect: self.get_texture_rect(),
+                                           mask: mask
+                                       },
+                                       &MaskedSprite {
+                                           bounds: other.get_global_bounds(),
+                                           inverse_transform: other.get_inverse_transform(),
+                                           texture_rect: other.get_texture_rect(),
+                                           mask: other_mask
+                                       })
+    }
 }
 
 impl Clone for Sprite {
@@ -492,6 +526,18 @@ impl Wrappable<*mut ffi::sfSprite> for Sprite {
     }
 }
 
+impl_transformable!(Sprite);
+
+impl Bounded for Sprite {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
 impl Drawable for Sprite {
     fn draw<RT:RenderTarget>(&self, render_target: &mut RT) -> () {
         render_target.draw_sprite_rc(self)
@@ -0,0 +1,316 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+#![allow(missing_copy_implementations)]
+
+//! Batch of textured quads drawn with few draw calls
+//!
+//! A SpriteBatch accepts sprites and raw quads, groups them by texture
+//! and blend mode and draws each group with a single call. The order of
+//! the quads is given by the sort mode.
+
+pub use self::BatchSortMode::{SortDeferred, SortByTexture, SortBackToFront};
+
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+
+use traits::Drawable;
+use graphics::{Color, Vertex, Texture, Sprite, IntRect, Transform, BlendMode, BlendAlpha,
+               RenderTarget, RenderStates, Quads};
+use system::vector2::Vector2f;
+
+/// Order in which the quads of a SpriteBatch are drawn
+#[deriving(Clone, PartialEq, Eq, Show, Copy)]
+pub enum BatchSortMode {
+    /// Draw the quads in the order they were added, merging consecutive
+    /// quads which share a texture and a blend mode
+    SortDeferred,
+    /// Group all the quads sharing a texture and a blend mode, for the
+    /// fewest draw calls. The order between textures is unspecified
+    SortByTexture,
+    /// Draw the quads with the highest depth first, for transparency
+    SortBackToFront
+}
+
+/// Batch of textured quads drawn with few draw calls
+pub struct SpriteBatch<'s> {
+    #[doc(hidden)]
+    quads: Vec<Quad<'s>>,
+    #[doc(hidden)]
+    sort_mode: BatchSortMode,
+    #[doc(hidden)]
+    blend_mode: BlendMode,
+    #[doc(hidden)]
+    vertices: RefCell<Vec<Vertex>>,
+    #[doc(hidden)]
+    batches: RefCell<Vec<Batch<'s>>>,
+    #[doc(hidden)]
+    dirty: Cell<bool>
+}
+
+#[doc(hidden)]
+struct Quad<'s> {
+    texture: Option<&'s Texture>,
+    blend_mode: BlendMode,
+    depth: f32,
+    vertices: [Vertex, ..4]
+}
+
+#[doc(hidden)]
+struct Batch<'s> {
+    texture: Option<&'s Texture>,
+    blend_mode: BlendMode,
+    start: uint,
+    end: uint
+}
+
+impl<'s> SpriteBatch<'s> {
+    /// Create a new empty sprite batch
+    ///
+    /// # Arguments
+    /// * sort_mode - The order in which the quads are drawn
+    ///
+    /// Return a new SpriteBatch
+    pub fn new(sort_mode: BatchSortMode) -> SpriteBatch<'s> {
+        SpriteBatch {
+            quads: Vec::new(),
+            sort_mode: sort_mode,
+            blend_mode: BlendAlpha,
+            vertices: RefCell::new(Vec::new()),
+            batches: RefCell::new(Vec::new()),
+            dirty: Cell::new(false)
+        }
+    }
+
+    /// Remove all the quads of the batch
+    ///
+    /// The memory is kept to be reused by the next frame.
+    pub fn clear(&mut self) -> () {
+        self.quads.clear();
+        self.dirty.set(true);
+    }
+
+    /// Set the order in which the quads are drawn
+    ///
+    /// # Arguments
+    /// * sort_mode - The new sort mode
+    pub fn set_sort_mode(&mut self, sort_mode: BatchSortMode) -> () {
+        self.sort_mode = sort_mode;
+        self.dirty.set(true);
+    }
+
+    /// Get the order in which the quads are drawn
+    ///
+    /// Return the sort mode
+    pub fn get_sort_mode(&self) -> BatchSortMode {
+        self.sort_mode
+    }
+
+    /// Set the blend mode of the quads added from now on
+    ///
+    /// The default blend mode is BlendAlpha.
+    ///
+    /// # Arguments
+    /// * blend_mode - The new blend mode
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) -> () {
+        self.blend_mode = blend_mode;
+    }
+
+    /// Get the blend mode of the quads added from now on
+    ///
+    /// Return the blend mode
+    pub fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    /// Add a sprite to the batch
+    ///
+    /// The sprite itself is not kept, only its quad.
+    ///
+    /// # Arguments
+    /// * sprite - The sprite to add
+    /// * depth - The depth used by SortBackToFront
+    pub fn add_sprite(&mut self, sprite: &Sprite<'s>, depth: f32) -> () {
+        self.add(sprite.get_texture(),
+                 &sprite.get_texture_rect(),
+                 &sprite.get_transform(),
+                 &sprite.get_color(),
+                 depth)
+    }
+
+    /// Add a textured rectangle to the batch
+    ///
+    /// # Arguments
+    /// * texture - The texture, or None for a plain rectangle
+    /// * texture_rect - The part of the texture to show, it also gives
+    ///   the size of the rectangle
+    /// * transform - The transform applied to the rectangle
+    /// * color - The color multiplied with the texture
+    /// * depth - The depth used by SortBackToFront
+    pub fn add(&mut self,
+               texture: Option<&'s Texture>,
+               texture_rect: &IntRect,
+               transform: &Transform,
+               color: &Color,
+               depth: f32) -> () {
+        let mut transform = *transform;
+        let (width, height) = (texture_rect.width.abs() as f32,
+                               texture_rect.height.abs() as f32);
+        let left = texture_rect.left as f32;
+        let top = texture_rect.top as f32;
+        let right = left + texture_rect.width as f32;
+        let bottom = top + texture_rect.height as f32;
+        let corners = [(0., 0., left, top),
+                       (width, 0., right, top),
+                       (width, height, right, bottom),
+                       (0., height, left, bottom)];
+        let mut vertices = [Vertex::default(), ..4];
+        for (vertex, &(x, y, u, v)) in vertices.iter_mut().zip(corners.iter()) {
+            *vertex = Vertex::new(&transform.transform_point(&Vector2f::new(x, y)),
+                                  color,
+                                  &Vector2f::new(u, v));
+        }
+        self.add_quad(texture, &vertices, depth)
+    }
+
+    /// Add a raw quad to the batch
+    ///
+    /// # Arguments
+    /// * texture - The texture, or None for an untextured quad
+    /// * vertices - The four vertices of the quad, in order around it
+    /// * depth - The depth used by SortBackToFront
+    pub fn add_quad(&mut self,
+                    texture: Option<&'s Texture>,
+                    vertices: &[Vertex, ..4],
+                    depth: f32) -> () {
+        self.quads.push(Quad {
+            texture: texture,
+            blend_mode: self.blend_mode,
+            depth: depth,
+            vertices: *vertices
+        });
+        self.dirty.set(true);
+    }
+
+    /// Get the number of quads in the batch
+    ///
+    /// Return the number of quads
+    pub fn len(&self) -> uint {
+        self.quads.len()
+    }
+
+    /// Tell whether the batch is empty
+    ///
+    /// Return true if the batch has no quad, false otherwise
+    pub fn is_empty(&self) -> bool {
+        self.quads.is_empty()
+    }
+
+    /// Get the number of draw calls needed to draw the batch
+    ///
+    /// Return the number of draw calls
+    pub fn get_draw_call_count(&self) -> uint {
+        self.update_batches();
+        self.batches.borrow().len()
+    }
+
+    // Sort the quads and group them into batches
+    #[doc(hidden)]
+    fn update_batches(&self) -> () {
+        if !self.dirty.get() {
+            return;
+        }
+        self.dirty.set(false);
+        let mut order: Vec<uint> = range(0, self.quads.len()).collect();
+        match self.sort_mode {
+            SortDeferred => {},
+            SortByTexture => order.sort_by(|&a, &b| {
+                batch_key(&self.quads[a]).cmp(&batch_key(&self.quads[b]))
+            }),
+            SortBackToFront => order.sort_by(|&a, &b| {
+                self.quads[b].depth.partial_cmp(&self.quads[a].depth).unwrap_or(Ordering::Equal)
+            })
+        }
+
+        let mut vertices = self.vertices.borrow_mut();
+        let mut batches = self.batches.borrow_mut();
+        vertices.clear();
+        batches.clear();
+        for &index in order.iter() {
+            let quad = &self.quads[index];
+            let start = vertices.len();
+            vertices.push_all(&quad.vertices);
+            let merged = match batches.last() {
+                Some(batch) => batch_key_of(batch.texture, batch.blend_mode) == batch_key(quad),
+                None => false
+            };
+            if merged {
+                let last = batches.len() - 1;
+                batches[last].end = vertices.len();
+            } else {
+                batches.push(Batch {
+                    texture: quad.texture,
+                    blend_mode: quad.blend_mode,
+                    start: start,
+                    end: vertices.len()
+                });
+            }
+        }
+    }
+}
+
+impl<'s> Drawable for SpriteBatch<'s> {
+    fn draw<RT: RenderTarget>(&self, render_target: &mut RT) -> () {
+        self.draw_rs(render_target, &mut RenderStates::default())
+    }
+
+    fn draw_rs<RT: RenderTarget>(&self,
+                                 render_target: &mut RT,
+                                 render_states: &mut RenderStates) -> () {
+        self.update_batches();
+        let vertices = self.vertices.borrow();
+        for batch in self.batches.borrow().iter() {
+            let mut states = RenderStates::new(batch.blend_mode,
+                                               render_states.transform,
+                                               batch.texture,
+                                               render_states.shader);
+            render_target.draw_primitives_rs(vertices.slice(batch.start, batch.end),
+                                              Quads,
+                                              &mut states);
+        }
+    }
+}
+
+#[doc(hidden)]
+fn batch_key(quad: &Quad) -> (BlendMode, uint) {
+    batch_key_of(quad.texture, quad.blend_mode)
+}
+
+#[doc(hidden)]
+fn batch_key_of(texture: Option<&Texture>, blend_mode: BlendMode) -> (BlendMode, uint) {
+    match texture {
+        Some(texture) => (blend_mode, texture as *const Texture as uint),
+        None => (blend_mode, 0)
+    }
+}
@@ -33,7 +33,7 @@ use std::c_vec::CVec;
 use std::c_str::CString;
 use libc::{c_float, c_uint, size_t};
 
-use traits::{Drawable, Wrappable};
+use traits::{Drawable, Wrappable, Bounded};
 use graphics::{RenderTarget, Font, FloatRect,
                Color, Transform, RenderStates, TextStyle};
 use system::vector2::Vector2f;
@@ -573,6 +573,18 @@ impl<'s> Wrappable<*mut ffi::sfText> for Text<'s> {
     }
 }
 
+impl_transformable!(Text<'s>, 's);
+
+impl<'s> Bounded for Text<'s> {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
 impl<'s> Drawable for Text<'s> {
     fn draw<RT:RenderTarget>(&self, render_target: &mut RT) -> () {
         render_target.draw_text(self)
@@ -35,7 +35,7 @@ use std::c_vec::CVec;
 use std::c_str::CString;
 use libc::{c_float, c_uint, size_t};
 
-use traits::{Drawable, Wrappable};
+use traits::{Drawable, Wrappable, Bounded};
 use graphics::{RenderTarget, Font, FloatRect,
                Color, Transform, rc, TextStyle};
 use system::vector2::Vector2f;
@@ -573,6 +573,18 @@ impl Wrappable<*mut ffi::sfText> for Text {
     }
 }
 
+impl_transformable!(Text);
+
+impl Bounded for Text {
+    fn get_local_bounds(&self) -> FloatRect {
+        self.get_local_bounds()
+    }
+
+    fn get_global_bounds(&self) -> FloatRect {
+        self.get_global_bounds()
+    }
+}
+
 impl Drawable for Text {
     fn draw<RT:RenderTarget>(&self, render_target: &mut RT) -> () {
         render_target.draw_text_rc(self)
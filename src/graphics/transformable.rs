@@ -237,6 +237,8 @@ impl Clone for Transformable {
     }
 }
 
+impl_transformable!(Transformable);
+
 impl Wrappable<*mut ffi::sfTransformable> for Transformable {
     fn wrap(transformable: *mut ffi::sfTransformable) -> Transformable {
         Transformable {
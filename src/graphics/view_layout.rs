@@ -0,0 +1,312 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Division of a render target into several views
+//!
+//! A ViewLayout holds one View per viewport, for split screen games,
+//! and picture-in-picture minimaps drawn above them. It routes the mouse
+//! to the right view and draws borders around the viewports.
+
+use graphics::{View, FloatRect, Color, RectangleShape, RenderTarget};
+use system::vector2::{Vector2f, Vector2i};
+
+/// Division of a render target into several views
+pub struct ViewLayout {
+    #[doc(hidden)]
+    panes: Vec<Pane>
+}
+
+#[doc(hidden)]
+struct Pane {
+    view: View,
+    minimap: bool,
+    border_thickness: f32,
+    border_color: Color
+}
+
+impl ViewLayout {
+    /// Create a layout of viewports side by side, from left to right
+    ///
+    /// # Arguments
+    /// * count - The number of viewports
+    ///
+    /// Return Some(ViewLayout) or None
+    pub fn new_horizontal(count: uint) -> Option<ViewLayout> {
+        ViewLayout::new_grid(count, 1)
+    }
+
+    /// Create a layout of viewports stacked from top to bottom
+    ///
+    /// # Arguments
+    /// * count - The number of viewports
+    ///
+    /// Return Some(ViewLayout) or None
+    pub fn new_vertical(count: uint) -> Option<ViewLayout> {
+        ViewLayout::new_grid(1, count)
+    }
+
+    /// Create a grid of viewports, ordered row by row
+    ///
+    /// # Arguments
+    /// * columns - The number of columns
+    /// * rows - The number of rows
+    ///
+    /// Return Some(ViewLayout) or None
+    pub fn new_grid(columns: uint, rows: uint) -> Option<ViewLayout> {
+        let mut viewports = Vec::new();
+        let (width, height) = (1. / columns as f32, 1. / rows as f32);
+        for row in range(0, rows) {
+            for column in range(0, columns) {
+                viewports.push(FloatRect::new(column as f32 * width,
+                                              row as f32 * height,
+                                              width,
+                                              height));
+            }
+        }
+        ViewLayout::new_custom(viewports.as_slice())
+    }
+
+    /// Create a layout from viewport rectangles
+    ///
+    /// The rectangles are expressed as factors (between 0 and 1) of the
+    /// size of the render target, like View::set_viewport. Every view
+    /// starts showing the area (0, 0, 1000, 1000), like View::new.
+    ///
+    /// # Arguments
+    /// * viewports - The viewports, from bottom to top
+    ///
+    /// Return Some(ViewLayout) or None
+    pub fn new_custom(viewports: &[FloatRect]) -> Option<ViewLayout> {
+        let mut layout = ViewLayout {
+            panes: Vec::with_capacity(viewports.len())
+        };
+        for viewport in viewports.iter() {
+            match layout.add_pane(viewport, &FloatRect::new(0., 0., 1000., 1000.), false) {
+                Some(_) => {},
+                None => return None
+            }
+        }
+        Some(layout)
+    }
+
+    /// Add a picture-in-picture minimap above the other viewports
+    ///
+    /// # Arguments
+    /// * viewport - The viewport of the minimap, as a factor of the target size
+    /// * area - The world area shown by the minimap
+    ///
+    /// Return Some(index) of the minimap or None
+    pub fn add_minimap(&mut self, viewport: &FloatRect, area: &FloatRect) -> Option<uint> {
+        self.add_pane(viewport, area, true)
+    }
+
+    /// Get the number of viewports, minimaps included
+    ///
+    /// Return the number of viewports
+    pub fn get_count(&self) -> uint {
+        self.panes.len()
+    }
+
+    /// Tell whether a viewport is a minimap
+    ///
+    /// # Arguments
+    /// * index - The index of the viewport
+    ///
+    /// Return true if the viewport is a minimap, false otherwise
+    pub fn is_minimap(&self, index: uint) -> bool {
+        index < self.panes.len() && self.panes[index].minimap
+    }
+
+    /// Get the view of a viewport
+    ///
+    /// # Arguments
+    /// * index - The index of the viewport
+    ///
+    /// Return Some(view) or None if the index is out of range
+    pub fn get_view(&self, index: uint) -> Option<&View> {
+        if index < self.panes.len() {
+            Some(&self.panes[index].view)
+        } else {
+            None
+        }
+    }
+
+    /// Get the view of a viewport, to move or zoom it
+    ///
+    /// # Arguments
+    /// * index - The index of the viewport
+    ///
+    /// Return Some(view) or None if the index is out of range
+    pub fn get_view_mut(&mut self, index: uint) -> Option<&mut View> {
+        if index < self.panes.len() {
+            Some(&mut self.panes[index].view)
+        } else {
+            None
+        }
+    }
+
+    /// Set the center of the view of a viewport
+    ///
+    /// # Arguments
+    /// * index - The index of the viewport
+    /// * center - The new center
+    pub fn set_center(&mut self, index: uint, center: &Vector2f) -> () {
+        match self.get_view_mut(index) {
+            Some(view) => view.set_center(center),
+            None => {}
+        }
+    }
+
+    /// Set the border drawn around a viewport
+    ///
+    /// The border is drawn inside the viewport. A thickness of 0
+    /// (the default for split viewports) disables it.
+    ///
+    /// # Arguments
+    /// * index - The index of the viewport
+    /// * thickness - The thickness of the border, in pixels
+    /// * color - The color of the border
+    pub fn set_border(&mut self, index: uint, thickness: f32, color: &Color) -> () {
+        if index < self.panes.len() {
+            self.panes[index].border_thickness = thickness;
+            self.panes[index].border_color = *color;
+        }
+    }
+
+    /// Set the border drawn around every viewport
+    ///
+    /// # Arguments
+    /// * thickness - The thickness of the borders, in pixels
+    /// * color - The color of the borders
+    pub fn set_borders(&mut self, thickness: f32, color: &Color) -> () {
+        for pane in self.panes.iter_mut() {
+            pane.border_thickness = thickness;
+            pane.border_color = *color;
+        }
+    }
+
+    /// Set the view of a viewport to a render target
+    ///
+    /// Draw the scene after applying each viewport in turn.
+    ///
+    /// # Arguments
+    /// * index - The index of the viewport
+    /// * render_target - The target on which the view is set
+    pub fn apply<RT: RenderTarget>(&self, index: uint, render_target: &mut RT) -> () {
+        if index < self.panes.len() {
+            render_target.set_view(&self.panes[index].view)
+        }
+    }
+
+    /// Find the viewport under a pixel
+    ///
+    /// Minimaps are above the other viewports.
+    ///
+    /// # Arguments
+    /// * pixel - The pixel, relative to the render target
+    /// * render_target - The target the layout is drawn on
+    ///
+    /// Return Some(index) of the topmost viewport under the pixel or None
+    pub fn route_pixel<RT: RenderTarget>(&self, pixel: &Vector2i, render_target: &RT) -> Option<uint> {
+        for index in range(0, self.panes.len()).rev() {
+            let viewport = render_target.get_viewport(&self.panes[index].view);
+            if viewport.contains(pixel.x, pixel.y) {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    /// Convert a pixel to the coordinates of the viewport under it
+    ///
+    /// # Arguments
+    /// * pixel - The pixel, relative to the render target
+    /// * render_target - The target the layout is drawn on
+    ///
+    /// Return Some((index, coordinates)) or None if no viewport is under the pixel
+    pub fn map_pixel_to_coords<RT: RenderTarget>(&self,
+                                                 pixel: &Vector2i,
+                                                 render_target: &RT) -> Option<(uint, Vector2f)> {
+        match self.route_pixel(pixel, render_target) {
+            Some(index) => {
+                let coords = render_target.map_pixel_to_coords(pixel, &self.panes[index].view);
+                Some((index, coords))
+            },
+            None => None
+        }
+    }
+
+    /// Draw the borders of the viewports
+    ///
+    /// The view of the render target is restored afterwards.
+    ///
+    /// # Arguments
+    /// * render_target - The target the layout is drawn on
+    pub fn draw_borders<RT: RenderTarget>(&self, render_target: &mut RT) -> () {
+        let mut rectangle = match RectangleShape::new() {
+            Some(rectangle) => rectangle,
+            None => return
+        };
+        rectangle.set_fill_color(&Color::transparent());
+        let previous = render_target.get_view();
+        // The default view keeps the size the target had when created, so
+        // the view is built from the current size to map one unit per pixel
+        let size = render_target.get_size();
+        let pixels = match View::new_from_rect(&FloatRect::new(0., 0.,
+                                                               size.x as f32,
+                                                               size.y as f32)) {
+            Some(view) => view,
+            None => return
+        };
+        render_target.set_view(&pixels);
+        for pane in self.panes.iter() {
+            if pane.border_thickness <= 0. {
+                continue;
+            }
+            let viewport = render_target.get_viewport(&pane.view);
+            rectangle.set_position(&Vector2f::new(viewport.left as f32, viewport.top as f32));
+            rectangle.set_size(&Vector2f::new(viewport.width as f32, viewport.height as f32));
+            rectangle.set_outline_thickness(-pane.border_thickness);
+            rectangle.set_outline_color(&pane.border_color);
+            render_target.draw(&rectangle);
+        }
+        render_target.set_view(&previous);
+    }
+
+    #[doc(hidden)]
+    fn add_pane(&mut self, viewport: &FloatRect, area: &FloatRect, minimap: bool) -> Option<uint> {
+        let mut view = match View::new_from_rect(area) {
+            Some(view) => view,
+            None => return None
+        };
+        view.set_viewport(viewport);
+        self.panes.push(Pane {
+            view: view,
+            minimap: minimap,
+            border_thickness: if minimap { 1. } else { 0. },
+            border_color: Color::white()
+        });
+        Some(self.panes.len() - 1)
+    }
+}
@@ -129,11 +129,14 @@
 #![feature(unsafe_destructor)]
 
 extern crate libc;
+extern crate flate;
 
+#[macro_escape]
 pub mod traits;
 pub mod system;
 pub mod window;
 pub mod audio;
 pub mod graphics;
 pub mod network;
+pub mod resources;
 mod ffi;
@@ -0,0 +1,425 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Caches loading each resource once
+//!
+//! A ResourceCache loads each resource once by key and hands out
+//! Rc<RefCell<T>> handles, which are the handles used by the rc drawables
+//! and sounds. A ResourceManager gathers a cache for each kind of resource.
+
+use std::rc::{mod, Rc};
+use std::cell::RefCell;
+use std::hash::Hash;
+use std::collections::HashMap;
+
+use graphics::{Texture, Font, Image};
+use graphics::rc::Shader;
+use audio::SoundBuffer;
+
+/// Resource which can be loaded from a file
+pub trait Resource {
+    /// Load the resource from a file
+    ///
+    /// # Arguments
+    /// * path - The path of the file
+    ///
+    /// Return Some(resource) or None
+    fn load_from_file(path: &str) -> Option<Self>;
+
+    /// Estimate the memory used by the resource
+    ///
+    /// Return the estimated size in bytes
+    fn get_memory_size(&self) -> uint;
+}
+
+impl Resource for Texture {
+    fn load_from_file(path: &str) -> Option<Texture> {
+        Texture::new_from_file(path)
+    }
+
+    fn get_memory_size(&self) -> uint {
+        let size = self.get_size();
+        size.x as uint * size.y as uint * 4
+    }
+}
+
+impl Resource for Image {
+    fn load_from_file(path: &str) -> Option<Image> {
+        Image::new_from_file(path)
+    }
+
+    fn get_memory_size(&self) -> uint {
+        let size = self.get_size();
+        size.x as uint * size.y as uint * 4
+    }
+}
+
+impl Resource for Font {
+    fn load_from_file(path: &str) -> Option<Font> {
+        Font::new_from_file(path)
+    }
+
+    /// The glyph pages of a font grow with the characters drawn and are
+    /// not known, they are not counted
+    fn get_memory_size(&self) -> uint {
+        0
+    }
+}
+
+impl Resource for SoundBuffer {
+    fn load_from_file(path: &str) -> Option<SoundBuffer> {
+        SoundBuffer::new(path)
+    }
+
+    fn get_memory_size(&self) -> uint {
+        // Samples are 16 bits integers
+        self.get_sample_count() as uint * 2
+    }
+}
+
+impl Resource for Shader {
+    /// Load a fragment shader, use ResourceManager::load_shader to load
+    /// a vertex shader too
+    fn load_from_file(path: &str) -> Option<Shader> {
+        Shader::new_from_file(None, Some(path))
+    }
+
+    fn get_memory_size(&self) -> uint {
+        0
+    }
+}
+
+/// Cache loading each resource once by key
+pub struct ResourceCache<K, T> {
+    #[doc(hidden)]
+    resources: HashMap<K, Rc<RefCell<T>>>
+}
+
+impl<K: Clone + Eq + Hash, T> ResourceCache<K, T> {
+    /// Create an empty cache
+    ///
+    /// Return a new ResourceCache
+    pub fn new() -> ResourceCache<K, T> {
+        ResourceCache {
+            resources: HashMap::new()
+        }
+    }
+
+    /// Add a resource created by other means than a file
+    ///
+    /// A resource already cached with this key is replaced.
+    ///
+    /// # Arguments
+    /// * key - The key of the resource
+    /// * resource - The resource
+    ///
+    /// Return a handle to the resource
+    pub fn insert(&mut self, key: K, resource: T) -> Rc<RefCell<T>> {
+        let handle = Rc::new(RefCell::new(resource));
+        self.resources.insert(key, handle.clone());
+        handle
+    }
+
+    /// Get a cached resource
+    ///
+    /// # Arguments
+    /// * key - The key of the resource
+    ///
+    /// Return Some(handle), or None if the resource is not cached
+    pub fn get(&self, key: &K) -> Option<Rc<RefCell<T>>> {
+        self.resources.get(key).map(|handle| handle.clone())
+    }
+
+    /// Tell whether a resource is cached
+    ///
+    /// # Arguments
+    /// * key - The key of the resource
+    ///
+    /// Return true if the resource is cached
+    pub fn contains(&self, key: &K) -> bool {
+        self.resources.contains_key(key)
+    }
+
+    /// Remove a resource from the cache
+    ///
+    /// The resource is destroyed when its last handle is dropped.
+    ///
+    /// # Arguments
+    /// * key - The key of the resource
+    ///
+    /// Return true if the resource was cached
+    pub fn unload(&mut self, key: &K) -> bool {
+        self.resources.remove(key).is_some()
+    }
+
+    /// Remove the resources which have no handle outside of the cache
+    ///
+    /// Return the number of removed resources
+    pub fn unload_unused(&mut self) -> uint {
+        let mut unused = Vec::new();
+        for (key, handle) in self.resources.iter() {
+            if rc::is_unique(handle) {
+                unused.push(key.clone());
+            }
+        }
+        for key in unused.iter() {
+            self.resources.remove(key);
+        }
+        unused.len()
+    }
+
+    /// Remove every resource from the cache
+    pub fn clear(&mut self) -> () {
+        self.resources.clear()
+    }
+
+    /// Get the number of cached resources
+    ///
+    /// Return the number of resources
+    pub fn len(&self) -> uint {
+        self.resources.len()
+    }
+
+    /// Tell whether the cache is empty
+    ///
+    /// Return true if no resource is cached
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+    }
+
+    /// Get the keys of the cached resources
+    ///
+    /// Return the keys, in no particular order
+    pub fn get_keys(&self) -> Vec<K> {
+        self.resources.keys().map(|key| key.clone()).collect()
+    }
+}
+
+impl<K: Clone + Eq + Hash, T: Resource> ResourceCache<K, T> {
+    /// Load a resource once
+    ///
+    /// If a resource is already cached with this key, it is returned and
+    /// the file is not loaded.
+    ///
+    /// # Arguments
+    /// * key - The key of the resource
+    /// * path - The path of the file
+    ///
+    /// Return Some(handle), or None if the resource can't be loaded
+    pub fn load(&mut self, key: K, path: &str) -> Option<Rc<RefCell<T>>> {
+        match self.resources.get(&key) {
+            Some(handle) => return Some(handle.clone()),
+            None => {}
+        }
+        match Resource::load_from_file(path) {
+            Some(resource) => Some(self.insert(key, resource)),
+            None => None
+        }
+    }
+
+    /// Load a list of resources ahead of their use
+    ///
+    /// # Arguments
+    /// * resources - The (key, path) pairs of the resources
+    ///
+    /// Return the keys of the resources which can't be loaded
+    pub fn preload(&mut self, resources: &[(K, &str)]) -> Vec<K> {
+        let mut failed = Vec::new();
+        for &(ref key, path) in resources.iter() {
+            if self.load(key.clone(), path).is_none() {
+                failed.push(key.clone());
+            }
+        }
+        failed
+    }
+
+    /// Estimate the memory used by a resource
+    ///
+    /// # Arguments
+    /// * key - The key of the resource
+    ///
+    /// Return Some(size) in bytes, or None if the resource is not cached
+    pub fn get_memory_size(&self, key: &K) -> Option<uint> {
+        self.resources.get(key).map(|handle| handle.borrow().get_memory_size())
+    }
+
+    /// Estimate the memory used by every cached resource
+    ///
+    /// Return the total size in bytes
+    pub fn get_total_memory_size(&self) -> uint {
+        self.resources.values().fold(0, |total, handle| total + handle.borrow().get_memory_size())
+    }
+}
+
+impl<T: Resource> ResourceCache<String, T> {
+    /// Load a resource once, using its path as key
+    ///
+    /// # Arguments
+    /// * path - The path of the file
+    ///
+    /// Return Some(handle), or None if the resource can't be loaded
+    pub fn load_path(&mut self, path: &str) -> Option<Rc<RefCell<T>>> {
+        self.load(path.to_string(), path)
+    }
+
+    /// Load a list of resources ahead of their use, using their paths as keys
+    ///
+    /// # Arguments
+    /// * paths - The paths of the files
+    ///
+    /// Return the paths of the resources which can't be loaded
+    pub fn preload_paths(&mut self, paths: &[&str]) -> Vec<String> {
+        let mut failed = Vec::new();
+        for &path in paths.iter() {
+            if self.load_path(path).is_none() {
+                failed.push(path.to_string());
+            }
+        }
+        failed
+    }
+}
+
+/// Caches of the textures, fonts, sound buffers, images and shaders of an
+/// application, keyed by path
+pub struct ResourceManager {
+    /// The cached textures
+    pub textures: ResourceCache<String, Texture>,
+    /// The cached fonts
+    pub fonts: ResourceCache<String, Font>,
+    /// The cached sound buffers
+    pub sound_buffers: ResourceCache<String, SoundBuffer>,
+    /// The cached images
+    pub images: ResourceCache<String, Image>,
+    /// The cached shaders
+    pub shaders: ResourceCache<String, Shader>
+}
+
+impl ResourceManager {
+    /// Create a manager with empty caches
+    ///
+    /// Return a new ResourceManager
+    pub fn new() -> ResourceManager {
+        ResourceManager {
+            textures: ResourceCache::new(),
+            fonts: ResourceCache::new(),
+            sound_buffers: ResourceCache::new(),
+            images: ResourceCache::new(),
+            shaders: ResourceCache::new()
+        }
+    }
+
+    /// Load a texture once
+    ///
+    /// # Arguments
+    /// * path - The path of the file
+    ///
+    /// Return Some(handle) or None
+    pub fn load_texture(&mut self, path: &str) -> Option<Rc<RefCell<Texture>>> {
+        self.textures.load_path(path)
+    }
+
+    /// Load a font once
+    ///
+    /// # Arguments
+    /// * path - The path of the file
+    ///
+    /// Return Some(handle) or None
+    pub fn load_font(&mut self, path: &str) -> Option<Rc<RefCell<Font>>> {
+        self.fonts.load_path(path)
+    }
+
+    /// Load a sound buffer once
+    ///
+    /// # Arguments
+    /// * path - The path of the file
+    ///
+    /// Return Some(handle) or None
+    pub fn load_sound_buffer(&mut self, path: &str) -> Option<Rc<RefCell<SoundBuffer>>> {
+        self.sound_buffers.load_path(path)
+    }
+
+    /// Load an image once
+    ///
+    /// # Arguments
+    /// * path - The path of the file
+    ///
+    /// Return Some(handle) or None
+    pub fn load_image(&mut self, path: &str) -> Option<Rc<RefCell<Image>>> {
+        self.images.load_path(path)
+    }
+
+    /// Load a shader once
+    ///
+    /// # Arguments
+    /// * key - The key of the shader
+    /// * vertex_shader - Some(path) of the vertex shader, or None to skip this shader
+    /// * fragment_shader - Some(path) of the fragment shader, or None to skip this shader
+    ///
+    /// Return Some(handle) or None
+    pub fn load_shader(&mut self,
+                       key: &str,
+                       vertex_shader: Option<&str>,
+                       fragment_shader: Option<&str>) -> Option<Rc<RefCell<Shader>>> {
+        let key = key.to_string();
+        match self.shaders.get(&key) {
+            Some(handle) => return Some(handle),
+            None => {}
+        }
+        match Shader::new_from_file(vertex_shader, fragment_shader) {
+            Some(shader) => Some(self.shaders.insert(key, shader)),
+            None => None
+        }
+    }
+
+    /// Remove the resources which have no handle outside of the caches
+    ///
+    /// Return the number of removed resources
+    pub fn unload_unused(&mut self) -> uint {
+        self.textures.unload_unused() +
+        self.fonts.unload_unused() +
+        self.sound_buffers.unload_unused() +
+        self.images.unload_unused() +
+        self.shaders.unload_unused()
+    }
+
+    /// Remove every resource from the caches
+    pub fn clear(&mut self) -> () {
+        self.textures.clear();
+        self.fonts.clear();
+        self.sound_buffers.clear();
+        self.images.clear();
+        self.shaders.clear();
+    }
+
+    /// Estimate the memory used by every cached resource
+    ///
+    /// Return the total size in bytes
+    pub fn get_total_memory_size(&self) -> uint {
+        self.textures.get_total_memory_size() +
+        self.fonts.get_total_memory_size() +
+        self.sound_buffers.get_total_memory_size() +
+        self.images.get_total_memory_size() +
+        self.shaders.get_total_memory_size()
+    }
+}
@@ -0,0 +1,507 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Resources reloaded when their files change
+//!
+//! A HotReload loads a resource from a ResourceSource and shares it through
+//! a Rc<RefCell<T>> handle. When the files of the source are modified, the
+//! resource is loaded again and replaces the content of the handle. If the
+//! new resource can't be loaded, the previous one is kept and an error is
+//! reported.
+//!
+//! Sprites, texts, shapes and sounds keep a pointer to the resource they
+//! were given. A texture which keeps its size is updated in place, so they
+//! see it without anything to do. Otherwise the resource is replaced, and the
+//! objects registered as dependants of the HotReload are given the new
+//! resource. The replaced resources stay alive as long as the HotReload, so
+//! the objects which are not registered keep a valid resource.
+
+pub use self::ReloadEvent::{Reloaded, ReloadFailed};
+
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use std::io::fs;
+use std::mem;
+
+use system::{Clock, Time};
+use graphics::{Texture, Font};
+use graphics::rc::{Shader, Sprite, Text, Shape, RectangleShape, CircleShape, ConvexShape,
+                   PolygonShape};
+use audio::SoundBuffer;
+use audio::rc::Sound;
+
+/// Source of a resource which can be reloaded
+pub trait ResourceSource<T> {
+    /// Get the files the resource is loaded from
+    ///
+    /// Return the paths of the files to watch
+    fn get_files(&self) -> Vec<Path>;
+
+    /// Load the resource from its files
+    ///
+    /// Return Some(resource) or None
+    fn load(&self) -> Option<T>;
+}
+
+/// Resource which can be reloaded
+pub trait Reloadable {
+    /// Update the resource in place with the content of a new one
+    ///
+    /// # Arguments
+    /// * resource - The new resource
+    ///
+    /// Return None if the resource was updated in place, or Some(resource)
+    /// to give the new resource back when it must be replaced
+    fn update_in_place(&mut self, resource: Self) -> Option<Self>;
+}
+
+impl Reloadable for Texture {
+    fn update_in_place(&mut self, resource: Texture) -> Option<Texture> {
+        if resource.get_size() != self.get_size() {
+            return Some(resource);
+        }
+        match resource.copy_to_image() {
+            Some(image) => {
+                self.update_from_image(&image, 0, 0);
+                None
+            },
+            None => Some(resource)
+        }
+    }
+}
+
+impl Reloadable for Font {
+    fn update_in_place(&mut self, resource: Font) -> Option<Font> {
+        Some(resource)
+    }
+}
+
+impl Reloadable for SoundBuffer {
+    fn update_in_place(&mut self, resource: SoundBuffer) -> Option<SoundBuffer> {
+        Some(resource)
+    }
+}
+
+impl Reloadable for Shader {
+    fn update_in_place(&mut self, resource: Shader) -> Option<Shader> {
+        Some(resource)
+    }
+}
+
+/// Object using a resource, given the resource again when it is replaced
+pub trait Dependant<T> {
+    /// Set the resource again after it was replaced
+    ///
+    /// # Arguments
+    /// * resource - The handle to the new resource
+    fn resource_replaced(&mut self, resource: Rc<RefCell<T>>) -> ();
+}
+
+impl Dependant<Texture> for Sprite {
+    fn resource_replaced(&mut self, resource: Rc<RefCell<Texture>>) -> () {
+        self.set_texture(resource, false)
+    }
+}
+
+impl Dependant<Texture> for Shape {
+    fn resource_replaced(&mut self, resource: Rc<RefCell<Texture>>) -> () {
+        self.set_texture(resource, false)
+    }
+}
+
+impl Dependant<Texture> for RectangleShape {
+    fn resource_replaced(&mut self, resource: Rc<RefCell<Texture>>) -> () {
+        self.set_texture(resource, false)
+    }
+}
+
+impl Dependant<Texture> for CircleShape {
+    fn resource_replaced(&mut self, resource: Rc<RefCell<Texture>>) -> () {
+        self.set_texture(resource, false)
+    }
+}
+
+impl Dependant<Texture> for ConvexShape {
+    fn resource_replaced(&mut self, resource: Rc<RefCell<Texture>>) -> () {
+        self.set_texture(resource, false)
+    }
+}
+
+impl Dependant<Texture> for PolygonShape {
+    fn resource_replaced(&mut self, resource: Rc<RefCell<Texture>>) -> () {
+        self.set_texture(resource, false)
+    }
+}
+
+impl Dependant<Font> for Text {
+    fn resource_replaced(&mut self, resource: Rc<RefCell<Font>>) -> () {
+        self.set_font(resource)
+    }
+}
+
+/// Setting the buffer again stops the sound
+impl Dependant<SoundBuffer> for Sound {
+    fn resource_replaced(&mut self, resource: Rc<RefCell<SoundBuffer>>) -> () {
+        self.set_buffer(resource)
+    }
+}
+
+// Dependant registered by a HotReload, without keeping it alive
+#[doc(hidden)]
+trait WeakDependant<T> {
+    // Return false if the dependant was dropped
+    fn resource_replaced(&self, resource: &Rc<RefCell<T>>) -> bool;
+}
+
+impl<T, D: Dependant<T>> WeakDependant<T> for Weak<RefCell<D>> {
+    fn resource_replaced(&self, resource: &Rc<RefCell<T>>) -> bool {
+        match self.upgrade() {
+            Some(dependant) => {
+                dependant.borrow_mut().resource_replaced(resource.clone());
+                true
+            },
+            None => false
+        }
+    }
+}
+
+/// A resource loaded from a single file: a Texture, a Font or a SoundBuffer
+#[deriving(Clone, PartialEq)]
+pub struct FileSource {
+    /// The path of the file
+    pub path: Path
+}
+
+impl FileSource {
+    /// Create a source for a file
+    ///
+    /// # Arguments
+    /// * path - The path of the file
+    ///
+    /// Return a new FileSource
+    pub fn new(path: &str) -> FileSource {
+        FileSource {
+            path: Path::new(path)
+        }
+    }
+}
+
+impl ResourceSource<Texture> for FileSource {
+    fn get_files(&self) -> Vec<Path> {
+        vec![self.path.clone()]
+    }
+
+    fn load(&self) -> Option<Texture> {
+        match self.path.as_str() {
+            Some(path) => Texture::new_from_file(path),
+            None => None
+        }
+    }
+}
+
+impl ResourceSource<Font> for FileSource {
+    fn get_files(&self) -> Vec<Path> {
+        vec![self.path.clone()]
+    }
+
+    fn load(&self) -> Option<Font> {
+        match self.path.as_str() {
+            Some(path) => Font::new_from_file(path),
+            None => None
+        }
+    }
+}
+
+impl ResourceSource<SoundBuffer> for FileSource {
+    fn get_files(&self) -> Vec<Path> {
+        vec![self.path.clone()]
+    }
+
+    fn load(&self) -> Option<SoundBuffer> {
+        match self.path.as_str() {
+            Some(path) => SoundBuffer::new(path),
+            None => None
+        }
+    }
+}
+
+/// A shader loaded from a vertex shader file and a fragment shader file
+#[deriving(Clone, PartialEq)]
+pub struct ShaderSource {
+    /// The path of the vertex shader, or None
+    pub vertex_shader: Option<Path>,
+    /// The path of the fragment shader, or None
+    pub fragment_shader: Option<Path>
+}
+
+impl ShaderSource {
+    /// Create a source for the files of a shader
+    ///
+    /// # Arguments
+    /// * vertex_shader - Some(path) of the vertex shader, or None to skip this shader
+    /// * fragment_shader - Some(path) of the fragment shader, or None to skip this shader
+    ///
+    /// Return a new ShaderSource
+    pub fn new(vertex_shader: Option<&str>, fragment_shader: Option<&str>) -> ShaderSource {
+        ShaderSource {
+            vertex_shader: vertex_shader.map(|path| Path::new(path)),
+            fragment_shader: fragment_shader.map(|path| Path::new(path))
+        }
+    }
+}
+
+impl ResourceSource<Shader> for ShaderSource {
+    fn get_files(&self) -> Vec<Path> {
+        let mut files = Vec::new();
+        for path in self.vertex_shader.iter().chain(self.fragment_shader.iter()) {
+            files.push(path.clone());
+        }
+        files
+    }
+
+    fn load(&self) -> Option<Shader> {
+        let vertex_shader = match self.vertex_shader {
+            Some(ref path) => match path.as_str() {
+                Some(path) => Some(path),
+                None => return None
+            },
+            None => None
+        };
+        let fragment_shader = match self.fragment_shader {
+            Some(ref path) => match path.as_str() {
+                Some(path) => Some(path),
+                None => return None
+            },
+            None => None
+        };
+        Shader::new_from_file(vertex_shader, fragment_shader)
+    }
+}
+
+/// Result of a reload
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub enum ReloadEvent {
+    /// The resource was reloaded
+    Reloaded,
+    /// The resource couldn't be reloaded and the previous one is kept
+    ReloadFailed(String)
+}
+
+/// Resource reloaded when its files change
+///
+/// The objects using the resource are registered with add_dependant, so
+/// that they are given the resource when it is replaced. The replaced
+/// resources are kept alive for the objects which are not registered, until
+/// the HotReload is destroyed: keep it alive as long as the resource is used.
+pub struct HotReload<T, S> {
+    #[doc(hidden)]
+    resource: Rc<RefCell<T>>,
+    #[doc(hidden)]
+    source: S,
+    #[doc(hidden)]
+    files: Vec<Path>,
+    #[doc(hidden)]
+    modification_times: Vec<Option<u64>>,
+    #[doc(hidden)]
+    dependants: Vec<Box<WeakDependant<T> + 'static>>,
+    #[doc(hidden)]
+    replaced: Vec<T>,
+    #[doc(hidden)]
+    poll_interval: Time,
+    #[doc(hidden)]
+    clock: Clock,
+    #[doc(hidden)]
+    last_error: Option<String>
+}
+
+impl<T: Reloadable + 'static, S: ResourceSource<T>> HotReload<T, S> {
+    /// Load a resource and watch its files
+    ///
+    /// # Arguments
+    /// * source - The source of the resource
+    ///
+    /// Return Some(HotReload), or None if the resource can't be loaded
+    pub fn new(source: S) -> Option<HotReload<T, S>> {
+        let resource = match source.load() {
+            Some(resource) => resource,
+            None => return None
+        };
+        let files = source.get_files();
+        let modification_times = get_modification_times(files.as_slice());
+        Some(HotReload {
+            resource: Rc::new(RefCell::new(resource)),
+            source: source,
+            files: files,
+            modification_times: modification_times,
+            dependants: Vec::new(),
+            replaced: Vec::new(),
+            poll_interval: Time::with_milliseconds(500),
+            clock: Clock::new(),
+            last_error: None
+        })
+    }
+
+    /// Get a handle to the resource
+    ///
+    /// Every handle refers to the current resource, even after a reload.
+    ///
+    /// Return the handle
+    pub fn get_handle(&self) -> Rc<RefCell<T>> {
+        self.resource.clone()
+    }
+
+    /// Register an object using the resource
+    ///
+    /// The object is given the resource again each time it is replaced, as
+    /// long as it is alive.
+    ///
+    /// # Arguments
+    /// * dependant - The object using the resource
+    pub fn add_dependant<D: Dependant<T> + 'static>(&mut self, dependant: &Rc<RefCell<D>>) -> () {
+        let dependant: Box<WeakDependant<T> + 'static> = box dependant.downgrade();
+        self.dependants.push(dependant)
+    }
+
+    /// Get the number of registered objects
+    ///
+    /// The objects which were destroyed are forgotten on the next replacement.
+    ///
+    /// Return the number of dependants
+    pub fn get_dependant_count(&self) -> uint {
+        self.dependants.len()
+    }
+
+    /// Get the source of the resource
+    ///
+    /// Return the source
+    pub fn get_source(&self) -> &S {
+        &self.source
+    }
+
+    /// Set the minimum time between two checks of the files
+    ///
+    /// # Arguments
+    /// * interval - The interval, 500 milliseconds by default
+    pub fn set_poll_interval(&mut self, interval: Time) -> () {
+        self.poll_interval = interval
+    }
+
+    /// Get the minimum time between two checks of the files
+    ///
+    /// Return the interval
+    pub fn get_poll_interval(&self) -> Time {
+        Time::with_microseconds(self.poll_interval.as_microseconds())
+    }
+
+    /// Check the files if the poll interval elapsed, and reload the
+    /// resource if they changed
+    ///
+    /// Call it once per frame.
+    ///
+    /// Return Some(event) if a reload was tried, None otherwise
+    pub fn update(&mut self) -> Option<ReloadEvent> {
+        if self.clock.get_elapsed_time() < self.poll_interval {
+            return None;
+        }
+        self.clock.restart();
+        self.poll()
+    }
+
+    /// Check the files now, and reload the resource if they changed
+    ///
+    /// Files which are missing, for instance while an editor saves them,
+    /// are checked again on the next poll.
+    ///
+    /// Return Some(event) if a reload was tried, None otherwise
+    pub fn poll(&mut self) -> Option<ReloadEvent> {
+        let modification_times = get_modification_times(self.files.as_slice());
+        if modification_times == self.modification_times ||
+           modification_times.iter().any(|time| time.is_none()) {
+            return None;
+        }
+        self.modification_times = modification_times;
+        Some(self.reload())
+    }
+
+    /// Reload the resource now
+    ///
+    /// The previous resource is kept if the new one can't be loaded.
+    ///
+    /// Return the result of the reload
+    pub fn reload(&mut self) -> ReloadEvent {
+        match self.source.load() {
+            Some(resource) => {
+                let replacement = self.resource.borrow_mut().update_in_place(resource);
+                match replacement {
+                    Some(resource) => self.replace(resource),
+                    None => {}
+                }
+                self.last_error = None;
+                Reloaded
+            },
+            None => {
+                let mut error = "Cannot reload".to_string();
+                for path in self.files.iter() {
+                    error.push_str(format!(" {}", path.display()).as_slice());
+                }
+                self.last_error = Some(error.clone());
+                ReloadFailed(error)
+            }
+        }
+    }
+
+    /// Get the error of the last reload
+    ///
+    /// Return Some(error), or None if the last reload succeeded
+    pub fn get_last_error(&self) -> Option<&str> {
+        self.last_error.as_ref().map(|error| error.as_slice())
+    }
+
+    /// Get the number of replaced resources kept alive
+    ///
+    /// Return the number of replaced resources
+    pub fn get_replaced_count(&self) -> uint {
+        self.replaced.len()
+    }
+
+    #[doc(hidden)]
+    fn replace(&mut self, resource: T) -> () {
+        let previous = mem::replace(&mut *self.resource.borrow_mut(), resource);
+        let resource = &self.resource;
+        self.dependants.retain(|dependant| dependant.resource_replaced(resource));
+        // Objects which are not dependants may still point to it
+        self.replaced.push(previous);
+    }
+}
+
+// Get the modification time of each file, None if it can't be read
+#[doc(hidden)]
+fn get_modification_times(files: &[Path]) -> Vec<Option<u64>> {
+    files.iter().map(|path| {
+        match fs::stat(path) {
+            Ok(stat) => Some(stat.modified),
+            Err(_) => None
+        }
+    }).collect()
+}
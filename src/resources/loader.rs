@@ -0,0 +1,532 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Loading of assets on worker threads
+//!
+//! An AssetLoader decodes images, sound buffers and fonts on worker threads,
+//! from files or from memory. The textures need the OpenGL context, which
+//! belongs to the thread of the window: their images are decoded on the
+//! workers and turned into textures by update, on the calling thread. The
+//! progress, the state of each asset and the errors can be read at any
+//! time, so that a loading screen can be animated meanwhile.
+
+pub use self::AssetSource::{FromFile, FromMemory};
+pub use self::AssetState::{Pending, Loaded, Failed, Cancelled};
+
+use self::AssetKind::{ImageAsset, TextureAsset, SoundBufferAsset, FontAsset};
+use self::Decoded::{DecodedImage, DecodedSoundBuffer, DecodedFont};
+use self::Asset::{ImageData, TextureData, SoundBufferData, FontData};
+
+use std::io::File;
+use std::mem;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Sender, Receiver};
+use std::thread::Thread;
+
+use graphics::{Image, Texture, Font};
+use audio::SoundBuffer;
+
+/// Origin of the data of an asset
+pub enum AssetSource {
+    /// A file, read by the worker
+    FromFile(Path),
+    /// The content of a file, already in memory
+    ///
+    /// The data of a font is kept by the font, since SFML reads it as long
+    /// as the font is used.
+    FromMemory(Vec<u8>)
+}
+
+/// State of an asset
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub enum AssetState {
+    /// The asset is waiting or being decoded
+    Pending,
+    /// The asset is loaded and can be taken
+    Loaded,
+    /// The asset can't be loaded, with the reason
+    Failed(String),
+    /// The loading was cancelled before the asset was loaded
+    Cancelled
+}
+
+// Kind of an asset
+#[doc(hidden)]
+#[deriving(Clone, PartialEq, Eq, Copy)]
+enum AssetKind {
+    ImageAsset,
+    TextureAsset,
+    SoundBufferAsset,
+    FontAsset
+}
+
+// Asset decoded by a worker
+//
+// It is sent from the worker to the loader through a channel, which needs
+// it to be Send. Image, SoundBuffer and Font only hold the pointer to their
+// CSFML object, which is Send for the compiler. Moving them is sound: SFML
+// lets these objects be used from any thread, one thread at a time, and the
+// worker gives its only reference to the object away when it sends it.
+#[doc(hidden)]
+enum Decoded {
+    DecodedImage(Image),
+    DecodedSoundBuffer(SoundBuffer),
+    DecodedFont(Font)
+}
+
+// Asset ready to be taken
+#[doc(hidden)]
+enum Asset {
+    ImageData(Image),
+    TextureData(Texture),
+    SoundBufferData(SoundBuffer),
+    FontData(Font)
+}
+
+// Asset waiting for a worker
+#[doc(hidden)]
+struct Job {
+    index: uint,
+    kind: AssetKind,
+    source: AssetSource
+}
+
+// State of an asset of the loader
+#[doc(hidden)]
+struct Entry {
+    name: String,
+    kind: AssetKind,
+    state: AssetState,
+    asset: Option<Asset>
+}
+
+/// Loader of assets on worker threads
+pub struct AssetLoader {
+    #[doc(hidden)]
+    entries: Vec<Entry>,
+    #[doc(hidden)]
+    jobs: Vec<Job>,
+    #[doc(hidden)]
+    worker_count: uint,
+    #[doc(hidden)]
+    sender: Sender<(uint, Result<Decoded, String>)>,
+    #[doc(hidden)]
+    receiver: Receiver<(uint, Result<Decoded, String>)>,
+    #[doc(hidden)]
+    cancelled: Arc<AtomicBool>
+}
+
+impl AssetLoader {
+    /// Create a loader
+    ///
+    /// # Arguments
+    /// * worker_count - The number of worker threads, at least 1
+    ///
+    /// Return a new AssetLoader
+    pub fn new(worker_count: uint) -> AssetLoader {
+        let (sender, receiver) = channel();
+        AssetLoader {
+            entries: Vec::new(),
+            jobs: Vec::new(),
+            worker_count: if worker_count == 0 { 1 } else { worker_count },
+            sender: sender,
+            receiver: receiver,
+            cancelled: Arc::new(AtomicBool::new(false))
+        }
+    }
+
+    /// Add an image to load
+    ///
+    /// # Arguments
+    /// * name - The name of the asset
+    /// * source - The origin of the data
+    ///
+    /// Return false if the name is already used
+    pub fn add_image(&mut self, name: &str, source: AssetSource) -> bool {
+        self.add(name, ImageAsset, source)
+    }
+
+    /// Add a texture to load
+    ///
+    /// Its image is decoded by a worker and the texture is created by update.
+    ///
+    /// # Arguments
+    /// * name - The name of the asset
+    /// * source - The origin of the data
+    ///
+    /// Return false if the name is already used
+    pub fn add_texture(&mut self, name: &str, source: AssetSource) -> bool {
+        self.add(name, TextureAsset, source)
+    }
+
+    /// Add a sound buffer to load
+    ///
+    /// # Arguments
+    /// * name - The name of the asset
+    /// * source - The origin of the data
+    ///
+    /// Return false if the name is already used
+    pub fn add_sound_buffer(&mut self, name: &str, source: AssetSource) -> bool {
+        self.add(name, SoundBufferAsset, source)
+    }
+
+    /// Add a font to load
+    ///
+    /// # Arguments
+    /// * name - The name of the asset
+    /// * source - The origin of the data
+    ///
+    /// Return false if the name is already used
+    pub fn add_font(&mut self, name: &str, source: AssetSource) -> bool {
+        self.add(name, FontAsset, source)
+    }
+
+    /// Start loading the assets added since the last call on the workers
+    pub fn start(&mut self) -> () {
+        if self.jobs.is_empty() || self.cancelled.load(Ordering::SeqCst) {
+            return;
+        }
+        let worker_count = if self.jobs.len() < self.worker_count {
+            self.jobs.len()
+        } else {
+            self.worker_count
+        };
+        let mut batches: Vec<Vec<Job>> = range(0, worker_count).map(|_| Vec::new()).collect();
+        let jobs = mem::replace(&mut self.jobs, Vec::new());
+        for (i, job) in jobs.into_iter().enumerate() {
+            batches[i % worker_count].push(job);
+        }
+        for batch in batches.into_iter() {
+            let cancelled = self.cancelled.clone();
+            let mut worker = Worker {
+                sender: self.sender.clone(),
+                remaining: batch.iter().rev().map(|job| job.index).collect()
+            };
+            Thread::spawn(move || {
+                for job in batch.into_iter() {
+                    if cancelled.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let index = job.index;
+                    let result = decode(job.kind, job.source);
+                    worker.remaining.pop();
+                    if worker.sender.send_opt((index, result)).is_err() {
+                        break;
+                    }
+                }
+            }).detach();
+        }
+    }
+
+    /// Finish the assets decoded by the workers
+    ///
+    /// The textures are created here, call it from the thread which owns
+    /// the window, once per frame while loading. It never blocks.
+    ///
+    /// Return the number of assets finished by this call
+    pub fn update(&mut self) -> uint {
+        let mut finished = 0;
+        loop {
+            let (index, result) = match self.receiver.try_recv() {
+                Ok(message) => message,
+                Err(_) => break
+            };
+            if self.entries[index].state != Pending {
+                continue;
+            }
+            let kind = self.entries[index].kind;
+            let (state, asset) = match finish(kind, result) {
+                Ok(asset) => (Loaded, Some(asset)),
+                Err(error) => (Failed(error), None)
+            };
+            let entry = &mut self.entries[index];
+            entry.state = state;
+            entry.asset = asset;
+            finished += 1;
+        }
+        finished
+    }
+
+    /// Cancel the loading of the assets which are not loaded yet
+    ///
+    /// The workers stop after the asset they are decoding.
+    pub fn cancel(&mut self) -> () {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.jobs.clear();
+        for entry in self.entries.iter_mut() {
+            if entry.state == Pending {
+                entry.state = Cancelled;
+            }
+        }
+    }
+
+    /// Tell whether the loading was cancelled
+    ///
+    /// Return true if cancel was called
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Get the progress of the loading
+    ///
+    /// Return the part of the assets which are finished, from 0 to 1
+    pub fn get_progress(&self) -> f32 {
+        if self.entries.is_empty() {
+            1.
+        } else {
+            self.get_finished_count() as f32 / self.entries.len() as f32
+        }
+    }
+
+    /// Get the number of assets which are loaded, failed or cancelled
+    ///
+    /// Return the number of finished assets
+    pub fn get_finished_count(&self) -> uint {
+        self.entries.iter().filter(|entry| entry.state != Pending).count()
+    }
+
+    /// Get the number of assets added to the loader
+    ///
+    /// Return the number of assets
+    pub fn get_total_count(&self) -> uint {
+        self.entries.len()
+    }
+
+    /// Tell whether every asset is finished
+    ///
+    /// Return true if no asset is pending
+    pub fn is_finished(&self) -> bool {
+        self.entries.iter().all(|entry| entry.state != Pending)
+    }
+
+    /// Get the state of an asset
+    ///
+    /// # Arguments
+    /// * name - The name of the asset
+    ///
+    /// Return Some(state), or None if there is no asset with this name
+    pub fn get_state(&self, name: &str) -> Option<AssetState> {
+        self.find(name).map(|index| self.entries[index].state.clone())
+    }
+
+    /// Get the errors of the assets which can't be loaded
+    ///
+    /// Return the (name, error) pairs
+    pub fn get_errors(&self) -> Vec<(String, String)> {
+        let mut errors = Vec::new();
+        for entry in self.entries.iter() {
+            match entry.state {
+                Failed(ref error) => errors.push((entry.name.clone(), error.clone())),
+                _ => {}
+            }
+        }
+        errors
+    }
+
+    /// Take a loaded image
+    ///
+    /// # Arguments
+    /// * name - The name of the asset
+    ///
+    /// Return Some(image), or None if it is not loaded or already taken
+    pub fn take_image(&mut self, name: &str) -> Option<Image> {
+        match self.take(name) {
+            Some(ImageData(image)) => Some(image),
+            _ => None
+        }
+    }
+
+    /// Take a loaded texture
+    ///
+    /// # Arguments
+    /// * name - The name of the asset
+    ///
+    /// Return Some(texture), or None if it is not loaded or already taken
+    pub fn take_texture(&mut self, name: &str) -> Option<Texture> {
+        match self.take(name) {
+            Some(TextureData(texture)) => Some(texture),
+            _ => None
+        }
+    }
+
+    /// Take a loaded sound buffer
+    ///
+    /// # Arguments
+    /// * name - The name of the asset
+    ///
+    /// Return Some(sound_buffer), or None if it is not loaded or already taken
+    pub fn take_sound_buffer(&mut self, name: &str) -> Option<SoundBuffer> {
+        match self.take(name) {
+            Some(SoundBufferData(sound_buffer)) => Some(sound_buffer),
+            _ => None
+        }
+    }
+
+    /// Take a loaded font
+    ///
+    /// # Arguments
+    /// * name - The name of the asset
+    ///
+    /// Return Some(font), or None if it is not loaded or already taken
+    pub fn take_font(&mut self, name: &str) -> Option<Font> {
+        match self.take(name) {
+            Some(FontData(font)) => Some(font),
+            _ => None
+        }
+    }
+
+    #[doc(hidden)]
+    fn add(&mut self, name: &str, kind: AssetKind, source: AssetSource) -> bool {
+        if self.find(name).is_some() {
+            return false;
+        }
+        let index = self.entries.len();
+        let state = if self.cancelled.load(Ordering::SeqCst) { Cancelled } else { Pending };
+        self.entries.push(Entry {
+            name: name.to_string(),
+            kind: kind,
+            state: state,
+            asset: None
+        });
+        if state == Pending {
+            self.jobs.push(Job {
+                index: index,
+                kind: kind,
+                source: source
+            });
+        }
+        true
+    }
+
+    #[doc(hidden)]
+    fn find(&self, name: &str) -> Option<uint> {
+        self.entries.iter().position(|entry| entry.name.as_slice() == name)
+    }
+
+    #[doc(hidden)]
+    fn take(&mut self, name: &str) -> Option<Asset> {
+        match self.find(name) {
+            Some(index) => self.entries[index].asset.take(),
+            None => None
+        }
+    }
+}
+
+// Jobs of a worker which are not reported yet
+//
+// They are reported as failed when the worker stops before them, even if
+// it panics, so that they don't stay pending forever. The jobs left when
+// the loading is cancelled are already cancelled and the reports ignored.
+#[doc(hidden)]
+struct Worker {
+    sender: Sender<(uint, Result<Decoded, String>)>,
+    // Indices of the jobs, the next one last
+    remaining: Vec<uint>
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) -> () {
+        for &index in self.remaining.iter().rev() {
+            let error = "The worker loading the asset stopped".to_string();
+            if self.sender.send_opt((index, Err(error))).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+// Decode an asset, on a worker
+#[doc(hidden)]
+fn decode(kind: AssetKind, source: AssetSource) -> Result<Decoded, String> {
+    if kind == FontAsset {
+        return decode_font(source);
+    }
+    let (data, origin) = match source {
+        FromFile(path) => match File::open(&path).read_to_end() {
+            Ok(data) => (data, path.display().to_string()),
+            Err(error) => return Err(format!("Cannot read {}: {}", path.display(), error))
+        },
+        FromMemory(data) => (data, "memory".to_string())
+    };
+    if data.is_empty() {
+        return Err(format!("No data in {}", origin));
+    }
+    match kind {
+        SoundBufferAsset => match SoundBuffer::new_from_memory(data.as_slice()) {
+            Some(sound_buffer) => Ok(DecodedSoundBuffer(sound_buffer)),
+            None => Err(format!("Cannot decode the sound in {}", origin))
+        },
+        _ => match Image::new_from_memory(data.as_slice()) {
+            Some(image) => Ok(DecodedImage(image)),
+            None => Err(format!("Cannot decode the image in {}", origin))
+        }
+    }
+}
+
+// Decode a font, on a worker
+#[doc(hidden)]
+fn decode_font(source: AssetSource) -> Result<Decoded, String> {
+    match source {
+        FromFile(path) => {
+            let font = match path.as_str() {
+                Some(path) => Font::new_from_file(path),
+                None => None
+            };
+            match font {
+                Some(font) => Ok(DecodedFont(font)),
+                None => Err(format!("Cannot load the font {}", path.display()))
+            }
+        },
+        FromMemory(data) => {
+            if data.is_empty() {
+                return Err("No data in memory".to_string());
+            }
+            // The font reads its data while it is used, so it keeps it
+            match Font::new_from_vec(data) {
+                Some(font) => Ok(DecodedFont(font)),
+                None => Err("Cannot load the font in memory".to_string())
+            }
+        }
+    }
+}
+
+// Turn a decoded asset into a finished one, on the thread of the loader
+#[doc(hidden)]
+fn finish(kind: AssetKind, result: Result<Decoded, String>) -> Result<Asset, String> {
+    match result {
+        Ok(DecodedImage(image)) => {
+            if kind != TextureAsset {
+                return Ok(ImageData(image));
+            }
+            match Texture::new_from_image(&image) {
+                Some(texture) => Ok(TextureData(texture)),
+                None => Err("Cannot create the texture".to_string())
+            }
+        },
+        Ok(DecodedSoundBuffer(sound_buffer)) => Ok(SoundBufferData(sound_buffer)),
+        Ok(DecodedFont(font)) => Ok(FontData(font)),
+        Err(error) => Err(error)
+    }
+}
@@ -0,0 +1,40 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Management of the resources of an application
+//!
+//! Caches loading each resource once, resources reloaded when their files
+//! change, loading of assets on worker threads and asset packs.
+
+pub use resources::hot_reload::{HotReload, ResourceSource, Reloadable, Dependant, FileSource,
+                                ShaderSource, ReloadEvent, Reloaded, ReloadFailed};
+pub use resources::cache::{ResourceCache, ResourceManager, Resource};
+pub use resources::loader::{AssetLoader, AssetSource, FromFile, FromMemory, AssetState, Pending,
+                            Loaded, Failed, Cancelled};
+pub use resources::pack::{Pack, PackWriter, PackEntry, PackCompression, Stored, Deflated};
+
+mod hot_reload;
+mod cache;
+mod loader;
+mod pack;
@@ -0,0 +1,688 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Asset packs
+//!
+//! A pack gathers many assets in a single file, so that they can be shipped
+//! and loaded together and can't be edited by accident. Each entry is stored
+//! as is or compressed with deflate, and is checked with a CRC-32 when it is
+//! read. Packs are built with a PackWriter or with the rsfml-pack tool, and
+//! read with a Pack, which gives the data of the entries to the new_from_memory
+//! constructors.
+//!
+//! The integers are little endian. A pack starts with a header:
+//!
+//! * the magic bytes `RSFMLPAK`
+//! * the version of the format, a u32 (1)
+//! * the number of entries, a u32
+//! * the offset of the index, a u64
+//!
+//! Then come the data of the entries and the index, which holds for each
+//! entry:
+//!
+//! * the length of its name, a u16, and its name in UTF-8, with / as separator
+//! * its compression, a u8 (0 stored, 1 deflated)
+//! * the offset of its data, a u64
+//! * the size of its data in the pack, a u64
+//! * the size of its data once decompressed, a u64
+//! * the CRC-32 of its data once decompressed, a u32
+//!
+//! The pack ends with the CRC-32 of the index, a u32.
+
+pub use self::PackCompression::{Stored, Deflated};
+
+use self::EntryData::{Borrowed, Owned};
+
+use std::cmp;
+use std::collections::HashMap;
+use std::io::{mod, File, BufReader, MemReader, IoResult, IoError};
+use std::io::fs::{mod, PathExtensions};
+use libc::{c_int, c_void, size_t};
+use flate;
+
+use graphics::{Image, Texture, Font, Shader};
+use audio::{SoundBuffer, Music};
+
+#[doc(hidden)]
+static MAGIC: &'static [u8] = b"RSFMLPAK";
+#[doc(hidden)]
+static VERSION: u32 = 1;
+#[doc(hidden)]
+static HEADER_SIZE: u64 = 24;
+// Size of an entry of the index with a name of one byte
+#[doc(hidden)]
+static MIN_INDEX_ENTRY_SIZE: uint = 32;
+// Maximum compression ratio of deflate
+#[doc(hidden)]
+static MAX_DEFLATE_RATIO: u64 = 1032;
+
+// Provided by miniz, which is linked by the flate crate
+#[doc(hidden)]
+extern {
+    fn tinfl_decompress_mem_to_mem(out_buf: *mut c_void,
+                                   out_buf_len: size_t,
+                                   src_buf: *const c_void,
+                                   src_buf_len: size_t,
+                                   flags: c_int) -> size_t;
+}
+
+/// Compression of an entry of a pack
+#[deriving(Clone, PartialEq, Eq, Show, Copy)]
+pub enum PackCompression {
+    /// The data is stored as is
+    Stored,
+    /// The data is compressed with deflate
+    Deflated
+}
+
+/// Description of an entry of a pack
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct PackEntry {
+    /// The name of the entry, with / as separator
+    pub name: String,
+    /// The compression of the data
+    pub compression: PackCompression,
+    /// The offset of the data in the pack
+    pub offset: u64,
+    /// The size of the data in the pack
+    pub stored_size: u64,
+    /// The size of the data once decompressed
+    pub size: u64,
+    /// The CRC-32 of the data once decompressed
+    pub checksum: u32
+}
+
+// Data of an entry, borrowed from the pack when it is stored
+#[doc(hidden)]
+enum EntryData<'a> {
+    Borrowed(&'a [u8]),
+    Owned(Vec<u8>)
+}
+
+impl<'a> EntryData<'a> {
+    #[doc(hidden)]
+    fn as_slice(&self) -> &[u8] {
+        match *self {
+            Borrowed(data) => data,
+            Owned(ref data) => data.as_slice()
+        }
+    }
+
+    #[doc(hidden)]
+    fn into_vec(self) -> Vec<u8> {
+        match self {
+            Borrowed(data) => data.to_vec(),
+            Owned(data) => data
+        }
+    }
+}
+
+/// Reader of a pack
+///
+/// The whole pack is kept in memory.
+pub struct Pack {
+    #[doc(hidden)]
+    data: Vec<u8>,
+    #[doc(hidden)]
+    entries: Vec<PackEntry>,
+    #[doc(hidden)]
+    names: HashMap<String, uint>
+}
+
+impl Pack {
+    /// Open a pack file
+    ///
+    /// # Arguments
+    /// * path - The path of the pack
+    ///
+    /// Return Ok(Pack), or the error if the file can't be read or is not
+    /// a valid pack
+    pub fn open(path: &Path) -> IoResult<Pack> {
+        let data = try!(File::open(path).read_to_end());
+        Pack::from_memory(data)
+    }
+
+    /// Read a pack from memory
+    ///
+    /// # Arguments
+    /// * data - The content of the pack file
+    ///
+    /// Return Ok(Pack), or the error if the data is not a valid pack
+    pub fn from_memory(data: Vec<u8>) -> IoResult<Pack> {
+        let (entry_count, index_offset) = {
+            let mut reader = BufReader::new(data.as_slice());
+            if try!(reader.read_exact(MAGIC.len())).as_slice() != MAGIC {
+                return Err(invalid_pack("not a pack"));
+            }
+            if try!(reader.read_le_u32()) != VERSION {
+                return Err(invalid_pack("unsupported version"));
+            }
+            (try!(reader.read_le_u32()), try!(reader.read_le_u64()))
+        };
+        let data_size = data.len() as u64;
+        let index_end = index_offset.checked_add(4);
+        if index_offset < HEADER_SIZE || index_end.map_or(true, |end| end > data_size) {
+            return Err(invalid_pack("index out of the pack"));
+        }
+
+        let mut entries = Vec::new();
+        let mut names = HashMap::new();
+        {
+            let index = data.slice(index_offset as uint, data.len() - 4);
+            let checksum = try!(BufReader::new(data.slice_from(data.len() - 4)).read_le_u32());
+            if crc32(index) != checksum {
+                return Err(invalid_pack("corrupted index"));
+            }
+            // The count comes from the file, the index bounds what it can hold
+            entries.reserve(cmp::min(entry_count as uint, index.len() / MIN_INDEX_ENTRY_SIZE));
+            let mut reader = BufReader::new(index);
+            for i in range(0, entry_count as uint) {
+                let name_length = try!(reader.read_le_u16()) as uint;
+                let name = match String::from_utf8(try!(reader.read_exact(name_length))) {
+                    Ok(name) => name,
+                    Err(_) => return Err(invalid_pack("entry name is not UTF-8"))
+                };
+                let compression = match try!(reader.read_u8()) {
+                    0 => Stored,
+                    1 => Deflated,
+                    _ => return Err(invalid_pack("unknown compression"))
+                };
+                let entry = PackEntry {
+                    name: name,
+                    compression: compression,
+                    offset: try!(reader.read_le_u64()),
+                    stored_size: try!(reader.read_le_u64()),
+                    size: try!(reader.read_le_u64()),
+                    checksum: try!(reader.read_le_u32())
+                };
+                let entry_end = entry.offset.checked_add(entry.stored_size);
+                if entry.offset < HEADER_SIZE || entry_end.map_or(true, |end| end > index_offset) {
+                    return Err(invalid_pack("entry out of the pack"));
+                }
+                let max_size = match entry.compression {
+                    Stored => Some(entry.stored_size),
+                    Deflated => entry.stored_size.checked_mul(MAX_DEFLATE_RATIO)
+                };
+                if max_size.map_or(false, |max_size| entry.size > max_size) {
+                    return Err(invalid_pack("entry size larger than its data allows"));
+                }
+                if names.insert(entry.name.clone(), i).is_some() {
+                    return Err(invalid_pack("duplicated entry"));
+                }
+                entries.push(entry);
+            }
+        }
+
+        Ok(Pack {
+            data: data,
+            entries: entries,
+            names: names
+        })
+    }
+
+    /// Get the number of entries of the pack
+    ///
+    /// Return the number of entries
+    pub fn len(&self) -> uint {
+        self.entries.len()
+    }
+
+    /// Tell whether the pack has no entries
+    ///
+    /// Return true if the pack is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Tell whether the pack has an entry
+    ///
+    /// # Arguments
+    /// * name - The name of the entry
+    ///
+    /// Return true if the entry exists
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.contains_key(name)
+    }
+
+    /// Get the entries of the pack, in the order they were added
+    ///
+    /// Return a slice of the entries
+    pub fn get_entries(&self) -> &[PackEntry] {
+        self.entries.as_slice()
+    }
+
+    /// Get the description of an entry
+    ///
+    /// # Arguments
+    /// * name - The name of the entry
+    ///
+    /// Return Some(entry), or None if there is no entry with this name
+    pub fn get_entry(&self, name: &str) -> Option<&PackEntry> {
+        self.names.get(name).map(|&index| &self.entries[index])
+    }
+
+    /// Read the data of an entry
+    ///
+    /// # Arguments
+    /// * name - The name of the entry
+    ///
+    /// Return Ok(data), or the error if the entry doesn't exist, can't be
+    /// decompressed or is corrupted
+    pub fn read(&self, name: &str) -> IoResult<Vec<u8>> {
+        match try!(self.get_data(name)) {
+            Borrowed(data) => Ok(data.to_vec()),
+            Owned(data) => Ok(data)
+        }
+    }
+
+    /// Get the data of a stored entry without copying it
+    ///
+    /// # Arguments
+    /// * name - The name of the entry
+    ///
+    /// Return Some(data), or None if the entry doesn't exist, is compressed
+    /// or is corrupted
+    pub fn get_slice(&self, name: &str) -> Option<&[u8]> {
+        match self.get_entry(name) {
+            Some(entry) if entry.compression == Stored => {},
+            _ => return None
+        }
+        match self.get_data(name) {
+            Ok(Borrowed(data)) => Some(data),
+            _ => None
+        }
+    }
+
+    /// Open a stream on the data of an entry
+    ///
+    /// # Arguments
+    /// * name - The name of the entry
+    ///
+    /// Return Ok(stream), which implements Reader and Seek, or the error
+    pub fn open_stream(&self, name: &str) -> IoResult<MemReader> {
+        self.read(name).map(MemReader::new)
+    }
+
+    /// Load an image from an entry
+    ///
+    /// # Arguments
+    /// * name - The name of the entry
+    ///
+    /// Return Some(Image) or None
+    pub fn load_image(&self, name: &str) -> Option<Image> {
+        self.get_non_empty_data(name).and_then(|data| Image::new_from_memory(data.as_slice()))
+    }
+
+    /// Load a texture from an entry
+    ///
+    /// # Arguments
+    /// * name - The name of the entry
+    ///
+    /// Return Some(Texture) or None
+    pub fn load_texture(&self, name: &str) -> Option<Texture> {
+        self.load_image(name).and_then(|image| Texture::new_from_image(&image))
+    }
+
+    /// Load a sound buffer from an entry
+    ///
+    /// # Arguments
+    /// * name - The name of the entry
+    ///
+    /// Return Some(SoundBuffer) or None
+    pub fn load_sound_buffer(&self, name: &str) -> Option<SoundBuffer> {
+        self.get_non_empty_data(name).and_then(|data| SoundBuffer::new_from_memory(data.as_slice()))
+    }
+
+    /// Load a font from an entry
+    ///
+    /// SFML reads the data of a font as long as the font is used, so the
+    /// font keeps a copy of the data.
+    ///
+    /// # Arguments
+    /// * name - The name of the entry
+    ///
+    /// Return Some(Font) or None
+    pub fn load_font(&self, name: &str) -> Option<Font> {
+        self.get_non_empty_data(name).and_then(|data| Font::new_from_vec(data.into_vec()))
+    }
+
+    /// Load a music from an entry
+    ///
+    /// A music is streamed from its data while it plays, so the music keeps
+    /// a copy of the data.
+    ///
+    /// # Arguments
+    /// * name - The name of the entry
+    ///
+    /// Return Some(Music) or None
+    pub fn load_music(&self, name: &str) -> Option<Music> {
+        self.get_non_empty_data(name).and_then(|data| Music::new_from_vec(data.into_vec()))
+    }
+
+    /// Load a shader from the entries holding its sources
+    ///
+    /// # Arguments
+    /// * vertex_shader - The name of the entry of the vertex shader, or None
+    /// * fragment_shader - The name of the entry of the fragment shader, or None
+    ///
+    /// Return Some(Shader) or None
+    pub fn load_shader<'s>(&self,
+                           vertex_shader: Option<&str>,
+                           fragment_shader: Option<&str>) -> Option<Shader<'s>> {
+        let vertex_source = match vertex_shader {
+            Some(name) => match self.read_string(name) {
+                Some(source) => Some(source),
+                None => return None
+            },
+            None => None
+        };
+        let fragment_source = match fragment_shader {
+            Some(name) => match self.read_string(name) {
+                Some(source) => Some(source),
+                None => return None
+            },
+            None => None
+        };
+        Shader::new_from_memory(vertex_source.as_ref().map(|source| source.as_slice()),
+                                fragment_source.as_ref().map(|source| source.as_slice()))
+    }
+
+    #[doc(hidden)]
+    fn get_data(&self, name: &str) -> IoResult<EntryData> {
+        let entry = match self.get_entry(name) {
+            Some(entry) => entry,
+            None => return Err(IoError {
+                kind: io::FileNotFound,
+                desc: "no such entry in the pack",
+                detail: Some(name.to_string())
+            })
+        };
+        let stored = self.data.slice(entry.offset as uint,
+                                     (entry.offset + entry.stored_size) as uint);
+        let data = match entry.compression {
+            Stored => Borrowed(stored),
+            Deflated => match inflate(stored, entry.size as uint) {
+                Some(data) => Owned(data),
+                None => return Err(invalid_entry("cannot decompress the entry", name))
+            }
+        };
+        if data.as_slice().len() as u64 != entry.size || crc32(data.as_slice()) != entry.checksum {
+            return Err(invalid_entry("corrupted entry", name));
+        }
+        Ok(data)
+    }
+
+    #[doc(hidden)]
+    fn get_non_empty_data(&self, name: &str) -> Option<EntryData> {
+        match self.get_data(name) {
+            Ok(data) => if data.as_slice().is_empty() { None } else { Some(data) },
+            Err(_) => None
+        }
+    }
+
+    #[doc(hidden)]
+    fn read_string(&self, name: &str) -> Option<String> {
+        match self.read(name) {
+            Ok(data) => String::from_utf8(data).ok(),
+            Err(_) => None
+        }
+    }
+}
+
+// Entry waiting to be written
+#[doc(hidden)]
+struct PendingEntry {
+    name: String,
+    compression: PackCompression,
+    size: u64,
+    checksum: u32,
+    data: Vec<u8>
+}
+
+/// Builder of packs
+///
+/// The entries are compressed when they are added and kept in memory until
+/// the pack is written.
+pub struct PackWriter {
+    #[doc(hidden)]
+    entries: Vec<PendingEntry>,
+    #[doc(hidden)]
+    names: HashMap<String, uint>
+}
+
+impl PackWriter {
+    /// Create an empty pack
+    ///
+    /// Return a new PackWriter
+    pub fn new() -> PackWriter {
+        PackWriter {
+            entries: Vec::new(),
+            names: HashMap::new()
+        }
+    }
+
+    /// Add an entry from memory
+    ///
+    /// A compressed entry is stored as is if deflate doesn't make it smaller.
+    ///
+    /// # Arguments
+    /// * name - The name of the entry, with / as separator
+    /// * data - The data of the entry
+    /// * compress - Compress the data with deflate
+    ///
+    /// Return Ok, or the error if the name is invalid or already used
+    pub fn add_memory(&mut self, name: &str, data: &[u8], compress: bool) -> IoResult<()> {
+        if name.is_empty() || name.len() > 0xFFFF {
+            return Err(invalid_entry("invalid entry name", name));
+        }
+        if self.names.contains_key(name) {
+            return Err(invalid_entry("duplicated entry", name));
+        }
+        let deflated = if compress { flate::deflate_bytes(data) } else { None };
+        let (compression, stored) = match deflated {
+            Some(ref deflated) if deflated.as_slice().len() < data.len() => {
+                (Deflated, deflated.as_slice().to_vec())
+            },
+            _ => (Stored, data.to_vec())
+        };
+        self.names.insert(name.to_string(), self.entries.len());
+        self.entries.push(PendingEntry {
+            name: name.to_string(),
+            compression: compression,
+            size: data.len() as u64,
+            checksum: crc32(data),
+            data: stored
+        });
+        Ok(())
+    }
+
+    /// Add an entry from a file
+    ///
+    /// # Arguments
+    /// * name - The name of the entry, with / as separator
+    /// * path - The path of the file
+    /// * compress - Compress the data with deflate
+    ///
+    /// Return Ok, or the error if the file can't be read or the name is
+    /// invalid or already used
+    pub fn add_file(&mut self, name: &str, path: &Path, compress: bool) -> IoResult<()> {
+        let data = try!(File::open(path).read_to_end());
+        self.add_memory(name, data.as_slice(), compress)
+    }
+
+    /// Add every file of a directory and of its sub directories
+    ///
+    /// The entries are named after the paths of the files relative to the
+    /// directory, and added in the order of their names.
+    ///
+    /// # Arguments
+    /// * directory - The path of the directory
+    /// * compress - Compress the data with deflate
+    ///
+    /// Return Ok(number of files added), or the error
+    pub fn add_directory(&mut self, directory: &Path, compress: bool) -> IoResult<uint> {
+        let mut files = Vec::new();
+        for path in try!(fs::walk_dir(directory)) {
+            if !path.is_file() {
+                continue;
+            }
+            let name = match path.path_relative_from(directory) {
+                Some(relative) => match relative.as_str() {
+                    Some(name) => name.replace("\\", "/"),
+                    None => return Err(invalid_entry("file name is not UTF-8",
+                                                     path.display().to_string().as_slice()))
+                },
+                None => continue
+            };
+            files.push((name, path));
+        }
+        files.sort_by(|&(ref a, _), &(ref b, _)| a.cmp(b));
+        for &(ref name, ref path) in files.iter() {
+            try!(self.add_file(name.as_slice(), path, compress));
+        }
+        Ok(files.len())
+    }
+
+    /// Get the number of entries
+    ///
+    /// Return the number of entries
+    pub fn len(&self) -> uint {
+        self.entries.len()
+    }
+
+    /// Tell whether the pack has no entries
+    ///
+    /// Return true if no entry was added
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the size of the data of the entries once decompressed
+    ///
+    /// Return the size in bytes
+    pub fn get_size(&self) -> u64 {
+        self.entries.iter().fold(0, |size, entry| size + entry.size)
+    }
+
+    /// Get the size of the data of the entries in the pack
+    ///
+    /// Return the size in bytes
+    pub fn get_stored_size(&self) -> u64 {
+        self.entries.iter().fold(0, |size, entry| size + entry.data.len() as u64)
+    }
+
+    /// Write the pack
+    ///
+    /// # Arguments
+    /// * writer - The writer receiving the pack
+    ///
+    /// Return Ok, or the error of the writer
+    pub fn write_to<W: Writer>(&self, writer: &mut W) -> IoResult<()> {
+        let mut index: Vec<u8> = Vec::new();
+        let mut offset = HEADER_SIZE;
+        for entry in self.entries.iter() {
+            try!(index.write_le_u16(entry.name.len() as u16));
+            try!(index.write(entry.name.as_bytes()));
+            try!(index.write_u8(match entry.compression { Stored => 0, Deflated => 1 }));
+            try!(index.write_le_u64(offset));
+            try!(index.write_le_u64(entry.data.len() as u64));
+            try!(index.write_le_u64(entry.size));
+            try!(index.write_le_u32(entry.checksum));
+            offset += entry.data.len() as u64;
+        }
+
+        try!(writer.write(MAGIC));
+        try!(writer.write_le_u32(VERSION));
+        try!(writer.write_le_u32(self.entries.len() as u32));
+        try!(writer.write_le_u64(offset));
+        for entry in self.entries.iter() {
+            try!(writer.write(entry.data.as_slice()));
+        }
+        try!(writer.write(index.as_slice()));
+        writer.write_le_u32(crc32(index.as_slice()))
+    }
+
+    /// Write the pack to a file
+    ///
+    /// # Arguments
+    /// * path - The path of the pack file
+    ///
+    /// Return Ok, or the error
+    pub fn write_file(&self, path: &Path) -> IoResult<()> {
+        let mut file = try!(File::create(path));
+        self.write_to(&mut file)
+    }
+}
+
+// Error of a pack which can't be read
+#[doc(hidden)]
+fn invalid_pack(detail: &str) -> IoError {
+    IoError {
+        kind: io::InvalidInput,
+        desc: "invalid pack",
+        detail: Some(detail.to_string())
+    }
+}
+
+// Error of an entry
+#[doc(hidden)]
+fn invalid_entry(desc: &'static str, name: &str) -> IoError {
+    IoError {
+        kind: io::InvalidInput,
+        desc: desc,
+        detail: Some(name.to_string())
+    }
+}
+
+// Decompress deflate data, None if it is invalid or doesn't give exactly size bytes
+#[doc(hidden)]
+fn inflate(data: &[u8], size: uint) -> Option<Vec<u8>> {
+    let mut output: Vec<u8> = Vec::with_capacity(size);
+    // The output buffer doesn't grow: decompression stops once it is full
+    let written = unsafe {
+        tinfl_decompress_mem_to_mem(output.as_mut_ptr() as *mut c_void,
+                                    size as size_t,
+                                    data.as_ptr() as *const c_void,
+                                    data.len() as size_t,
+                                    0)
+    };
+    if written != size as size_t {
+        return None;
+    }
+    unsafe { output.set_len(size) };
+    Some(output)
+}
+
+// CRC-32 (IEEE) of some data
+#[doc(hidden)]
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFFFFFFu32;
+    for &byte in data.iter() {
+        crc ^= byte as u32;
+        for _ in range(0u, 8) {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
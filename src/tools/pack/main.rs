@@ -0,0 +1,84 @@
+//! Build an asset pack from a directory
+//!
+//! Usage:
+//!
+//! * `rsfml-pack [--store] <directory> <pack>` packs every file of the
+//!   directory, compressed unless --store is given
+//! * `rsfml-pack --list <pack>` lists the entries of a pack
+
+#![crate_name = "rsfml_pack"]
+#![crate_type = "bin"]
+
+extern crate rsfml;
+
+use std::os;
+use std::io;
+use rsfml::resources::{Pack, PackWriter, Stored, Deflated};
+
+/* Print the usage */
+fn usage() -> () {
+    let mut stderr = io::stderr();
+    let _ = writeln!(&mut stderr, "Usage: rsfml-pack [--store] <directory> <pack>");
+    let _ = writeln!(&mut stderr, "       rsfml-pack --list <pack>");
+    os::set_exit_status(1);
+}
+
+/* Print an error and fail */
+fn error(message: String) -> () {
+    let _ = writeln!(&mut io::stderr(), "Error, {}", message);
+    os::set_exit_status(1);
+}
+
+/* Build a pack from the files of a directory */
+fn build(directory: &Path, output: &Path, compress: bool) -> () {
+    let mut writer = PackWriter::new();
+    match writer.add_directory(directory, compress) {
+        Ok(_)       => {},
+        Err(err)    => {
+            error(format!("cannot pack {}: {}", directory.display(), err));
+            return;
+        }
+    }
+    match writer.write_file(output) {
+        Ok(_)       => println!("{}: {} entries, {} bytes packed in {} bytes",
+                                output.display(),
+                                writer.len(),
+                                writer.get_size(),
+                                writer.get_stored_size()),
+        Err(err)    => error(format!("cannot write {}: {}", output.display(), err))
+    }
+}
+
+/* List the entries of a pack */
+fn list(path: &Path) -> () {
+    let pack = match Pack::open(path) {
+        Ok(pack)    => pack,
+        Err(err)    => {
+            error(format!("cannot read {}: {}", path.display(), err));
+            return;
+        }
+    };
+    for entry in pack.get_entries().iter() {
+        let compression = match entry.compression {
+            Stored      => "stored",
+            Deflated    => "deflated"
+        };
+        println!("{:>10} {:>10} {:<8} {}",
+                 entry.size,
+                 entry.stored_size,
+                 compression,
+                 entry.name);
+    }
+    println!("{} entries", pack.len());
+}
+
+fn main() -> () {
+    let args = os::args();
+    let args: Vec<&str> = args.iter().map(|arg| arg.as_slice()).collect();
+    match args.slice_from(1) {
+        ["--list", pack]                => list(&Path::new(pack)),
+        ["--store", directory, pack]    => build(&Path::new(directory), &Path::new(pack), false),
+        [directory, pack]               => build(&Path::new(directory), &Path::new(pack), true),
+        _                               => usage()
+    }
+}
@@ -0,0 +1,52 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Bounded trait
+//!
+//! Implemented by each object which has a bounding rectangle.
+
+use graphics::FloatRect;
+
+/// Bounded trait
+///
+/// Implemented by Sprite, Text and the shapes.
+pub trait Bounded {
+    /// Get the local bounding rectangle of the object
+    ///
+    /// The returned rectangle is in local coordinates, which means
+    /// that it ignores the transformations (translation, rotation,
+    /// scale, ...) that are applied to the object.
+    ///
+    /// Return the local bounding rectangle of the object
+    fn get_local_bounds(&self) -> FloatRect;
+
+    /// Get the global bounding rectangle of the object
+    ///
+    /// The returned rectangle is in global coordinates, which means
+    /// that it takes in account the transformations (translation,
+    /// rotation, scale, ...) that are applied to the object.
+    ///
+    /// Return the global bounding rectangle of the object
+    fn get_global_bounds(&self) -> FloatRect;
+}
@@ -27,7 +27,12 @@
 pub use traits::drawable::Drawable;
 pub use traits::wrappable::Wrappable;
 pub use traits::shape_impl::ShapeImpl;
+pub use traits::transformable::Transformable;
+pub use traits::bounded::Bounded;
 
 pub mod drawable;
 pub mod shape_impl;
 pub mod wrappable;
+#[macro_escape]
+pub mod transformable;
+pub mod bounded;
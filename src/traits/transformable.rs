@@ -0,0 +1,227 @@
+/*
+* Rust-SFML - Copyright (c) 2013 Letang Jeremy.
+*
+* The original software, SFML library, is provided by Laurent Gomila.
+*
+* This software is provided 'as-is', without any express or implied warranty.
+* In no event will the authors be held liable for any damages arising from
+* the use of this software.
+*
+* Permission is granted to anyone to use this software for any purpose,
+* including commercial applications, and to alter it and redistribute it
+* freely, subject to the following restrictions:
+*
+* 1. The origin of this software must not be misrepresented; you must not claim
+*    that you wrote the original software. If you use this software in a product,
+*    an acknowledgment in the product documentation would be appreciated but is
+*    not required.
+*
+* 2. Altered source versions must be plainly marked as such, and must not be
+*    misrepresented as being the original software.
+*
+* 3. This notice may not be removed or altered from any source distribution.
+*/
+
+//! Transformable trait
+//!
+//! Implemented by each object which has a position, a rotation, a scale
+//! and an origin, so generic code can move any of them.
+//!
+//! The implementations are generated by the impl_transformable macro, which
+//! forwards each method to the inherent method of the same name.
+
+use graphics::Transform;
+use system::vector2::Vector2f;
+
+/// Transformable trait
+///
+/// Implemented by Sprite, Text, the shapes and graphics::Transformable.
+pub trait Transformable {
+    /// Set the position of the object
+    ///
+    /// # Arguments
+    /// * position - New position
+    fn set_position(&mut self, position: &Vector2f) -> ();
+
+    /// Set the orientation of the object
+    ///
+    /// # Arguments
+    /// * angle - New rotation, in degrees
+    fn set_rotation(&mut self, angle: f32) -> ();
+
+    /// Set the scale factors of the object
+    ///
+    /// # Arguments
+    /// * scale - New scale factors
+    fn set_scale(&mut self, scale: &Vector2f) -> ();
+
+    /// Set the local origin of the object
+    ///
+    /// # Arguments
+    /// * origin - New origin
+    fn set_origin(&mut self, origin: &Vector2f) -> ();
+
+    /// Get the position of the object
+    ///
+    /// Return the current position
+    fn get_position(&self) -> Vector2f;
+
+    /// Get the orientation of the object
+    ///
+    /// Return the current rotation, in degrees
+    fn get_rotation(&self) -> f32;
+
+    /// Get the current scale of the object
+    ///
+    /// Return the current scale factors
+    fn get_scale(&self) -> Vector2f;
+
+    /// Get the local origin of the object
+    ///
+    /// Return the current origin
+    fn get_origin(&self) -> Vector2f;
+
+    /// Move the object by a given offset
+    ///
+    /// # Arguments
+    /// * offset - Offset
+    fn move_(&mut self, offset: &Vector2f) -> ();
+
+    /// Rotate the object
+    ///
+    /// # Arguments
+    /// * angle - Angle of rotation, in degrees
+    fn rotate(&mut self, angle: f32) -> ();
+
+    /// Scale the object
+    ///
+    /// # Arguments
+    /// * factors - Scale factors
+    fn scale(&mut self, factors: &Vector2f) -> ();
+
+    /// Get the combined transform of the object
+    ///
+    /// Return the transform combining the position/rotation/scale/origin of the object
+    fn get_transform(&self) -> Transform;
+
+    /// Get the inverse of the combined transform of the object
+    ///
+    /// Return the inverse of the combined transformations applied to the object
+    fn get_inverse_transform(&self) -> Transform;
+
+    /// Set the position of the object
+    ///
+    /// # Arguments
+    /// * x - New x coordinate
+    /// * y - New y coordinate
+    fn set_position2f(&mut self, x: f32, y: f32) -> () {
+        self.set_position(&Vector2f::new(x, y))
+    }
+
+    /// Set the scale factors of the object
+    ///
+    /// # Arguments
+    /// * scale_x - New x scale factor
+    /// * scale_y - New y scale factor
+    fn set_scale2f(&mut self, scale_x: f32, scale_y: f32) -> () {
+        self.set_scale(&Vector2f::new(scale_x, scale_y))
+    }
+
+    /// Set the local origin of the object
+    ///
+    /// # Arguments
+    /// * x - New x origin coordinate
+    /// * y - New y origin coordinate
+    fn set_origin2f(&mut self, x: f32, y: f32) -> () {
+        self.set_origin(&Vector2f::new(x, y))
+    }
+
+    /// Move the object by a given offset
+    ///
+    /// # Arguments
+    /// * offset_x - Offset x
+    /// * offset_y - Offset y
+    fn move2f(&mut self, offset_x: f32, offset_y: f32) -> () {
+        self.move_(&Vector2f::new(offset_x, offset_y))
+    }
+
+    /// Scale the object
+    ///
+    /// # Arguments
+    /// * factor_x - Scale x factor
+    /// * factor_y - Scale y factor
+    fn scale2f(&mut self, factor_x: f32, factor_y: f32) -> () {
+        self.scale(&Vector2f::new(factor_x, factor_y))
+    }
+}
+
+/// Implement the Transformable trait by forwarding to inherent methods
+///
+/// The type must have an inherent method for each required method of the
+/// trait. Don't import the trait in the module using the macro: a missing
+/// inherent method then fails to build instead of calling the trait method
+/// itself.
+///
+/// # Arguments
+/// * ty - The type implementing the trait
+/// * generics - The generic parameters of the impl, if any
+macro_rules! impl_transformable(
+    ($ty:ty) => (
+        impl_transformable!($ty,)
+    );
+    ($ty:ty, $($generics:tt)*) => (
+        impl<$($generics)*> ::traits::Transformable for $ty {
+            fn set_position(&mut self, position: &::system::vector2::Vector2f) -> () {
+                self.set_position(position)
+            }
+
+            fn set_rotation(&mut self, angle: f32) -> () {
+                self.set_rotation(angle)
+            }
+
+            fn set_scale(&mut self, scale: &::system::vector2::Vector2f) -> () {
+                self.set_scale(scale)
+            }
+
+            fn set_origin(&mut self, origin: &::system::vector2::Vector2f) -> () {
+                self.set_origin(origin)
+            }
+
+            fn get_position(&self) -> ::system::vector2::Vector2f {
+                self.get_position()
+            }
+
+            fn get_rotation(&self) -> f32 {
+                self.get_rotation()
+            }
+
+            fn get_scale(&self) -> ::system::vector2::Vector2f {
+                self.get_scale()
+            }
+
+            fn get_origin(&self) -> ::system::vector2::Vector2f {
+                self.get_origin()
+            }
+
+            fn move_(&mut self, offset: &::system::vector2::Vector2f) -> () {
+                self.move_(offset)
+            }
+
+            fn rotate(&mut self, angle: f32) -> () {
+                self.rotate(angle)
+            }
+
+            fn scale(&mut self, factors: &::system::vector2::Vector2f) -> () {
+                self.scale(factors)
+            }
+
+            fn get_transform(&self) -> ::graphics::Transform {
+                self.get_transform()
+            }
+
+            fn get_inverse_transform(&self) -> ::graphics::Transform {
+                self.get_inverse_transform()
+            }
+        }
+    )
+)